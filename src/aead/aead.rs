@@ -0,0 +1,62 @@
+/// Common interface of the AES-based AEADs in the crate.
+///
+/// # Description
+/// - `Error`: The error type of the algorithm.
+/// - `tag_size`: Bytes `seal` adds to the plaintext.
+/// - `seal`: Encrypt `plaintext` and authenticate it together with `aad`
+///   under `nonce`, returns the ciphertext with the tag.
+/// - `open`: Verify and decrypt the output of `seal`. Fails closed: on a
+///   tag mismatch no plaintext is returned at all.
+///
+/// Where the tag goes (in front for SIV, at the end otherwise) is up to
+/// the algorithm, `open` of the same algorithm is the only reader of the
+/// sealed bytes.
+pub trait Aead {
+    type Error: std::error::Error;
+
+    fn tag_size(&self) -> usize;
+
+    fn seal(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, Self::Error>;
+
+    fn open(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, Self::Error>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ccm::AesCcm;
+    use crate::gcm::AesGcm;
+    use crate::gcm_siv::AesGcmSiv;
+    use crate::siv::AesSiv;
+
+    fn round_trip<A: Aead>(aead: &A, nonce: &[u8]) {
+        let msg = b"the same code for every AEAD";
+
+        let sealed = aead.seal(nonce, b"header", msg).unwrap();
+        assert_eq!((sealed.len()), (msg.len() + aead.tag_size()));
+
+        let opened = aead.open(nonce, b"header", &sealed).unwrap();
+        assert_eq!((opened), (msg.to_vec()));
+        assert!(aead.open(nonce, b"Header", &sealed).is_err());
+    }
+
+    #[test]
+    fn every_aead_round_trips_through_the_trait() {
+        let key = [0x42u8; 32];
+
+        round_trip(&AesGcm::new(&key).unwrap(), &[0x24u8; 12]);
+        round_trip(&AesCcm::new(&key, 8, 4).unwrap(), &[0x24u8; 11]);
+        round_trip(&AesSiv::new(&key).unwrap(), &[0x24u8; 16]);
+        round_trip(&AesGcmSiv::new(&key).unwrap(), &[0x24u8; 12]);
+    }
+}
@@ -1,34 +1,186 @@
-// Finite field multiplication (xTimes).
-fn xtimes(byte: u8) -> u8 {
-    if byte & 0x80 != 0 {
-        (byte << 1) ^ 0x1b
-    } else {
-        byte << 1
-    }
+use crate::aes::{
+    add_round_key, inv_mix_columns, inv_shift_rows, inv_sub_bytes,
+    key_expansion, mix_columns, shift_rows, sub_bytes, AesError
+};
+use crate::block_cipher::BlockCipher;
+
+/// AES block size in bytes, the same for every key size.
+pub const BLOCK_SIZE: usize = 16;
+
+/// AES-128, AES-192 or AES-256, picked by the key length.
+///
+/// # Description
+/// - The key schedule is expanded once in `new`, every block then runs
+///   Nr = 10, 12 or 14 rounds.
+/// - `encrypt_block` is the FIPS 197 Cipher, `decrypt_block` the
+///   InvCipher (not the equivalent inverse cipher, the round keys are
+///   shared).
+///
+/// # Reference
+/// [FIPS 197](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.197-upd1.pdf)
+#[derive(Clone)]
+pub struct Aes {
+    round_keys: Vec<[u8; 16]>,
 }
 
-// Galois field multiplication.
-fn gf_mul(mut a: u8, mut b: u8) -> u8 {
-    let mut result = 0;
+impl Aes {
+    /// Expand a 16, 24 or 32-byte key.
+    pub fn new(key: &[u8]) -> Result<Self, AesError> {
+        match key.len() {
+            16 | 24 | 32 => Ok(Aes {
+                round_keys: key_expansion(key),
+            }),
+            len => Err(AesError::InvalidKeyLength { len }),
+        }
+    }
+
+    /// Number of rounds Nr.
+    pub fn rounds(&self) -> usize {
+        self.round_keys.len() - 1
+    }
+
+    /// Cipher: encrypt one 16-byte block.
+    pub fn encrypt_block(&self, block: &[u8; BLOCK_SIZE]) -> [u8; BLOCK_SIZE] {
+        let nr = self.rounds();
+        let mut state = *block;
 
-    while b != 0 {
-        if b & 1 != 0 {
-            result ^= a;
+        add_round_key(&mut state, &self.round_keys[0]);
+        for round in 1..nr {
+            sub_bytes(&mut state);
+            shift_rows(&mut state);
+            mix_columns(&mut state);
+            add_round_key(&mut state, &self.round_keys[round]);
         }
 
-        a = xtimes(a);
-        b >>= 1;
+        // The last round skips MixColumns.
+        sub_bytes(&mut state);
+        shift_rows(&mut state);
+        add_round_key(&mut state, &self.round_keys[nr]);
+
+        state
     }
 
-    result
+    /// InvCipher: decrypt one 16-byte block.
+    pub fn decrypt_block(&self, block: &[u8; BLOCK_SIZE]) -> [u8; BLOCK_SIZE] {
+        let nr = self.rounds();
+        let mut state = *block;
+
+        add_round_key(&mut state, &self.round_keys[nr]);
+        for round in (1..nr).rev() {
+            inv_shift_rows(&mut state);
+            inv_sub_bytes(&mut state);
+            add_round_key(&mut state, &self.round_keys[round]);
+            inv_mix_columns(&mut state);
+        }
+
+        inv_shift_rows(&mut state);
+        inv_sub_bytes(&mut state);
+        add_round_key(&mut state, &self.round_keys[0]);
+
+        state
+    }
 }
 
-fn main() {
-    let msb_is_1 = xtimes(128);
-    println!("MSB is 1: {}", msb_is_1);
+impl BlockCipher for Aes {
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        let out = Aes::encrypt_block(self, &to_block(block));
+        block.copy_from_slice(&out);
+    }
 
-    let msb_is_0 = xtimes(0);
-    println!("MSB is 0: {}", msb_is_0);
+    fn decrypt_block(&self, block: &mut [u8]) {
+        let out = Aes::decrypt_block(self, &to_block(block));
+        block.copy_from_slice(&out);
+    }
+}
+
+// The modes hand over blocks as slices of exactly BLOCK_SIZE bytes.
+fn to_block(bytes: &[u8]) -> [u8; BLOCK_SIZE] {
+    let mut block = [0u8; BLOCK_SIZE];
+    block.copy_from_slice(bytes);
+    block
+}
 
-    println!("Galois Field Multiplication: {}", gf_mul(18, 19));
-}
\ No newline at end of file
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Plaintext of FIPS 197 Appendix C.
+    const PLAINTEXT: [u8; 16] = [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 
+        0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+    ];
+
+    // Appendix C keys are 00 01 02 ... up to the key length.
+    fn key(len: usize) -> Vec<u8> {
+        (0..len as u8).collect()
+    }
+
+    #[test]
+    fn aes_128_fips_197_appendix_c1() {
+        let aes = Aes::new(&key(16)).unwrap();
+        let expected = [
+            0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 
+            0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a,
+        ];
+
+        assert_eq!((aes.rounds()), (10));
+        assert_eq!((aes.encrypt_block(&PLAINTEXT)), (expected));
+        assert_eq!((aes.decrypt_block(&expected)), (PLAINTEXT));
+    }
+
+    #[test]
+    fn aes_192_fips_197_appendix_c2() {
+        let aes = Aes::new(&key(24)).unwrap();
+        let expected = [
+            0xdd, 0xa9, 0x7c, 0xa4, 0x86, 0x4c, 0xdf, 0xe0, 
+            0x6e, 0xaf, 0x70, 0xa0, 0xec, 0x0d, 0x71, 0x91,
+        ];
+
+        assert_eq!((aes.rounds()), (12));
+        assert_eq!((aes.encrypt_block(&PLAINTEXT)), (expected));
+        assert_eq!((aes.decrypt_block(&expected)), (PLAINTEXT));
+    }
+
+    #[test]
+    fn aes_256_fips_197_appendix_c3() {
+        let aes = Aes::new(&key(32)).unwrap();
+        let expected = [
+            0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf, 
+            0xea, 0xfc, 0x49, 0x90, 0x4b, 0x49, 0x60, 0x89,
+        ];
+
+        assert_eq!((aes.rounds()), (14));
+        assert_eq!((aes.encrypt_block(&PLAINTEXT)), (expected));
+        assert_eq!((aes.decrypt_block(&expected)), (PLAINTEXT));
+    }
+
+    #[test]
+    fn aes_128_fips_197_appendix_b() {
+        let aes = Aes::new(&[
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 
+            0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+        ]).unwrap();
+        let input = [
+            0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 
+            0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07, 0x34,
+        ];
+        let expected = [
+            0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 
+            0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a, 0x0b, 0x32,
+        ];
+
+        assert_eq!((aes.encrypt_block(&input)), (expected));
+    }
+
+    #[test]
+    fn aes_rejects_invalid_key_lengths() {
+        for len in [0, 15, 17, 20, 31, 33, 64] {
+            assert_eq!(
+                (Aes::new(&vec![0u8; len]).err()), 
+                (Some(AesError::InvalidKeyLength { len }))
+            );
+        }
+    }
+}
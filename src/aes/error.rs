@@ -0,0 +1,20 @@
+use std::fmt;
+
+/// Errors of AES key setup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AesError {
+    /// Keys must be 16, 24 or 32 bytes.
+    InvalidKeyLength { len: usize },
+}
+
+impl fmt::Display for AesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AesError::InvalidKeyLength { len } => {
+                write!(f, "AES key length {len} is not 16, 24 or 32 bytes")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AesError {}
@@ -0,0 +1,77 @@
+// GF(2^8) arithmetic modulo the AES polynomial x^8 + x^4 + x^3 + x + 1.
+//
+// Written as `const fn` so the S-boxes can be derived at compile time.
+
+/// Finite field multiplication by x (xTimes).
+pub const fn xtimes(byte: u8) -> u8 {
+    if byte & 0x80 != 0 {
+        (byte << 1) ^ 0x1b
+    } else {
+        byte << 1
+    }
+}
+
+/// Galois field multiplication.
+pub const fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut result = 0;
+
+    while b != 0 {
+        if b & 1 != 0 {
+            result ^= a;
+        }
+
+        a = xtimes(a);
+        b >>= 1;
+    }
+
+    result
+}
+
+/// Multiplicative inverse in GF(2^8), with 0 mapped to 0.
+///
+/// The multiplicative group has order 255, so a^254 = a^-1.
+pub const fn gf_inv(a: u8) -> u8 {
+    // Square-and-multiply over the bits of 254.
+    let mut result = 1;
+    let mut base = a;
+    let mut exp = 254;
+
+    while exp != 0 {
+        if exp & 1 != 0 {
+            result = gf_mul(result, base);
+        }
+
+        base = gf_mul(base, base);
+        exp >>= 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn xtimes_reduces_when_msb_is_set() {
+        // FIPS 197 section 4.2: {57} * {02} = {ae}, {ae} * {02} = {47}.
+        assert_eq!((xtimes(0x57)), (0xae));
+        assert_eq!((xtimes(0xae)), (0x47));
+        assert_eq!((xtimes(0x80)), (0x1b));
+        assert_eq!((xtimes(0x00)), (0x00));
+    }
+
+    #[test]
+    fn gf_mul_fips_197_examples() {
+        assert_eq!((gf_mul(0x57, 0x83)), (0xc1));
+        assert_eq!((gf_mul(0x57, 0x13)), (0xfe));
+    }
+
+    #[test]
+    fn gf_inv_is_the_inverse_of_every_nonzero_byte() {
+        assert_eq!((gf_inv(0x00)), (0x00));
+        for a in 1..=255u8 {
+            assert_eq!((gf_mul(a, gf_inv(a))), (0x01));
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::aes::{ xtimes, SBOX };
+use crate::utils::rotl;
+
+/// AES KeyExpansion.
+///
+/// # Arguments
+/// `key`: 16, 24 or 32 bytes (Nk = 4, 6 or 8 words), checked by the caller.
+///
+/// # Description
+/// - The first Nk words are the key itself.
+/// - Every Nk-th word is RotWord, SubWord and XOR with Rcon of the word
+///   before it; AES-256 also runs SubWord halfway through every Nk words.
+/// - All other words are the previous word XOR the word Nk positions back.
+///
+/// # Returns
+/// Nr + 1 round keys of 16 bytes, Nr = Nk + 6.
+///
+/// # Reference
+/// [FIPS 197](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.197-upd1.pdf),
+/// section 5.2.
+pub fn key_expansion(key: &[u8]) -> Vec<[u8; 16]> {
+    let nk = key.len() / 4;
+    let nr = nk + 6;
+    let total_words = 4 * (nr + 1);
+
+    let mut w: Vec<u32> = Vec::with_capacity(total_words);
+    for i in 0..nk {
+        w.push(
+            (key[4 * i] as u32) << 24
+                | (key[4 * i + 1] as u32) << 16
+                | (key[4 * i + 2] as u32) << 8
+                | key[4 * i + 3] as u32
+        );
+    }
+
+    let mut rcon: u8 = 0x01;
+    for i in nk..total_words {
+        let mut temp = w[i - 1];
+        if i % nk == 0 {
+            temp = sub_word(rotl(temp, 8)) ^ ((rcon as u32) << 24);
+            rcon = xtimes(rcon);
+        } else if nk > 6 && i % nk == 4 {
+            temp = sub_word(temp);
+        }
+
+        w.push(w[i - nk] ^ temp);
+    }
+
+    // Four words per round key, big-endian like the input block.
+    let mut round_keys = Vec::with_capacity(nr + 1);
+    for round in 0..=nr {
+        let mut round_key = [0u8; 16];
+        for c in 0..4 {
+            let word = w[4 * round + c];
+            round_key[4 * c] = (word >> 24) as u8;
+            round_key[4 * c + 1] = (word >> 16) as u8;
+            round_key[4 * c + 2] = (word >> 8) as u8;
+            round_key[4 * c + 3] = word as u8;
+        }
+        round_keys.push(round_key);
+    }
+
+    round_keys
+}
+
+// SubWord: the S-box on each byte of a word.
+fn sub_word(word: u32) -> u32 {
+    (SBOX[(word >> 24) as usize] as u32) << 24
+        | (SBOX[(word >> 16 & 0xff) as usize] as u32) << 16
+        | (SBOX[(word >> 8 & 0xff) as usize] as u32) << 8
+        | SBOX[(word & 0xff) as usize] as u32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn aes_128_key_expansion_fips_197_appendix_a1() {
+        let key = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 
+            0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+        ];
+        let round_keys = key_expansion(&key);
+
+        assert_eq!((round_keys.len()), (11));
+        // w[4..7] and w[40..43].
+        assert_eq!(
+            (round_keys[1]), 
+            ([
+                0xa0, 0xfa, 0xfe, 0x17, 0x88, 0x54, 0x2c, 0xb1, 
+                0x23, 0xa3, 0x39, 0x39, 0x2a, 0x6c, 0x76, 0x05,
+            ])
+        );
+        assert_eq!(
+            (round_keys[10]), 
+            ([
+                0xd0, 0x14, 0xf9, 0xa8, 0xc9, 0xee, 0x25, 0x89, 
+                0xe1, 0x3f, 0x0c, 0xc8, 0xb6, 0x63, 0x0c, 0xa6,
+            ])
+        );
+    }
+
+    #[test]
+    fn aes_192_key_expansion_fips_197_appendix_a2() {
+        let key = [
+            0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 
+            0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90, 0x79, 0xe5, 
+            0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b,
+        ];
+        let round_keys = key_expansion(&key);
+
+        assert_eq!((round_keys.len()), (13));
+        // w[48..51].
+        assert_eq!(
+            (round_keys[12]), 
+            ([
+                0xe9, 0x8b, 0xa0, 0x6f, 0x44, 0x8c, 0x77, 0x3c, 
+                0x8e, 0xcc, 0x72, 0x04, 0x01, 0x00, 0x22, 0x02,
+            ])
+        );
+    }
+
+    #[test]
+    fn aes_256_key_expansion_fips_197_appendix_a3() {
+        let key = [
+            0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 
+            0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d, 0x77, 0x81, 
+            0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 
+            0x2d, 0x98, 0x10, 0xa3, 0x09, 0x14, 0xdf, 0xf4,
+        ];
+        let round_keys = key_expansion(&key);
+
+        assert_eq!((round_keys.len()), (15));
+        // w[56..59].
+        assert_eq!(
+            (round_keys[14]), 
+            ([
+                0xfe, 0x48, 0x90, 0xd1, 0xe6, 0x18, 0x8d, 0x0b, 
+                0x04, 0x6d, 0xf3, 0x44, 0x70, 0x6c, 0x63, 0x1e,
+            ])
+        );
+    }
+}
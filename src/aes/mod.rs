@@ -0,0 +1,17 @@
+pub mod error;
+pub mod gf;
+pub mod sbox;
+pub mod key_expansion;
+pub mod round;
+#[allow(clippy::module_inception)]
+pub mod aes;
+
+pub use error::AesError;
+pub use gf::{ gf_inv, gf_mul, xtimes };
+pub use sbox::{ INV_SBOX, SBOX };
+pub use key_expansion::key_expansion;
+pub use round::{
+    add_round_key, inv_mix_columns, inv_shift_rows, inv_sub_bytes,
+    mix_columns, shift_rows, sub_bytes
+};
+pub use aes::{ Aes, BLOCK_SIZE };
@@ -0,0 +1,129 @@
+use crate::aes::{ gf_mul, xtimes, INV_SBOX, SBOX };
+
+// The state is the 16-byte block itself: byte r + 4c is row r of column c,
+// the column-major order of FIPS 197 section 3.4.
+
+/// AddRoundKey: XOR the round key into the state.
+pub fn add_round_key(state: &mut [u8; 16], round_key: &[u8; 16]) {
+    for i in 0..16 {
+        state[i] ^= round_key[i];
+    }
+}
+
+/// SubBytes: the S-box on every byte.
+pub fn sub_bytes(state: &mut [u8; 16]) {
+    for i in 0..16 {
+        state[i] = SBOX[state[i] as usize];
+    }
+}
+
+/// InvSubBytes: the inverse S-box on every byte.
+pub fn inv_sub_bytes(state: &mut [u8; 16]) {
+    for i in 0..16 {
+        state[i] = INV_SBOX[state[i] as usize];
+    }
+}
+
+/// ShiftRows: row r is rotated left by r columns.
+pub fn shift_rows(state: &mut [u8; 16]) {
+    let old = *state;
+    for r in 1..4 {
+        for c in 0..4 {
+            state[r + 4 * c] = old[r + 4 * ((c + r) % 4)];
+        }
+    }
+}
+
+/// InvShiftRows: row r is rotated right by r columns.
+pub fn inv_shift_rows(state: &mut [u8; 16]) {
+    let old = *state;
+    for r in 1..4 {
+        for c in 0..4 {
+            state[r + 4 * ((c + r) % 4)] = old[r + 4 * c];
+        }
+    }
+}
+
+/// MixColumns: every column times {03}x^3 + {01}x^2 + {01}x + {02}.
+pub fn mix_columns(state: &mut [u8; 16]) {
+    for c in 0..4 {
+        let s0 = state[4 * c];
+        let s1 = state[4 * c + 1];
+        let s2 = state[4 * c + 2];
+        let s3 = state[4 * c + 3];
+
+        // {03} * s = {02} * s ^ s.
+        state[4 * c] = xtimes(s0) ^ xtimes(s1) ^ s1 ^ s2 ^ s3;
+        state[4 * c + 1] = s0 ^ xtimes(s1) ^ xtimes(s2) ^ s2 ^ s3;
+        state[4 * c + 2] = s0 ^ s1 ^ xtimes(s2) ^ xtimes(s3) ^ s3;
+        state[4 * c + 3] = xtimes(s0) ^ s0 ^ s1 ^ s2 ^ xtimes(s3);
+    }
+}
+
+/// InvMixColumns: every column times {0b}x^3 + {0d}x^2 + {09}x + {0e}.
+pub fn inv_mix_columns(state: &mut [u8; 16]) {
+    for c in 0..4 {
+        let s0 = state[4 * c];
+        let s1 = state[4 * c + 1];
+        let s2 = state[4 * c + 2];
+        let s3 = state[4 * c + 3];
+
+        state[4 * c] = gf_mul(s0, 0x0e) ^ gf_mul(s1, 0x0b)
+            ^ gf_mul(s2, 0x0d) ^ gf_mul(s3, 0x09);
+        state[4 * c + 1] = gf_mul(s0, 0x09) ^ gf_mul(s1, 0x0e)
+            ^ gf_mul(s2, 0x0b) ^ gf_mul(s3, 0x0d);
+        state[4 * c + 2] = gf_mul(s0, 0x0d) ^ gf_mul(s1, 0x09)
+            ^ gf_mul(s2, 0x0e) ^ gf_mul(s3, 0x0b);
+        state[4 * c + 3] = gf_mul(s0, 0x0b) ^ gf_mul(s1, 0x0d)
+            ^ gf_mul(s2, 0x09) ^ gf_mul(s3, 0x0e);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // State of FIPS 197 Appendix B, round 1, after SubBytes.
+    const AFTER_SUB_BYTES: [u8; 16] = [
+        0xd4, 0x27, 0x11, 0xae, 0xe0, 0xbf, 0x98, 0xf1, 
+        0xb8, 0xb4, 0x5d, 0xe5, 0x1e, 0x41, 0x52, 0x30,
+    ];
+
+    const AFTER_SHIFT_ROWS: [u8; 16] = [
+        0xd4, 0xbf, 0x5d, 0x30, 0xe0, 0xb4, 0x52, 0xae, 
+        0xb8, 0x41, 0x11, 0xf1, 0x1e, 0x27, 0x98, 0xe5,
+    ];
+
+    const AFTER_MIX_COLUMNS: [u8; 16] = [
+        0x04, 0x66, 0x81, 0xe5, 0xe0, 0xcb, 0x19, 0x9a, 
+        0x48, 0xf8, 0xd3, 0x7a, 0x28, 0x06, 0x26, 0x4c,
+    ];
+
+    #[test]
+    fn shift_rows_fips_197_appendix_b() {
+        let mut state = AFTER_SUB_BYTES;
+        shift_rows(&mut state);
+        assert_eq!((state), (AFTER_SHIFT_ROWS));
+
+        inv_shift_rows(&mut state);
+        assert_eq!((state), (AFTER_SUB_BYTES));
+    }
+
+    #[test]
+    fn mix_columns_fips_197_appendix_b() {
+        let mut state = AFTER_SHIFT_ROWS;
+        mix_columns(&mut state);
+        assert_eq!((state), (AFTER_MIX_COLUMNS));
+
+        inv_mix_columns(&mut state);
+        assert_eq!((state), (AFTER_SHIFT_ROWS));
+    }
+
+    #[test]
+    fn sub_bytes_round_trips() {
+        let mut state = AFTER_MIX_COLUMNS;
+        sub_bytes(&mut state);
+        inv_sub_bytes(&mut state);
+        assert_eq!((state), (AFTER_MIX_COLUMNS));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::aes::gf_inv;
+
+/// AES S-box, derived from the GF(2^8) inverse (FIPS 197 section 5.1.1).
+pub const SBOX: [u8; 256] = build_sbox();
+
+/// Inverse S-box, SBOX read backwards.
+pub const INV_SBOX: [u8; 256] = build_inv_sbox();
+
+// Affine transformation over GF(2): each bit b_i of the inverse is XORed
+// with b_(i+4), b_(i+5), b_(i+6), b_(i+7) and the constant {63}.
+const fn affine(b: u8) -> u8 {
+    b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3)
+        ^ b.rotate_left(4) ^ 0x63
+}
+
+const fn build_sbox() -> [u8; 256] {
+    let mut sbox = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        sbox[i] = affine(gf_inv(i as u8));
+        i += 1;
+    }
+
+    sbox
+}
+
+const fn build_inv_sbox() -> [u8; 256] {
+    let sbox = build_sbox();
+    let mut inv = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        inv[sbox[i] as usize] = i as u8;
+        i += 1;
+    }
+
+    inv
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sbox_matches_fips_197_table() {
+        // First row and a few entries of Table 4.
+        assert_eq!(
+            (SBOX[..16]),
+            ([
+                0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 
+                0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+            ])
+        );
+        assert_eq!((SBOX[0x53]), (0xed));
+        assert_eq!((SBOX[0xff]), (0x16));
+    }
+
+    #[test]
+    fn inv_sbox_undoes_sbox() {
+        assert_eq!((INV_SBOX[0xed]), (0x53));
+        assert_eq!((INV_SBOX[0x00]), (0x52));
+        for i in 0..256 {
+            assert_eq!((INV_SBOX[SBOX[i] as usize] as usize), (i));
+        }
+    }
+}
@@ -0,0 +1,476 @@
+use std::thread;
+
+use crate::argon2::{
+    block_from_bytes, block_to_bytes, compress, h_prime, 
+    Argon2Error, Block, Params, Variant, VERSION
+};
+use crate::blake2::Blake2b;
+
+// Number of slices (synchronization points) per pass.
+const SYNC_POINTS: usize = 4;
+
+// Memory layout derived from the parameters.
+struct Layout {
+    variant: Variant,
+    passes: u32,
+    lanes: usize,
+    // Blocks per lane (q) and per segment.
+    lane_len: usize,
+    segment_len: usize,
+    // Total number of blocks m'.
+    blocks: usize,
+}
+
+/// Argon2 password hashing (RFC 9106).
+///
+/// # Arguments
+/// - `variant`: Argon2d, Argon2i or Argon2id.
+/// - `params`: Memory cost, time cost, parallelism and tag length.
+/// - `password`, `salt`: The salt must be at least 8 bytes.
+/// - `secret`: Optional key K, may be empty.
+/// - `associated_data`: Optional data X, may be empty.
+///
+/// # Description
+/// - `H0` hashes all parameters and inputs with BLAKE2b.
+/// - The first two blocks of every lane are derived from `H0` with `H'`.
+/// - Every pass fills the lanes slice by slice; within a slice the lanes
+///   only read blocks of finished slices, so they are split across up to
+///   `available_parallelism` threads.
+/// - The tag is `H'` of the XOR of the last block of every lane.
+///
+/// # Reference
+/// [RFC 9106](https://www.rfc-editor.org/rfc/rfc9106), section 3.
+pub fn argon2(
+    variant: Variant, 
+    params: &Params, 
+    password: &[u8], 
+    salt: &[u8], 
+    secret: &[u8], 
+    associated_data: &[u8]
+) -> Result<Vec<u8>, Argon2Error> {
+    if salt.len() < 8 {
+        return Err(Argon2Error::SaltTooShort { len: salt.len() });
+    }
+
+    for input in [password, salt, secret, associated_data] {
+        if input.len() > u32::MAX as usize {
+            return Err(Argon2Error::InputTooLong);
+        }
+    }
+
+    let lanes = params.parallelism() as usize;
+    let m_cost = params.m_cost() as usize;
+    let blocks = 4 * lanes * (m_cost / (4 * lanes));
+    let layout = Layout {
+        variant,
+        passes: params.t_cost(),
+        lanes,
+        lane_len: blocks / lanes,
+        segment_len: blocks / lanes / SYNC_POINTS,
+        blocks,
+    };
+
+    let h0 = initial_hash(
+        variant, params, password, salt, secret, associated_data
+    );
+
+    // B[i][0] = H'^(1024)(H0 || LE32(0) || LE32(i)), B[i][1] likewise.
+    let mut memory: Vec<Block> = vec![[0u64; 128]; blocks];
+    for lane in 0..lanes {
+        for col in 0..2 {
+            let mut input = h0.clone();
+            input.extend_from_slice(&(col as u32).to_le_bytes());
+            input.extend_from_slice(&(lane as u32).to_le_bytes());
+            let block = h_prime(&input, 1024);
+            memory[lane * layout.lane_len + col] = block_from_bytes(&block);
+        }
+    }
+
+    for pass in 0..layout.passes {
+        for slice in 0..SYNC_POINTS {
+            fill_slice(&mut memory, &layout, pass, slice);
+        }
+    }
+
+    // C = B[0][q-1] ^ B[1][q-1] ^ ... ^ B[p-1][q-1].
+    let mut c = memory[layout.lane_len - 1];
+    for lane in 1..lanes {
+        let last = &memory[lane * layout.lane_len + layout.lane_len - 1];
+        for k in 0..128 {
+            c[k] ^= last[k];
+        }
+    }
+
+    Ok(h_prime(&block_to_bytes(&c), params.tag_len() as usize))
+}
+
+// H0 = H^(64)(LE32(p) || LE32(T) || LE32(m) || LE32(t) || LE32(v) ||
+//      LE32(y) || LE32(len(P)) || P || LE32(len(S)) || S ||
+//      LE32(len(K)) || K || LE32(len(X)) || X).
+fn initial_hash(
+    variant: Variant, 
+    params: &Params, 
+    password: &[u8], 
+    salt: &[u8], 
+    secret: &[u8], 
+    associated_data: &[u8]
+) -> Vec<u8> {
+    let mut hasher = Blake2b::new(64).expect("64 is a BLAKE2b length");
+    hasher.update(&params.parallelism().to_le_bytes());
+    hasher.update(&params.tag_len().to_le_bytes());
+    hasher.update(&params.m_cost().to_le_bytes());
+    hasher.update(&params.t_cost().to_le_bytes());
+    hasher.update(&VERSION.to_le_bytes());
+    hasher.update(&(variant as u32).to_le_bytes());
+
+    for input in [password, salt, secret, associated_data] {
+        hasher.update(&(input.len() as u32).to_le_bytes());
+        hasher.update(input);
+    }
+
+    hasher.finalize()
+}
+
+// Fill one slice of every lane, then store the new segments. The lanes are
+// split into chunks, one per worker thread, with no more workers than the
+// machine runs in parallel. Threads share the memory read-only: a segment
+// only reads its own blocks from its local buffer.
+fn fill_slice(memory: &mut [Block], layout: &Layout, pass: u32, slice: usize) {
+    let workers = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(layout.lanes);
+
+    let segments: Vec<Vec<Block>> = if workers == 1 {
+        (0..layout.lanes)
+            .map(|lane| fill_segment(memory, layout, pass, slice, lane))
+            .collect()
+    } else {
+        let shared: &[Block] = memory;
+        let lanes_per_worker = layout.lanes.div_ceil(workers);
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..layout.lanes)
+                .step_by(lanes_per_worker)
+                .map(|first| {
+                    let last = (first + lanes_per_worker).min(layout.lanes);
+                    scope.spawn(move || {
+                        (first..last)
+                            .map(|lane| {
+                                fill_segment(shared, layout, pass, slice, lane)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("Argon2 lane panicked"))
+                .collect()
+        })
+    };
+
+    for (lane, segment) in segments.into_iter().enumerate() {
+        let start = lane * layout.lane_len + slice * layout.segment_len;
+        for (i, block) in segment.into_iter().enumerate() {
+            // The first two blocks of pass 0 are already in place.
+            if pass == 0 && slice == 0 && i < 2 {
+                continue;
+            }
+            memory[start + i] = block;
+        }
+    }
+}
+
+// Compute the segment (pass, slice, lane). Returns all of its blocks; the
+// first two are placeholders when the segment starts the first pass.
+fn fill_segment(
+    memory: &[Block], 
+    layout: &Layout, 
+    pass: u32, 
+    slice: usize, 
+    lane: usize
+) -> Vec<Block> {
+    let q = layout.lane_len;
+    let lane_start = lane * q;
+    let segment_start = slice * layout.segment_len;
+
+    let data_independent = match layout.variant {
+        Variant::Argon2i => true,
+        Variant::Argon2d => false,
+        Variant::Argon2id => pass == 0 && slice < SYNC_POINTS / 2,
+    };
+
+    // Address generation input: (r, l, sl, m', t, y, counter, 0, ...).
+    let zero = [0u64; 128];
+    let mut input = [0u64; 128];
+    input[0] = pass as u64;
+    input[1] = lane as u64;
+    input[2] = slice as u64;
+    input[3] = layout.blocks as u64;
+    input[4] = layout.passes as u64;
+    input[5] = layout.variant as u64;
+    let mut addresses = [0u64; 128];
+
+    let mut segment: Vec<Block> = Vec::with_capacity(layout.segment_len);
+    let mut first = 0;
+    if pass == 0 && slice == 0 {
+        segment.push(memory[lane_start]);
+        segment.push(memory[lane_start + 1]);
+        first = 2;
+        if data_independent {
+            next_addresses(&mut addresses, &mut input, &zero);
+        }
+    }
+
+    for i in first..layout.segment_len {
+        let col = segment_start + i;
+
+        // Previous block, wrapping to the end of the lane at column 0.
+        let prev = if i > 0 {
+            segment[i - 1]
+        } else if col == 0 {
+            memory[lane_start + q - 1]
+        } else {
+            memory[lane_start + col - 1]
+        };
+
+        let pseudo_rand = if data_independent {
+            if i % 128 == 0 {
+                next_addresses(&mut addresses, &mut input, &zero);
+            }
+            addresses[i % 128]
+        } else {
+            prev[0]
+        };
+
+        // Reference lane; the first slice of pass 0 stays in its own lane.
+        let ref_lane = if pass == 0 && slice == 0 {
+            lane
+        } else {
+            ((pseudo_rand >> 32) % layout.lanes as u64) as usize
+        };
+        let same_lane = ref_lane == lane;
+        let ref_col = reference_column(
+            layout, pass, slice, i, pseudo_rand & 0xffffffff, same_lane
+        );
+
+        let reference = if same_lane 
+            && ref_col >= segment_start 
+            && ref_col < segment_start + i 
+        {
+            segment[ref_col - segment_start]
+        } else {
+            memory[ref_lane * q + ref_col]
+        };
+
+        let mut block = compress(&prev, &reference);
+
+        // Version 1.3: later passes XOR into the old block.
+        if pass > 0 {
+            let old = &memory[lane_start + col];
+            for k in 0..128 {
+                block[k] ^= old[k];
+            }
+        }
+
+        segment.push(block);
+    }
+
+    segment
+}
+
+// Next block of pseudo-random addresses: G(0, G(0, input)), with the
+// counter in input[6] incremented first.
+fn next_addresses(addresses: &mut Block, input: &mut Block, zero: &Block) {
+    input[6] += 1;
+    let tmp = compress(zero, input);
+    *addresses = compress(zero, &tmp);
+}
+
+// Map J1 onto a column of the reference lane (RFC 9106, section 3.4.2).
+fn reference_column(
+    layout: &Layout, 
+    pass: u32, 
+    slice: usize, 
+    index: usize, 
+    j1: u64, 
+    same_lane: bool
+) -> usize {
+    let q = layout.lane_len;
+    let seg = layout.segment_len;
+
+    // Blocks that may be referenced: finished slices, plus the blocks of
+    // the current segment before the previous one when in the same lane.
+    let area = if pass == 0 {
+        if slice == 0 || same_lane {
+            slice * seg + index - 1
+        } else if index == 0 {
+            slice * seg - 1
+        } else {
+            slice * seg
+        }
+    } else if same_lane {
+        q - seg + index - 1
+    } else if index == 0 {
+        q - seg - 1
+    } else {
+        q - seg
+    };
+
+    let x = (j1 * j1) >> 32;
+    let y = (area as u64 * x) >> 32;
+    let relative = area as u64 - 1 - y;
+
+    let start = if pass == 0 || slice == SYNC_POINTS - 1 {
+        0
+    } else {
+        (slice + 1) * seg
+    };
+
+    ((start as u64 + relative) % q as u64) as usize
+}
+
+/// Argon2id, the recommended variant.
+pub fn argon2id(
+    params: &Params, 
+    password: &[u8], 
+    salt: &[u8]
+) -> Result<Vec<u8>, Argon2Error> {
+    argon2(Variant::Argon2id, params, password, salt, &[], &[])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Inputs shared by the RFC 9106 test vectors (section 5).
+    fn rfc9106_tag(variant: Variant) -> Vec<u8> {
+        let params = Params::new(32, 3, 4, 32).unwrap();
+        argon2(
+            variant, 
+            &params, 
+            &[0x01; 32], 
+            &[0x02; 16], 
+            &[0x03; 8], 
+            &[0x04; 12]
+        ).unwrap()
+    }
+
+    #[test]
+    fn argon2d_rfc9106_vector() {
+        let expected = vec![
+            0x51, 0x2b, 0x39, 0x1b, 0x6f, 0x11, 0x62, 0x97,
+            0x53, 0x71, 0xd3, 0x09, 0x19, 0x73, 0x42, 0x94,
+            0xf8, 0x68, 0xe3, 0xbe, 0x39, 0x84, 0xf3, 0xc1,
+            0xa1, 0x3a, 0x4d, 0xb9, 0xfa, 0xbe, 0x4a, 0xcb,
+        ];
+
+        assert_eq!((rfc9106_tag(Variant::Argon2d)), (expected));
+    }
+
+    #[test]
+    fn argon2i_rfc9106_vector() {
+        let expected = vec![
+            0xc8, 0x14, 0xd9, 0xd1, 0xdc, 0x7f, 0x37, 0xaa,
+            0x13, 0xf0, 0xd7, 0x7f, 0x24, 0x94, 0xbd, 0xa1,
+            0xc8, 0xde, 0x6b, 0x01, 0x6d, 0xd3, 0x88, 0xd2,
+            0x99, 0x52, 0xa4, 0xc4, 0x67, 0x2b, 0x6c, 0xe8,
+        ];
+
+        assert_eq!((rfc9106_tag(Variant::Argon2i)), (expected));
+    }
+
+    #[test]
+    fn argon2id_rfc9106_vector() {
+        let expected = vec![
+            0x0d, 0x64, 0x0d, 0xf5, 0x8d, 0x78, 0x76, 0x6c,
+            0x08, 0xc0, 0x37, 0xa3, 0x4a, 0x8b, 0x53, 0xc9,
+            0xd0, 0x1e, 0xf0, 0x45, 0x2d, 0x75, 0xb6, 0x5e,
+            0xb5, 0x25, 0x20, 0xe9, 0x6b, 0x01, 0xe6, 0x59,
+        ];
+
+        assert_eq!((rfc9106_tag(Variant::Argon2id)), (expected));
+    }
+
+    #[test]
+    fn argon2id_single_lane() {
+        let params = Params::new(64, 2, 1, 32).unwrap();
+        let expected = vec![
+            0x16, 0xa1, 0xa4, 0x98, 0x73, 0x46, 0x09, 0xdd,
+            0x01, 0x45, 0x6d, 0xa4, 0x06, 0xde, 0x9f, 0x3d,
+            0x9d, 0xa9, 0x3e, 0x6c, 0x86, 0xc3, 0x00, 0xa1,
+            0x2f, 0xc1, 0x46, 0x52, 0x14, 0xce, 0x49, 0x22,
+        ];
+
+        let result = argon2id(&params, b"password", b"somesalt");
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn argon2i_rounds_memory_down_and_uses_long_tags() {
+        // m = 37 KiB with 4 lanes uses m' = 32 blocks.
+        let params = Params::new(37, 1, 4, 100).unwrap();
+        let result = argon2(
+            Variant::Argon2i, &params, b"password", b"somesalt", &[], &[]
+        );
+        let expected = vec![
+            0xf5, 0xc2, 0x36, 0xfa, 0xf2, 0x84, 0x76, 0x92,
+            0x16, 0x0f, 0x5b, 0xed, 0x06, 0x39, 0x08, 0x29,
+            0xcf, 0xa5, 0x6b, 0x76, 0x1f, 0x06, 0xd1, 0x60,
+            0x53, 0x27, 0xc2, 0x3b, 0x29, 0xc7, 0xad, 0x4c,
+            0xb0, 0x8c, 0x25, 0x65, 0x56, 0x7a, 0x7b, 0x8e,
+            0x09, 0xcd, 0x61, 0xfe, 0xef, 0xf8, 0x52, 0x4d,
+            0x63, 0x8e, 0x12, 0x88, 0x03, 0x99, 0x5d, 0x6f,
+            0xbe, 0xb5, 0xae, 0xc7, 0x37, 0x6c, 0x67, 0x77,
+            0xf9, 0xfd, 0x29, 0x77, 0xa8, 0xea, 0x29, 0xa0,
+            0x73, 0x6d, 0x1e, 0x53, 0x42, 0x19, 0x92, 0x39,
+            0x37, 0x2c, 0x62, 0x5f, 0x1b, 0x0e, 0x12, 0xd7,
+            0x1d, 0xc8, 0x31, 0x58, 0xa2, 0xc0, 0x33, 0xfa,
+            0x61, 0x0f, 0x00, 0x8c,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn argon2id_regenerates_addresses_in_long_segments() {
+        // 150 blocks per segment, more than one block of addresses.
+        let params = Params::new(600, 2, 2, 32).unwrap();
+        let expected = vec![
+            0x31, 0x55, 0x12, 0x79, 0x03, 0x65, 0x42, 0x3c,
+            0xa2, 0x33, 0x27, 0x5d, 0x78, 0x18, 0xa9, 0x2b,
+            0x8d, 0xf1, 0xa4, 0x13, 0x51, 0xe3, 0xb4, 0x51,
+            0x75, 0x3e, 0x6b, 0x9d, 0xdd, 0xf0, 0x04, 0xac,
+        ];
+
+        let result = argon2id(&params, b"password", b"somesalt");
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn argon2id_more_lanes_than_threads() {
+        // 64 lanes of 8 blocks each, shared by far fewer worker threads.
+        let params = Params::new(512, 2, 64, 32).unwrap();
+        let expected = vec![
+            0x97, 0xde, 0x4a, 0x1e, 0xee, 0xa0, 0x17, 0x49,
+            0xff, 0x6d, 0x89, 0xd8, 0x61, 0x51, 0x1f, 0x57,
+            0xad, 0x10, 0x7c, 0x2d, 0x3f, 0x78, 0x24, 0x96,
+            0x71, 0x1b, 0xea, 0xec, 0xc9, 0xeb, 0xad, 0xed,
+        ];
+
+        let result = argon2id(&params, b"password", b"somesalt");
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn rejects_short_salt() {
+        let params = Params::new(32, 3, 4, 32).unwrap();
+        let result = argon2id(&params, b"password", b"salt");
+
+        assert_eq!((result), (Err(Argon2Error::SaltTooShort { len: 4 })));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::utils64::{ rotr64, z64 };
+
+/// One 1024-byte Argon2 memory block as 128 little-endian 64-bit words.
+pub type Block = [u64; 128];
+
+/// Parse 1024 bytes into a block.
+pub fn block_from_bytes(bytes: &[u8]) -> Block {
+    let mut block = [0u64; 128];
+    for i in 0..128 {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&bytes[i * 8..i * 8 + 8]);
+        block[i] = u64::from_le_bytes(word);
+    }
+
+    block
+}
+
+/// Serialize a block back into 1024 bytes.
+pub fn block_to_bytes(block: &Block) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1024);
+    for word in block {
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+
+    bytes
+}
+
+/// Argon2 compression function G.
+///
+/// # Description
+/// - `R = X ^ Y`, viewed as an 8x8 matrix of 16-byte registers.
+/// - Apply the permutation P to every row, then to every column.
+/// - Return `Z ^ R`.
+///
+/// # Reference
+/// [RFC 9106](https://www.rfc-editor.org/rfc/rfc9106), section 3.5.
+pub fn compress(x: &Block, y: &Block) -> Block {
+    let mut r = [0u64; 128];
+    for i in 0..128 {
+        r[i] = x[i] ^ y[i];
+    }
+
+    let mut q = r;
+
+    // Rows: registers 8i .. 8i + 7, i.e. 16 consecutive words.
+    for row in 0..8 {
+        let mut idx = [0usize; 16];
+        for (k, i) in idx.iter_mut().enumerate() {
+            *i = row * 16 + k;
+        }
+        permute(&mut q, &idx);
+    }
+
+    // Columns: registers i, i + 8, ..., i + 56, two words each.
+    for col in 0..8 {
+        let mut idx = [0usize; 16];
+        for k in 0..8 {
+            idx[2 * k] = k * 16 + col * 2;
+            idx[2 * k + 1] = k * 16 + col * 2 + 1;
+        }
+        permute(&mut q, &idx);
+    }
+
+    for i in 0..128 {
+        q[i] ^= r[i];
+    }
+
+    q
+}
+
+// Permutation P: one BLAKE2b round without message words, on the 16 words
+// of `q` at the positions in `idx`.
+fn permute(q: &mut Block, idx: &[usize; 16]) {
+    let mut v = [0u64; 16];
+    for k in 0..16 {
+        v[k] = q[idx[k]];
+    }
+
+    gb(&mut v, 0, 4, 8, 12);
+    gb(&mut v, 1, 5, 9, 13);
+    gb(&mut v, 2, 6, 10, 14);
+    gb(&mut v, 3, 7, 11, 15);
+
+    gb(&mut v, 0, 5, 10, 15);
+    gb(&mut v, 1, 6, 11, 12);
+    gb(&mut v, 2, 7, 8, 13);
+    gb(&mut v, 3, 4, 9, 14);
+
+    for k in 0..16 {
+        q[idx[k]] = v[k];
+    }
+}
+
+// BLAKE2b G with the additions replaced by a + b + 2 * lo(a) * lo(b).
+fn gb(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize) {
+    v[a] = fbla(v[a], v[b]);
+    v[d] = rotr64(v[d] ^ v[a], 32);
+    v[c] = fbla(v[c], v[d]);
+    v[b] = rotr64(v[b] ^ v[c], 24);
+    v[a] = fbla(v[a], v[b]);
+    v[d] = rotr64(v[d] ^ v[a], 16);
+    v[c] = fbla(v[c], v[d]);
+    v[b] = rotr64(v[b] ^ v[c], 63);
+}
+
+// Multiply-hardened addition of the Argon2 round function.
+fn fbla(x: u64, y: u64) -> u64 {
+    let lo = (x & 0xffffffff).wrapping_mul(y & 0xffffffff);
+    z64(z64(x, y), lo.wrapping_mul(2))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bytes_round_trip() {
+        let bytes: Vec<u8> = (0..1024).map(|i| i as u8).collect();
+        assert_eq!((block_to_bytes(&block_from_bytes(&bytes))), (bytes));
+    }
+
+    #[test]
+    fn compression_of_zero_blocks_is_zero() {
+        // P maps the all-zero state to itself, so G(0, 0) = 0.
+        let zero = [0u64; 128];
+        assert_eq!((compress(&zero, &zero)), (zero));
+    }
+}
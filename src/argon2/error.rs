@@ -0,0 +1,56 @@
+use std::fmt;
+
+/// Errors of Argon2 parameter validation and PHC string handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Argon2Error {
+    /// Parallelism (lanes) must be 1 to 2^24 - 1.
+    InvalidParallelism { p: u32 },
+    /// Time cost (passes) must be at least 1.
+    ZeroTimeCost,
+    /// Memory cost in KiB must be at least 8 * parallelism.
+    MemoryTooSmall { m: u32, min: u32 },
+    /// Tags must be at least 4 bytes.
+    TagTooShort { len: u32 },
+    /// Salts must be at least 8 bytes.
+    SaltTooShort { len: usize },
+    /// Password, salt, secret and associated data are limited to 2^32 - 1
+    /// bytes each.
+    InputTooLong,
+    /// Only version 0x13 (19) is implemented.
+    UnsupportedVersion { version: u32 },
+    /// Malformed PHC string.
+    InvalidEncoding { reason: &'static str },
+}
+
+impl fmt::Display for Argon2Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Argon2Error::InvalidParallelism { p } => {
+                write!(f, "Argon2 parallelism {p} is outside 1 to 2^24 - 1")
+            }
+            Argon2Error::ZeroTimeCost => {
+                write!(f, "Argon2 time cost must be at least 1")
+            }
+            Argon2Error::MemoryTooSmall { m, min } => {
+                write!(f, "Argon2 memory cost {m} KiB is below {min} KiB")
+            }
+            Argon2Error::TagTooShort { len } => {
+                write!(f, "Argon2 tag length {len} is below 4 bytes")
+            }
+            Argon2Error::SaltTooShort { len } => {
+                write!(f, "Argon2 salt length {len} is below 8 bytes")
+            }
+            Argon2Error::InputTooLong => {
+                write!(f, "Argon2 inputs are limited to 2^32 - 1 bytes")
+            }
+            Argon2Error::UnsupportedVersion { version } => {
+                write!(f, "Argon2 version {version} is not supported")
+            }
+            Argon2Error::InvalidEncoding { reason } => {
+                write!(f, "invalid Argon2 PHC string: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Argon2Error {}
@@ -0,0 +1,72 @@
+use crate::blake2::Blake2b;
+
+/// Variable-length hash function H' built on BLAKE2b.
+///
+/// # Description
+/// - Up to 64 bytes: `H^T(LE32(T) || X)`, BLAKE2b with digest length T.
+/// - Longer: `V_1 = H^64(LE32(T) || X)`, `V_i = H^64(V_{i-1})` up to
+///   `V_r` with `r = ceil(T / 32) - 2`, and `V_{r+1} = H^{T - 32r}(V_r)`.
+///   The output is the first 32 bytes of `V_1 .. V_r` followed by all of
+///   `V_{r+1}`.
+///
+/// # Reference
+/// [RFC 9106](https://www.rfc-editor.org/rfc/rfc9106), section 3.3.
+pub fn h_prime(input: &[u8], out_len: usize) -> Vec<u8> {
+    let mut hasher = Blake2b::new(out_len.min(64))
+        .expect("H' output is 1 byte or longer");
+    hasher.update(&(out_len as u32).to_le_bytes());
+    hasher.update(input);
+    let mut v = hasher.finalize();
+
+    if out_len <= 64 {
+        return v;
+    }
+
+    let r = out_len.div_ceil(32) - 2;
+    let mut out: Vec<u8> = Vec::with_capacity(out_len);
+    out.extend_from_slice(&v[..32]);
+
+    for _ in 2..=r {
+        let mut hasher = Blake2b::new(64).expect("64 is a BLAKE2b length");
+        hasher.update(&v);
+        v = hasher.finalize();
+        out.extend_from_slice(&v[..32]);
+    }
+
+    let mut hasher = Blake2b::new(out_len - 32 * r)
+        .expect("the last block is 33 to 64 bytes");
+    hasher.update(&v);
+    out.extend_from_slice(&hasher.finalize());
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::blake2::blake2b;
+
+    #[test]
+    fn short_output_is_a_single_blake2b() {
+        let mut input = 4u32.to_le_bytes().to_vec();
+        input.extend_from_slice(b"abc");
+
+        assert_eq!((h_prime(b"abc", 4)), (blake2b(&input, 4).unwrap()));
+    }
+
+    #[test]
+    fn long_output_chains_64_byte_hashes() {
+        let out = h_prime(b"abc", 100);
+        assert_eq!((out.len()), (100));
+
+        // r = 2: V_1 = H^64(LE32(100) || X), V_2 = H^64(V_1),
+        // V_3 = H^36(V_2).
+        let mut input = 100u32.to_le_bytes().to_vec();
+        input.extend_from_slice(b"abc");
+        let v1 = blake2b(&input, 64).unwrap();
+        let v2 = blake2b(&v1, 64).unwrap();
+        assert_eq!((&out[..32]), (&v1[..32]));
+        assert_eq!((&out[32..64]), (&v2[..32]));
+        assert_eq!((&out[64..]), (&blake2b(&v2, 36).unwrap()[..]));
+    }
+}
@@ -0,0 +1,14 @@
+pub mod error;
+pub mod params;
+pub mod block;
+pub mod h_prime;
+#[allow(clippy::module_inception)]
+pub mod argon2;
+pub mod phc;
+
+pub use error::Argon2Error;
+pub use params::{ Params, Variant, VERSION };
+pub use block::{ block_from_bytes, block_to_bytes, compress, Block };
+pub use h_prime::h_prime;
+pub use argon2::{ argon2, argon2id };
+pub use phc::{ hash_encoded, verify_encoded, PhcString };
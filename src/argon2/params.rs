@@ -0,0 +1,123 @@
+use crate::argon2::Argon2Error;
+
+/// Argon2 version 1.3, the only one specified by RFC 9106.
+pub const VERSION: u32 = 0x13;
+
+/// Argon2 variant, with its type code `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Data-dependent memory access.
+    Argon2d = 0,
+    /// Data-independent memory access.
+    Argon2i = 1,
+    /// Argon2i for the first half pass, Argon2d afterwards.
+    Argon2id = 2,
+}
+
+impl Variant {
+    /// Identifier used in PHC strings.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Variant::Argon2d => "argon2d",
+            Variant::Argon2i => "argon2i",
+            Variant::Argon2id => "argon2id",
+        }
+    }
+}
+
+/// Validated Argon2 cost parameters.
+///
+/// # Description
+/// - `m_cost`: Memory size in KiB, at least `8 * parallelism`.
+/// - `t_cost`: Number of passes over the memory, at least 1.
+/// - `parallelism`: Number of lanes, 1 to 2^24 - 1.
+/// - `tag_len`: Output length in bytes, at least 4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    m_cost: u32,
+    t_cost: u32,
+    parallelism: u32,
+    tag_len: u32,
+}
+
+impl Params {
+    pub fn new(
+        m_cost: u32, 
+        t_cost: u32, 
+        parallelism: u32, 
+        tag_len: u32
+    ) -> Result<Self, Argon2Error> {
+        if parallelism == 0 || parallelism >= 1 << 24 {
+            return Err(Argon2Error::InvalidParallelism { p: parallelism });
+        }
+
+        if t_cost == 0 {
+            return Err(Argon2Error::ZeroTimeCost);
+        }
+
+        let min = 8 * parallelism;
+        if m_cost < min {
+            return Err(Argon2Error::MemoryTooSmall { m: m_cost, min });
+        }
+
+        if tag_len < 4 {
+            return Err(Argon2Error::TagTooShort { len: tag_len });
+        }
+
+        Ok(Params { m_cost, t_cost, parallelism, tag_len })
+    }
+
+    pub fn m_cost(&self) -> u32 {
+        self.m_cost
+    }
+
+    pub fn t_cost(&self) -> u32 {
+        self.t_cost
+    }
+
+    pub fn parallelism(&self) -> u32 {
+        self.parallelism
+    }
+
+    pub fn tag_len(&self) -> u32 {
+        self.tag_len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn accepts_rfc9106_parameters() {
+        let params = Params::new(32, 3, 4, 32).unwrap();
+        assert_eq!((params.m_cost()), (32));
+        assert_eq!((params.t_cost()), (3));
+        assert_eq!((params.parallelism()), (4));
+        assert_eq!((params.tag_len()), (32));
+    }
+
+    #[test]
+    fn rejects_out_of_range_parameters() {
+        assert_eq!(
+            (Params::new(32, 3, 0, 32)), 
+            (Err(Argon2Error::InvalidParallelism { p: 0 }))
+        );
+        assert_eq!(
+            (Params::new(1 << 30, 3, 1 << 24, 32)), 
+            (Err(Argon2Error::InvalidParallelism { p: 1 << 24 }))
+        );
+        assert_eq!(
+            (Params::new(32, 0, 4, 32)), 
+            (Err(Argon2Error::ZeroTimeCost))
+        );
+        assert_eq!(
+            (Params::new(31, 3, 4, 32)), 
+            (Err(Argon2Error::MemoryTooSmall { m: 31, min: 32 }))
+        );
+        assert_eq!(
+            (Params::new(32, 3, 4, 3)), 
+            (Err(Argon2Error::TagTooShort { len: 3 }))
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use crate::argon2::{ argon2, Argon2Error, Params, Variant, VERSION };
+use crate::utils::ct_eq;
+
+const BASE64: &[u8; 64] = 
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Argon2 hash in PHC string format:
+/// `$argon2id$v=19$m=65536,t=3,p=4$<salt>$<hash>`.
+///
+/// Salt and hash are base64 without padding, the tag length is the length
+/// of the decoded hash. Secret and associated data are not part of the
+/// string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhcString {
+    pub variant: Variant,
+    pub params: Params,
+    pub salt: Vec<u8>,
+    pub hash: Vec<u8>,
+}
+
+impl PhcString {
+    pub fn encode(&self) -> String {
+        format!(
+            "${}$v={}$m={},t={},p={}${}${}",
+            self.variant.name(),
+            VERSION,
+            self.params.m_cost(),
+            self.params.t_cost(),
+            self.params.parallelism(),
+            base64_encode(&self.salt),
+            base64_encode(&self.hash)
+        )
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self, Argon2Error> {
+        let fields: Vec<&str> = encoded.split('$').collect();
+        if fields.len() != 6 || !fields[0].is_empty() {
+            return Err(invalid("expected $id$v=..$m=..,t=..,p=..$salt$hash"));
+        }
+
+        let variant = match fields[1] {
+            "argon2d" => Variant::Argon2d,
+            "argon2i" => Variant::Argon2i,
+            "argon2id" => Variant::Argon2id,
+            _ => return Err(invalid("unknown algorithm identifier")),
+        };
+
+        let version = match fields[2].strip_prefix("v=") {
+            Some(value) => parse_decimal(value)?,
+            None => return Err(invalid("missing version")),
+        };
+        if version != VERSION {
+            return Err(Argon2Error::UnsupportedVersion { version });
+        }
+
+        let costs: Vec<&str> = fields[3].split(',').collect();
+        if costs.len() != 3 {
+            return Err(invalid("expected m=..,t=..,p=.."));
+        }
+        let m_cost = parse_param(costs[0], "m=")?;
+        let t_cost = parse_param(costs[1], "t=")?;
+        let parallelism = parse_param(costs[2], "p=")?;
+
+        let salt = base64_decode(fields[4])?;
+        let hash = base64_decode(fields[5])?;
+        let tag_len = u32::try_from(hash.len())
+            .map_err(|_| Argon2Error::InputTooLong)?;
+        let params = Params::new(m_cost, t_cost, parallelism, tag_len)?;
+
+        Ok(PhcString { variant, params, salt, hash })
+    }
+}
+
+/// Hash `password` and return the PHC string to store.
+pub fn hash_encoded(
+    variant: Variant, 
+    params: &Params, 
+    password: &[u8], 
+    salt: &[u8]
+) -> Result<String, Argon2Error> {
+    let hash = argon2(variant, params, password, salt, &[], &[])?;
+
+    let phc = PhcString {
+        variant,
+        params: *params,
+        salt: salt.to_vec(),
+        hash,
+    };
+
+    Ok(phc.encode())
+}
+
+/// Recompute the hash of `password` with the parameters and salt stored
+/// in `encoded`, and compare it in constant time.
+pub fn verify_encoded(
+    encoded: &str, 
+    password: &[u8]
+) -> Result<bool, Argon2Error> {
+    let phc = PhcString::decode(encoded)?;
+    let hash = argon2(phc.variant, &phc.params, password, &phc.salt, &[], &[])?;
+
+    Ok(ct_eq(&hash, &phc.hash))
+}
+
+fn invalid(reason: &'static str) -> Argon2Error {
+    Argon2Error::InvalidEncoding { reason }
+}
+
+// "name=value" with a decimal u32 value.
+fn parse_param(field: &str, name: &str) -> Result<u32, Argon2Error> {
+    match field.strip_prefix(name) {
+        Some(value) => parse_decimal(value),
+        None => Err(invalid("expected m=..,t=..,p=.. in this order")),
+    }
+}
+
+// Decimal u32 without sign or leading zeros, as the PHC format requires.
+fn parse_decimal(value: &str) -> Result<u32, Argon2Error> {
+    let digits_only = !value.is_empty() 
+        && value.bytes().all(|b| b.is_ascii_digit());
+    if !digits_only || (value.len() > 1 && value.starts_with('0')) {
+        return Err(invalid("parameter is not a decimal number"));
+    }
+
+    value.parse::<u32>().map_err(|_| invalid("parameter is out of range"))
+}
+
+// Standard base64 (RFC 4648) without padding.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = if chunk.len() > 1 { chunk[1] as u32 } else { 0 };
+        let b2 = if chunk.len() > 2 { chunk[2] as u32 } else { 0 };
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+
+        // 2, 3 or 4 characters for 1, 2 or 3 input bytes.
+        for k in 0..chunk.len() + 1 {
+            let index = (triple >> (18 - 6 * k)) & 0x3f;
+            out.push(BASE64[index as usize] as char);
+        }
+    }
+
+    out
+}
+
+fn base64_decode(text: &str) -> Result<Vec<u8>, Argon2Error> {
+    let mut out: Vec<u8> = Vec::with_capacity(text.len() * 3 / 4);
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+
+    for c in text.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(invalid("invalid base64 character")),
+        };
+
+        acc = (acc << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+
+    // A single leftover character, or set padding bits, is not canonical.
+    if bits >= 6 || acc & ((1 << bits) - 1) != 0 {
+        return Err(invalid("non-canonical base64 length or padding bits"));
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ENCODED: &str = "$argon2id$v=19$m=64,t=2,p=1$c29tZXNhbHQ\
+                           $FqGkmHNGCd0BRW2kBt6fPZ2pPmyGwwChL8FGUhTOSSI";
+
+    #[test]
+    fn encodes_phc_string() {
+        let params = Params::new(64, 2, 1, 32).unwrap();
+        let result = hash_encoded(
+            Variant::Argon2id, &params, b"password", b"somesalt"
+        );
+
+        assert_eq!((result), (Ok(ENCODED.to_string())));
+    }
+
+    #[test]
+    fn decodes_phc_string() {
+        let phc = PhcString::decode(ENCODED).unwrap();
+
+        assert_eq!((phc.variant), (Variant::Argon2id));
+        assert_eq!((phc.params), (Params::new(64, 2, 1, 32).unwrap()));
+        assert_eq!((phc.salt), (b"somesalt".to_vec()));
+        assert_eq!((phc.encode()), (ENCODED));
+    }
+
+    #[test]
+    fn verifies_password_against_phc_string() {
+        assert_eq!((verify_encoded(ENCODED, b"password")), (Ok(true)));
+        assert_eq!((verify_encoded(ENCODED, b"passw0rd")), (Ok(false)));
+    }
+
+    #[test]
+    fn rejects_malformed_phc_strings() {
+        let cases = [
+            "argon2id$v=19$m=64,t=2,p=1$c29tZXNhbHQ$FqGk",
+            "$argon2x$v=19$m=64,t=2,p=1$c29tZXNhbHQ$FqGkmHNG",
+            "$argon2id$m=64,t=2,p=1$c29tZXNhbHQ$FqGkmHNG",
+            "$argon2id$v=19$t=2,m=64,p=1$c29tZXNhbHQ$FqGkmHNG",
+            "$argon2id$v=19$m=064,t=2,p=1$c29tZXNhbHQ$FqGkmHNG",
+            "$argon2id$v=19$m=64,t=2,p=1$c29tZXNhbHQ=$FqGkmHNG",
+            "$argon2id$v=19$m=64,t=2,p=1$c29tZXNhbHR$FqGkmHNG",
+        ];
+
+        for case in cases {
+            assert!(matches!(
+                PhcString::decode(case), 
+                Err(Argon2Error::InvalidEncoding { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_other_versions_and_bad_parameters() {
+        let old = "$argon2id$v=16$m=64,t=2,p=1$c29tZXNhbHQ$FqGkmHNG";
+        assert_eq!(
+            (PhcString::decode(old)), 
+            (Err(Argon2Error::UnsupportedVersion { version: 16 }))
+        );
+
+        let zero_time = "$argon2id$v=19$m=64,t=0,p=1$c29tZXNhbHQ$FqGkmHNG";
+        assert_eq!(
+            (PhcString::decode(zero_time)), 
+            (Err(Argon2Error::ZeroTimeCost))
+        );
+    }
+
+    #[test]
+    fn base64_round_trips_every_length() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for len in 0..10 {
+            let encoded = base64_encode(&bytes[..len]);
+            assert_eq!((base64_decode(&encoded)), (Ok(bytes[..len].to_vec())));
+        }
+        assert_eq!((base64_encode(b"somesalt")), ("c29tZXNhbHQ"));
+    }
+}
@@ -0,0 +1,400 @@
+use crate::blake2::{ compress_b, Blake2Error, IV_B };
+use crate::digest::Digest;
+
+/// BLAKE2b streaming context with a digest length of 1 to 64 bytes.
+///
+/// # Description
+/// - Digest length, key length, salt and personalization make up the
+///   parameter block XORed into the IV, so BLAKE2b-256 is not a truncated
+///   BLAKE2b-512.
+/// - A key is zero-padded to a full first block.
+/// - The last block has to be compressed with the final flag set, so a full
+///   buffer is only compressed once more input arrives.
+#[derive(Clone)]
+pub struct Blake2b {
+    h: [u64; 8],
+    t: u128,
+    buffer: [u8; 128],
+    buffer_len: usize,
+    out_len: usize,
+}
+
+impl Blake2b {
+    /// Unkeyed BLAKE2b with a digest length of 1 to 64 bytes.
+    pub fn new(out_len: usize) -> Result<Self, Blake2Error> {
+        Self::with_params(out_len, &[], &[], &[])
+    }
+
+    /// BLAKE2b with an optional key (up to 64 bytes), salt and
+    /// personalization (up to 16 bytes each, zero-padded).
+    pub fn with_params(
+        out_len: usize, 
+        key: &[u8], 
+        salt: &[u8], 
+        personal: &[u8]
+    ) -> Result<Self, Blake2Error> {
+        if !(1..=64).contains(&out_len) {
+            let len = out_len;
+            return Err(Blake2Error::InvalidOutputLength { len, max: 64 });
+        }
+
+        if key.len() > 64 {
+            return Err(Blake2Error::KeyTooLong { len: key.len(), max: 64 });
+        }
+
+        if salt.len() > 16 {
+            return Err(Blake2Error::SaltTooLong { len: salt.len(), max: 16 });
+        }
+
+        if personal.len() > 16 {
+            let len = personal.len();
+            return Err(Blake2Error::PersonalTooLong { len, max: 16 });
+        }
+
+        Ok(Self::init(out_len, key, salt, personal))
+    }
+
+    fn init(out_len: usize, key: &[u8], salt: &[u8], personal: &[u8]) -> Self {
+        // Parameter block: digest length, key length, fanout 1, depth 1,
+        // then salt and personalization in words 4..7.
+        let mut h = IV_B;
+        h[0] ^= 0x01010000 ^ ((key.len() as u64) << 8) ^ out_len as u64;
+
+        let salt_words = words_le(salt);
+        let personal_words = words_le(personal);
+        for i in 0..2 {
+            h[4 + i] ^= salt_words[i];
+            h[6 + i] ^= personal_words[i];
+        }
+
+        let mut hasher = Blake2b {
+            h,
+            t: 0,
+            buffer: [0u8; 128],
+            buffer_len: 0,
+            out_len,
+        };
+
+        // The padded key is the first block of the message.
+        if !key.is_empty() {
+            hasher.buffer[..key.len()].copy_from_slice(key);
+            hasher.buffer_len = 128;
+        }
+
+        hasher
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut i = 0;
+        while i < data.len() {
+            // Only compress a full buffer once it is known not to be last.
+            if self.buffer_len == 128 {
+                self.t = self.t.wrapping_add(128);
+                let block = parse_block(&self.buffer);
+                compress_b(&mut self.h, &block, self.t, false);
+                self.buffer_len = 0;
+            }
+
+            self.buffer[self.buffer_len] = data[i];
+            self.buffer_len += 1;
+            i += 1;
+        }
+    }
+
+    /// Compress the zero-padded last block and return `out_len` bytes.
+    pub fn finalize(mut self) -> Vec<u8> {
+        self.t = self.t.wrapping_add(self.buffer_len as u128);
+        for i in self.buffer_len..128 {
+            self.buffer[i] = 0x00;
+        }
+
+        let block = parse_block(&self.buffer);
+        compress_b(&mut self.h, &block, self.t, true);
+
+        let mut out = Vec::with_capacity(64);
+        for word in self.h {
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+        out.truncate(self.out_len);
+        out
+    }
+}
+
+/// BLAKE2b-512 with the `Digest` interface.
+#[derive(Clone)]
+pub struct Blake2b512 {
+    inner: Blake2b,
+}
+
+impl Blake2b512 {
+    pub fn new() -> Self {
+        Blake2b512 {
+            inner: Blake2b::init(64, &[], &[], &[]),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Return the 64-byte digest.
+    pub fn finalize(self) -> [u8; 64] {
+        let digest = self.inner.finalize();
+
+        let mut out = [0u8; 64];
+        out.copy_from_slice(&digest);
+        out
+    }
+}
+
+impl Default for Blake2b512 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Blake2b512 {
+    const OUTPUT_SIZE: usize = 64;
+    const BLOCK_SIZE: usize = 128;
+    type Output = [u8; 64];
+
+    fn new() -> Self { Blake2b512::new() }
+    fn update(&mut self, data: &[u8]) { Blake2b512::update(self, data) }
+    fn finalize(self) -> [u8; 64] { Blake2b512::finalize(self) }
+}
+
+/// BLAKE2b: `out_len`-byte digest of `msg`.
+pub fn blake2b(
+    msg: &[u8], 
+    out_len: usize
+) -> Result<Vec<u8>, Blake2Error> {
+    let mut hasher = Blake2b::new(out_len)?;
+    hasher.update(msg);
+    Ok(hasher.finalize())
+}
+
+/// Keyed BLAKE2b: `out_len`-byte MAC of `msg` under `key`.
+pub fn blake2b_keyed(
+    key: &[u8], 
+    msg: &[u8], 
+    out_len: usize
+) -> Result<Vec<u8>, Blake2Error> {
+    let mut hasher = Blake2b::with_params(out_len, key, &[], &[])?;
+    hasher.update(msg);
+    Ok(hasher.finalize())
+}
+
+// Zero-padded salt or personalization as two little-endian words.
+fn words_le(bytes: &[u8]) -> [u64; 2] {
+    let mut padded = [0u8; 16];
+    padded[..bytes.len()].copy_from_slice(bytes);
+
+    let bytes = &padded;
+    let mut words = [0u64; 2];
+    for i in 0..2 {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&bytes[i * 8..i * 8 + 8]);
+        words[i] = u64::from_le_bytes(word);
+    }
+
+    words
+}
+
+// Little-endian parsing of one 128-byte block into 16 words.
+fn parse_block(bytes: &[u8; 128]) -> [u64; 16] {
+    let mut block = [0u64; 16];
+    for i in 0..16 {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&bytes[i * 8..i * 8 + 8]);
+        block[i] = u64::from_le_bytes(word);
+    }
+
+    block
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    // Deterministic input of the RFC 7693 self-test (Appendix E).
+    fn selftest_seq(len: usize, seed: u32) -> Vec<u8> {
+        let mut a = 0xdead4badu32.wrapping_mul(seed);
+        let mut b = 1u32;
+        let mut out = Vec::with_capacity(len);
+        for _ in 0..len {
+            let t = a.wrapping_add(b);
+            a = b;
+            b = t;
+            out.push((t >> 24) as u8);
+        }
+
+        out
+    }
+
+    #[test]
+    fn blake2b_512_abc() {
+        let hex = bytes_to_hex(&blake2b(b"abc", 64).unwrap());
+        assert_eq!(
+            hex,
+            b"ba80a53f981c4d0d6a2797b69f12f6e9\
+              4c212f14685ac4b74b12bb6fdbffa2d1\
+              7d87c5392aab792dc252d5de4533cc95\
+              18d38aa8dbf1925ab92386edd4009923"
+        );
+    }
+
+    #[test]
+    fn blake2b_512_empty() {
+        let hex = bytes_to_hex(&blake2b(b"", 64).unwrap());
+        assert_eq!(
+            hex,
+            b"786a02f742015903c6c6fd852552d272\
+              912f4740e15847618a86e217f71f5419\
+              d25e1031afee585313896444934eb04b\
+              903a685b1448b755d56f701afe9be2ce"
+        );
+    }
+
+    #[test]
+    fn blake2b_256_is_not_truncated_512() {
+        let hex = bytes_to_hex(&blake2b(b"abc", 32).unwrap());
+        assert_eq!(
+            hex,
+            b"bddd813c634239723171ef3fee98579b\
+              94964e3bb1cb3e427262c8c068d52319"
+        );
+    }
+
+    #[test]
+    fn blake2b_512_abc_rfc7693() {
+        let hex = bytes_to_hex(&Blake2b512::digest(b"abc"));
+        assert_eq!(
+            hex,
+            b"ba80a53f981c4d0d6a2797b69f12f6e9\
+              4c212f14685ac4b74b12bb6fdbffa2d1\
+              7d87c5392aab792dc252d5de4533cc95\
+              18d38aa8dbf1925ab92386edd4009923"
+        );
+    }
+
+    #[test]
+    fn blake2b_rfc7693_selftest() {
+        // Hash of all unkeyed and keyed digests over the listed lengths.
+        let mut grand = Blake2b::new(32).unwrap();
+        for out_len in [20, 32, 48, 64] {
+            for in_len in [0, 3, 128, 129, 255, 1024] {
+                let msg = selftest_seq(in_len, in_len as u32);
+                grand.update(&blake2b(&msg, out_len).unwrap());
+
+                let key = selftest_seq(out_len, out_len as u32);
+                grand.update(&blake2b_keyed(&key, &msg, out_len).unwrap());
+            }
+        }
+
+        let hex = bytes_to_hex(&grand.finalize());
+        assert_eq!(
+            hex,
+            b"c23a7800d98123bd10f506c61e29da56\
+              03d763b8bbad2e737f5e765a7bccd475"
+        );
+    }
+
+    #[test]
+    fn blake2b_key_salt_and_personalization() {
+        let mut hasher = Blake2b::with_params(64, b"key", b"salty", b"me")
+            .unwrap();
+        hasher.update(b"abc");
+        let hex = bytes_to_hex(&hasher.finalize());
+        assert_eq!(
+            hex,
+            b"11f9a7768223c0e6b6a4d18587b23e0b\
+              5eef6ef2305aa1a21c15aa6db4f38de3\
+              7f1742ec4aff3c6e087db482074d399b\
+              cf70483eb25c2ae1f5cdd4fbbe341e66"
+        );
+    }
+
+    #[test]
+    fn blake2b_keyed_empty_message() {
+        // The key block alone is the last block.
+        let hex = bytes_to_hex(&blake2b_keyed(b"key", b"", 64).unwrap());
+        assert_eq!(
+            hex,
+            b"5b3cfd8f422b490b764b55eceb330b50\
+              0c79cbefa9a928ad00202b8b3c5dd778\
+              a81122570434a2e3b8bfd028d105dfef\
+              d0a9576e88ed66de742ca9fbb5f8d2b6"
+        );
+    }
+
+    #[test]
+    fn blake2b_exact_multiple_of_block_size() {
+        // The second full block is the last one and must carry the flag.
+        let hex = bytes_to_hex(&blake2b(&[b'a'; 256], 64).unwrap());
+        assert_eq!(
+            hex,
+            b"0eee13d0c73a2710c5015a8b4be0a161\
+              20bb88f826b662951ffe4b3b81441cfd\
+              ce1f712c58e237dba72a0dad7f9c86b9\
+              745ea0b4b3b850ff3a260fb7df9d3e81"
+        );
+    }
+
+    #[test]
+    fn blake2b_streams_across_block_boundaries() {
+        let msg = b"a".repeat(1_000);
+        let expected = blake2b(&msg, 64).unwrap();
+
+        for chunk_size in [1, 127, 128, 129, 999] {
+            let mut hasher = Blake2b::new(64).unwrap();
+            for chunk in msg.chunks(chunk_size) {
+                hasher.update(chunk);
+            }
+
+            assert_eq!((hasher.finalize()), (expected));
+        }
+    }
+
+    #[test]
+    fn blake2b_keyed_streams_across_block_boundaries() {
+        let msg = b"a".repeat(1_000);
+        let expected = blake2b_keyed(b"key", &msg, 64).unwrap();
+
+        for chunk_size in [1, 127, 128, 129, 999] {
+            let mut hasher = Blake2b::with_params(64, b"key", &[], &[])
+                .unwrap();
+            for chunk in msg.chunks(chunk_size) {
+                hasher.update(chunk);
+            }
+
+            assert_eq!((hasher.finalize()), (expected));
+        }
+    }
+
+    #[test]
+    fn blake2b_rejects_invalid_parameters() {
+        assert_eq!(
+            (Blake2b::new(65).err()), 
+            (Some(Blake2Error::InvalidOutputLength { len: 65, max: 64 }))
+        );
+        assert_eq!(
+            (Blake2b::with_params(0, &[], &[], &[]).err()), 
+            (Some(Blake2Error::InvalidOutputLength { len: 0, max: 64 }))
+        );
+        assert_eq!(
+            (Blake2b::with_params(64, &[0; 65], &[], &[]).err()), 
+            (Some(Blake2Error::KeyTooLong { len: 65, max: 64 }))
+        );
+        assert_eq!(
+            (Blake2b::with_params(64, &[], &[0; 17], &[]).err()), 
+            (Some(Blake2Error::SaltTooLong { len: 17, max: 16 }))
+        );
+        assert_eq!(
+            (Blake2b::with_params(64, &[], &[], &[0; 17]).err()), 
+            (Some(Blake2Error::PersonalTooLong { len: 17, max: 16 }))
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use crate::blake2::{ compress_s, Blake2Error, IV_S };
+use crate::digest::Digest;
+use crate::padd_pars::little_endian_pars_block;
+
+/// BLAKE2s streaming context with a digest length of 1 to 32 bytes.
+///
+/// # Description
+/// - Digest length, key length, salt and personalization make up the
+///   parameter block XORed into the IV, so BLAKE2s-128 is not a truncated
+///   BLAKE2s-256.
+/// - A key is zero-padded to a full first block.
+/// - The last block has to be compressed with the final flag set, so a full
+///   buffer is only compressed once more input arrives.
+#[derive(Clone)]
+pub struct Blake2s {
+    h: [u32; 8],
+    t: u64,
+    buffer: [u8; 64],
+    buffer_len: usize,
+    out_len: usize,
+}
+
+impl Blake2s {
+    /// Unkeyed BLAKE2s with a digest length of 1 to 32 bytes.
+    pub fn new(out_len: usize) -> Result<Self, Blake2Error> {
+        Self::with_params(out_len, &[], &[], &[])
+    }
+
+    /// BLAKE2s with an optional key (up to 32 bytes), salt and
+    /// personalization (up to 8 bytes each, zero-padded).
+    pub fn with_params(
+        out_len: usize, 
+        key: &[u8], 
+        salt: &[u8], 
+        personal: &[u8]
+    ) -> Result<Self, Blake2Error> {
+        if !(1..=32).contains(&out_len) {
+            let len = out_len;
+            return Err(Blake2Error::InvalidOutputLength { len, max: 32 });
+        }
+
+        if key.len() > 32 {
+            return Err(Blake2Error::KeyTooLong { len: key.len(), max: 32 });
+        }
+
+        if salt.len() > 8 {
+            return Err(Blake2Error::SaltTooLong { len: salt.len(), max: 8 });
+        }
+
+        if personal.len() > 8 {
+            let len = personal.len();
+            return Err(Blake2Error::PersonalTooLong { len, max: 8 });
+        }
+
+        Ok(Self::init(out_len, key, salt, personal))
+    }
+
+    fn init(out_len: usize, key: &[u8], salt: &[u8], personal: &[u8]) -> Self {
+        // Parameter block: digest length, key length, fanout 1, depth 1,
+        // then salt and personalization in words 4..7.
+        let mut h = IV_S;
+        h[0] ^= 0x01010000 ^ ((key.len() as u32) << 8) ^ out_len as u32;
+
+        let salt_words = words_le(salt);
+        let personal_words = words_le(personal);
+        for i in 0..2 {
+            h[4 + i] ^= salt_words[i];
+            h[6 + i] ^= personal_words[i];
+        }
+
+        let mut hasher = Blake2s {
+            h,
+            t: 0,
+            buffer: [0u8; 64],
+            buffer_len: 0,
+            out_len,
+        };
+
+        // The padded key is the first block of the message.
+        if !key.is_empty() {
+            hasher.buffer[..key.len()].copy_from_slice(key);
+            hasher.buffer_len = 64;
+        }
+
+        hasher
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut i = 0;
+        while i < data.len() {
+            // Only compress a full buffer once it is known not to be last.
+            if self.buffer_len == 64 {
+                self.t = self.t.wrapping_add(64);
+                let block = little_endian_pars_block(&self.buffer);
+                compress_s(&mut self.h, &block, self.t, false);
+                self.buffer_len = 0;
+            }
+
+            self.buffer[self.buffer_len] = data[i];
+            self.buffer_len += 1;
+            i += 1;
+        }
+    }
+
+    /// Compress the zero-padded last block and return `out_len` bytes.
+    pub fn finalize(mut self) -> Vec<u8> {
+        self.t = self.t.wrapping_add(self.buffer_len as u64);
+        for i in self.buffer_len..64 {
+            self.buffer[i] = 0x00;
+        }
+
+        let block = little_endian_pars_block(&self.buffer);
+        compress_s(&mut self.h, &block, self.t, true);
+
+        let mut out = Vec::with_capacity(32);
+        for word in self.h {
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+        out.truncate(self.out_len);
+        out
+    }
+}
+
+/// BLAKE2s-256 with the `Digest` interface.
+#[derive(Clone)]
+pub struct Blake2s256 {
+    inner: Blake2s,
+}
+
+impl Blake2s256 {
+    pub fn new() -> Self {
+        Blake2s256 {
+            inner: Blake2s::init(32, &[], &[], &[]),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Return the 32-byte digest.
+    pub fn finalize(self) -> [u8; 32] {
+        let digest = self.inner.finalize();
+
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&digest);
+        out
+    }
+}
+
+impl Default for Blake2s256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Blake2s256 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 64;
+    type Output = [u8; 32];
+
+    fn new() -> Self { Blake2s256::new() }
+    fn update(&mut self, data: &[u8]) { Blake2s256::update(self, data) }
+    fn finalize(self) -> [u8; 32] { Blake2s256::finalize(self) }
+}
+
+/// BLAKE2s: `out_len`-byte digest of `msg`.
+pub fn blake2s(
+    msg: &[u8], 
+    out_len: usize
+) -> Result<Vec<u8>, Blake2Error> {
+    let mut hasher = Blake2s::new(out_len)?;
+    hasher.update(msg);
+    Ok(hasher.finalize())
+}
+
+/// Keyed BLAKE2s: `out_len`-byte MAC of `msg` under `key`.
+pub fn blake2s_keyed(
+    key: &[u8], 
+    msg: &[u8], 
+    out_len: usize
+) -> Result<Vec<u8>, Blake2Error> {
+    let mut hasher = Blake2s::with_params(out_len, key, &[], &[])?;
+    hasher.update(msg);
+    Ok(hasher.finalize())
+}
+
+// Zero-padded salt or personalization as two little-endian words.
+fn words_le(bytes: &[u8]) -> [u32; 2] {
+    let mut padded = [0u8; 8];
+    padded[..bytes.len()].copy_from_slice(bytes);
+
+    let bytes = &padded;
+    let mut words = [0u32; 2];
+    for i in 0..2 {
+        let mut word = [0u8; 4];
+        word.copy_from_slice(&bytes[i * 4..i * 4 + 4]);
+        words[i] = u32::from_le_bytes(word);
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    // Deterministic input of the RFC 7693 self-test (Appendix E).
+    fn selftest_seq(len: usize, seed: u32) -> Vec<u8> {
+        let mut a = 0xdead4badu32.wrapping_mul(seed);
+        let mut b = 1u32;
+        let mut out = Vec::with_capacity(len);
+        for _ in 0..len {
+            let t = a.wrapping_add(b);
+            a = b;
+            b = t;
+            out.push((t >> 24) as u8);
+        }
+
+        out
+    }
+
+    #[test]
+    fn blake2s_256_abc_rfc7693() {
+        let hex = bytes_to_hex(&Blake2s256::digest(b"abc"));
+        assert_eq!(
+            hex,
+            b"508c5e8c327c14e2e1a72ba34eeb452f\
+              37458b209ed63a294d999b4c86675982"
+        );
+    }
+
+    #[test]
+    fn blake2s_rfc7693_selftest() {
+        // Hash of all unkeyed and keyed digests over the listed lengths.
+        let mut grand = Blake2s::new(32).unwrap();
+        for out_len in [16, 20, 28, 32] {
+            for in_len in [0, 3, 64, 65, 255, 1024] {
+                let msg = selftest_seq(in_len, in_len as u32);
+                grand.update(&blake2s(&msg, out_len).unwrap());
+
+                let key = selftest_seq(out_len, out_len as u32);
+                grand.update(&blake2s_keyed(&key, &msg, out_len).unwrap());
+            }
+        }
+
+        let hex = bytes_to_hex(&grand.finalize());
+        assert_eq!(
+            hex,
+            b"6a411f08ce25adcdfb02aba641451cec\
+              53c598b24f4fc787fbdc88797f4c1dfe"
+        );
+    }
+
+    #[test]
+    fn blake2s_key_salt_and_personalization() {
+        let mut hasher = Blake2s::with_params(32, b"key", b"salty", b"me")
+            .unwrap();
+        hasher.update(b"abc");
+        let hex = bytes_to_hex(&hasher.finalize());
+        assert_eq!(
+            hex,
+            b"9f75648b5fef1e6b077dde27747f5df3\
+              93c4e1b5c37b04ea385245362e46bd5e"
+        );
+    }
+
+    #[test]
+    fn blake2s_keyed_empty_message() {
+        // The key block alone is the last block.
+        let hex = bytes_to_hex(&blake2s_keyed(b"key", b"", 32).unwrap());
+        assert_eq!(
+            hex,
+            b"a65f92611fdc3722a305edf1ed575947\
+              aa86209290344f817e45c3a4edfddad9"
+        );
+    }
+
+    #[test]
+    fn blake2s_exact_multiple_of_block_size() {
+        // The second full block is the last one and must carry the flag.
+        let hex = bytes_to_hex(&blake2s(&[b'a'; 128], 32).unwrap());
+        assert_eq!(
+            hex,
+            b"3ac477e27353f9019b81694afe60c804\
+              9403784f91a58288428ea318bfa82809"
+        );
+    }
+
+    #[test]
+    fn blake2s_keyed_streams_across_block_boundaries() {
+        let msg = b"a".repeat(1_000);
+        let expected = blake2s_keyed(b"key", &msg, 32).unwrap();
+
+        for chunk_size in [1, 63, 64, 65, 999] {
+            let mut hasher = Blake2s::with_params(32, b"key", &[], &[])
+                .unwrap();
+            for chunk in msg.chunks(chunk_size) {
+                hasher.update(chunk);
+            }
+
+            assert_eq!((hasher.finalize()), (expected));
+        }
+    }
+
+    #[test]
+    fn blake2s_rejects_invalid_parameters() {
+        assert_eq!(
+            (Blake2s::new(33).err()), 
+            (Some(Blake2Error::InvalidOutputLength { len: 33, max: 32 }))
+        );
+        assert_eq!(
+            (Blake2s::with_params(0, &[], &[], &[]).err()), 
+            (Some(Blake2Error::InvalidOutputLength { len: 0, max: 32 }))
+        );
+        assert_eq!(
+            (Blake2s::with_params(32, &[0; 33], &[], &[]).err()), 
+            (Some(Blake2Error::KeyTooLong { len: 33, max: 32 }))
+        );
+        assert_eq!(
+            (Blake2s::with_params(32, &[], &[0; 9], &[]).err()), 
+            (Some(Blake2Error::SaltTooLong { len: 9, max: 8 }))
+        );
+        assert_eq!(
+            (Blake2s::with_params(32, &[], &[], &[0; 9]).err()), 
+            (Some(Blake2Error::PersonalTooLong { len: 9, max: 8 }))
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::blake2::{ IV_B, IV_S, SIGMA };
+use crate::utils::{ rotr, z };
+use crate::utils64::{ rotr64, z64 };
+
+/// BLAKE2b compression function F.
+///
+/// # Arguments
+/// - `h`: Chained state, updated in place.
+/// - `m`: One 128-byte block as sixteen little-endian 64-bit words.
+/// - `t`: Total number of message bytes absorbed so far, this block
+///   included.
+/// - `last`: Final block flag, set only for the very last block.
+///
+/// # Reference
+/// [RFC 7693](https://www.rfc-editor.org/rfc/rfc7693), section 3.2.
+pub fn compress_b(h: &mut [u64; 8], m: &[u64; 16], t: u128, last: bool) {
+    // Local work vector v[0..15].
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV_B);
+
+    // Mix in the 128-bit offset counter and the final block flag.
+    v[12] ^= t as u64;
+    v[13] ^= (t >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+
+    // Twelve rounds of column then diagonal mixing.
+    for round in 0..12 {
+        let s = &SIGMA[round % 10];
+
+        g_b(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        g_b(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        g_b(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        g_b(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+
+        g_b(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        g_b(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        g_b(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        g_b(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+
+    // XOR the two halves into the state.
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// BLAKE2s compression function F.
+///
+/// Same structure as `compress_b` on 32-bit words: 64-byte blocks, a
+/// 64-bit offset counter and ten rounds.
+pub fn compress_s(h: &mut [u32; 8], m: &[u32; 16], t: u64, last: bool) {
+    // Local work vector v[0..15].
+    let mut v = [0u32; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV_S);
+
+    // Mix in the 64-bit offset counter and the final block flag.
+    v[12] ^= t as u32;
+    v[13] ^= (t >> 32) as u32;
+    if last {
+        v[14] = !v[14];
+    }
+
+    // Ten rounds of column then diagonal mixing.
+    for s in &SIGMA {
+
+        g_s(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        g_s(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        g_s(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        g_s(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+
+        g_s(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        g_s(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        g_s(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        g_s(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+
+    // XOR the two halves into the state.
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+// Mixing function G with the BLAKE2b rotation constants (32, 24, 16, 63).
+fn g_b(
+    v: &mut [u64; 16], 
+    a: usize, 
+    b: usize, 
+    c: usize, 
+    d: usize, 
+    x: u64, 
+    y: u64
+) {
+    v[a] = z64(z64(v[a], v[b]), x);
+    v[d] = rotr64(v[d] ^ v[a], 32);
+    v[c] = z64(v[c], v[d]);
+    v[b] = rotr64(v[b] ^ v[c], 24);
+    v[a] = z64(z64(v[a], v[b]), y);
+    v[d] = rotr64(v[d] ^ v[a], 16);
+    v[c] = z64(v[c], v[d]);
+    v[b] = rotr64(v[b] ^ v[c], 63);
+}
+
+// Mixing function G with the BLAKE2s rotation constants (16, 12, 8, 7).
+fn g_s(
+    v: &mut [u32; 16], 
+    a: usize, 
+    b: usize, 
+    c: usize, 
+    d: usize, 
+    x: u32, 
+    y: u32
+) {
+    v[a] = z(z(v[a], v[b]), x);
+    v[d] = rotr(v[d] ^ v[a], 16);
+    v[c] = z(v[c], v[d]);
+    v[b] = rotr(v[b] ^ v[c], 12);
+    v[a] = z(z(v[a], v[b]), y);
+    v[d] = rotr(v[d] ^ v[a], 8);
+    v[c] = z(v[c], v[d]);
+    v[b] = rotr(v[b] ^ v[c], 7);
+}
@@ -0,0 +1,36 @@
+use std::fmt;
+
+/// Errors of BLAKE2 parameter validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Blake2Error {
+    /// Digest length must be 1 to 64 bytes (BLAKE2b) or 1 to 32 (BLAKE2s).
+    InvalidOutputLength { len: usize, max: usize },
+    /// Key longer than 64 bytes (BLAKE2b) or 32 bytes (BLAKE2s).
+    KeyTooLong { len: usize, max: usize },
+    /// Salt longer than 16 bytes (BLAKE2b) or 8 bytes (BLAKE2s).
+    SaltTooLong { len: usize, max: usize },
+    /// Personalization longer than 16 bytes (BLAKE2b) or 8 bytes (BLAKE2s).
+    PersonalTooLong { len: usize, max: usize },
+}
+
+impl fmt::Display for Blake2Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Blake2Error::InvalidOutputLength { len, max } => {
+                write!(f, "BLAKE2 digest length {len} is outside 1 to {max}")
+            }
+            Blake2Error::KeyTooLong { len, max } => {
+                write!(f, "BLAKE2 key of {len} bytes exceeds {max} bytes")
+            }
+            Blake2Error::SaltTooLong { len, max } => {
+                write!(f, "BLAKE2 salt of {len} bytes exceeds {max} bytes")
+            }
+            Blake2Error::PersonalTooLong { len, max } => write!(
+                f, 
+                "BLAKE2 personalization of {len} bytes exceeds {max} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Blake2Error {}
@@ -0,0 +1,11 @@
+pub mod constants;
+pub mod compression;
+pub mod error;
+pub mod blake2b;
+pub mod blake2s;
+
+pub use constants::{ IV_B, IV_S, SIGMA };
+pub use compression::{ compress_b, compress_s };
+pub use error::Blake2Error;
+pub use blake2b::{ blake2b, blake2b_keyed, Blake2b, Blake2b512 };
+pub use blake2s::{ blake2s, blake2s_keyed, Blake2s, Blake2s256 };
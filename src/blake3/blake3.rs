@@ -0,0 +1,662 @@
+use std::thread;
+
+use crate::blake3::{
+    compress, BLOCK_LEN, CHUNK_END, CHUNK_LEN, CHUNK_START,
+    DERIVE_KEY_CONTEXT, DERIVE_KEY_MATERIAL, IV, KEYED_HASH, KEY_LEN,
+    OUT_LEN, PARENT, ROOT
+};
+use crate::digest::Digest;
+use crate::padd_pars::little_endian_pars_block;
+
+// Subtrees below this size are hashed on the calling thread, spawning costs
+// more than hashing them.
+const PARALLEL_MIN_LEN: usize = 16 * CHUNK_LEN;
+
+// Last compression of a node, kept uncompressed so it can either become a
+// chaining value or, for the root, be squeezed for any number of bytes.
+#[derive(Clone, Copy)]
+struct Output {
+    input_cv: [u32; 8],
+    block: [u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+}
+
+impl Output {
+    fn chaining_value(&self) -> [u32; 8] {
+        let v = compress(
+            &self.input_cv, 
+            &self.block, 
+            self.counter, 
+            self.block_len, 
+            self.flags
+        );
+        first_8_words(&v)
+    }
+
+    // Root output block number `index`, 64 bytes of the XOF stream.
+    fn root_block(&self, index: u64) -> [u8; BLOCK_LEN] {
+        let v = compress(
+            &self.input_cv, 
+            &self.block, 
+            index, 
+            self.block_len, 
+            self.flags | ROOT
+        );
+
+        let mut out = [0u8; BLOCK_LEN];
+        for i in 0..16 {
+            out[i * 4..i * 4 + 4].copy_from_slice(&v[i].to_le_bytes());
+        }
+        out
+    }
+}
+
+// Chunk being absorbed: up to 16 blocks chained under one chunk counter.
+#[derive(Clone)]
+struct ChunkState {
+    cv: [u32; 8],
+    chunk_counter: u64,
+    buffer: [u8; BLOCK_LEN],
+    buffer_len: usize,
+    blocks_compressed: usize,
+    flags: u32,
+}
+
+impl ChunkState {
+    fn new(key: &[u32; 8], chunk_counter: u64, flags: u32) -> Self {
+        ChunkState {
+            cv: *key,
+            chunk_counter,
+            buffer: [0u8; BLOCK_LEN],
+            buffer_len: 0,
+            blocks_compressed: 0,
+            flags,
+        }
+    }
+
+    fn len(&self) -> usize {
+        BLOCK_LEN * self.blocks_compressed + self.buffer_len
+    }
+
+    fn start_flag(&self) -> u32 {
+        if self.blocks_compressed == 0 { CHUNK_START } else { 0 }
+    }
+
+    // Absorb at most the rest of the chunk. Like in BLAKE2, a full buffer
+    // is only compressed once more input arrives, the last block of the
+    // chunk needs the CHUNK_END flag.
+    fn update(&mut self, data: &[u8]) {
+        let mut i = 0;
+        while i < data.len() {
+            if self.buffer_len == BLOCK_LEN {
+                let block = little_endian_pars_block(&self.buffer);
+                let v = compress(
+                    &self.cv, 
+                    &block, 
+                    self.chunk_counter, 
+                    BLOCK_LEN as u32, 
+                    self.flags | self.start_flag()
+                );
+                self.cv = first_8_words(&v);
+                self.blocks_compressed += 1;
+                self.buffer_len = 0;
+            }
+
+            self.buffer[self.buffer_len] = data[i];
+            self.buffer_len += 1;
+            i += 1;
+        }
+    }
+
+    fn output(&self) -> Output {
+        let mut padded = [0u8; BLOCK_LEN];
+        let len = self.buffer_len;
+        padded[..len].copy_from_slice(&self.buffer[..len]);
+
+        Output {
+            input_cv: self.cv,
+            block: little_endian_pars_block(&padded),
+            counter: self.chunk_counter,
+            block_len: self.buffer_len as u32,
+            flags: self.flags | self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+/// BLAKE3 streaming context for the `hash`, `keyed_hash` and `derive_key`
+/// modes.
+///
+/// # Description
+/// - The input is split into 1024-byte chunks, the leaves of a binary tree
+///   whose parents compress the two child chaining values.
+/// - Completed subtrees are kept on a stack of chaining values and merged
+///   as soon as their sibling is complete, only the root is finalized with
+///   the ROOT flag.
+/// - Large updates are cut into the biggest power-of-two subtrees allowed
+///   by the current position, which are hashed on `std::thread` workers.
+/// - The root output is extendable: `finalize_xof` reads any number of
+///   bytes, `finalize` is its first 32 bytes.
+///
+/// # Reference
+/// [BLAKE3 specification](https://github.com/BLAKE3-team/BLAKE3-specs)
+#[derive(Clone)]
+pub struct Blake3 {
+    key: [u32; 8],
+    chunk_state: ChunkState,
+    cv_stack: Vec<[u32; 8]>,
+    flags: u32,
+}
+
+impl Blake3 {
+    /// `hash` mode.
+    pub fn new() -> Self {
+        Self::with_key_and_flags(IV, 0)
+    }
+
+    /// `keyed_hash` mode, a MAC or PRF under a 32-byte key.
+    pub fn new_keyed(key: &[u8; KEY_LEN]) -> Self {
+        Self::with_key_and_flags(key_words(key), KEYED_HASH)
+    }
+
+    /// `derive_key` mode: the context string is hashed into the key that
+    /// the key material is then hashed under.
+    ///
+    /// The context should be hardcoded, globally unique and
+    /// application-specific.
+    pub fn new_derive_key(context: &str) -> Self {
+        let mut context_hasher = Self::with_key_and_flags(
+            IV, 
+            DERIVE_KEY_CONTEXT
+        );
+        context_hasher.update(context.as_bytes());
+        let context_key = context_hasher.finalize();
+
+        Self::with_key_and_flags(key_words(&context_key), DERIVE_KEY_MATERIAL)
+    }
+
+    fn with_key_and_flags(key: [u32; 8], flags: u32) -> Self {
+        Blake3 {
+            key,
+            chunk_state: ChunkState::new(&key, 0, flags),
+            cv_stack: Vec::new(),
+            flags,
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut input = data;
+        while !input.is_empty() {
+            // Continue a started chunk, or close a full one now that more
+            // input is known to follow.
+            if self.chunk_state.len() > 0 {
+                if self.chunk_state.len() == CHUNK_LEN {
+                    let cv = self.chunk_state.output().chaining_value();
+                    let counter = self.chunk_state.chunk_counter;
+                    self.push_subtree(cv, counter, 1);
+                    self.chunk_state = ChunkState::new(
+                        &self.key, 
+                        counter + 1, 
+                        self.flags
+                    );
+                    continue;
+                }
+
+                let room = CHUNK_LEN - self.chunk_state.len();
+                let take = room.min(input.len());
+                self.chunk_state.update(&input[..take]);
+                input = &input[take..];
+                continue;
+            }
+
+            // At a chunk boundary: the last chunk might be the root, so at
+            // least one byte has to stay behind for the chunk state.
+            let full_chunks = ((input.len() - 1) / CHUNK_LEN) as u64;
+            if full_chunks == 0 {
+                self.chunk_state.update(input);
+                break;
+            }
+
+            // Biggest power-of-two subtree that is aligned on the current
+            // chunk counter.
+            let counter = self.chunk_state.chunk_counter;
+            let mut subtree_chunks = 1u64 << (63 - full_chunks.leading_zeros());
+            while !counter.is_multiple_of(subtree_chunks) {
+                subtree_chunks >>= 1;
+            }
+
+            let subtree_len = subtree_chunks as usize * CHUNK_LEN;
+            let threads = thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1);
+            let cv = hash_subtree(
+                &input[..subtree_len], 
+                counter, 
+                &self.key, 
+                self.flags, 
+                threads
+            );
+            self.push_subtree(cv, counter, subtree_chunks);
+            self.chunk_state = ChunkState::new(
+                &self.key, 
+                counter + subtree_chunks, 
+                self.flags
+            );
+            input = &input[subtree_len..];
+        }
+    }
+
+    // Push the chaining value of `chunks` (a power of two) chunks starting
+    // at `counter`, merging every subtree that is now complete.
+    fn push_subtree(&mut self, cv: [u32; 8], counter: u64, chunks: u64) {
+        let mut cv = cv;
+        let mut total = (counter + chunks) / chunks;
+        while total & 1 == 0 {
+            let left = self.cv_stack.pop().unwrap();
+            cv = parent_output(&left, &cv, &self.key, self.flags)
+                .chaining_value();
+            total >>= 1;
+        }
+
+        self.cv_stack.push(cv);
+    }
+
+    /// Return the 32-byte digest.
+    pub fn finalize(self) -> [u8; OUT_LEN] {
+        let mut out = [0u8; OUT_LEN];
+        self.finalize_xof().read(&mut out);
+        out
+    }
+
+    /// Merge the stack into the root node and hand it over to a reader
+    /// that squeezes as many bytes as asked for.
+    pub fn finalize_xof(self) -> OutputReader {
+        let mut output = self.chunk_state.output();
+        for left in self.cv_stack.iter().rev() {
+            let right = output.chaining_value();
+            output = parent_output(left, &right, &self.key, self.flags);
+        }
+
+        OutputReader {
+            output,
+            counter: 0,
+            block: [0u8; BLOCK_LEN],
+            pos: BLOCK_LEN,
+        }
+    }
+}
+
+impl Default for Blake3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Blake3 {
+    const OUTPUT_SIZE: usize = OUT_LEN;
+    const BLOCK_SIZE: usize = BLOCK_LEN;
+    type Output = [u8; OUT_LEN];
+
+    fn new() -> Self { Blake3::new() }
+    fn update(&mut self, data: &[u8]) { Blake3::update(self, data) }
+    fn finalize(self) -> [u8; OUT_LEN] { Blake3::finalize(self) }
+}
+
+/// Extendable output of a finalized BLAKE3 root node.
+///
+/// Every `read` continues exactly where the previous one stopped, output
+/// block `i` is the root compressed with counter `i`.
+#[derive(Clone)]
+pub struct OutputReader {
+    output: Output,
+    counter: u64,
+    block: [u8; BLOCK_LEN],
+    pos: usize,
+}
+
+impl OutputReader {
+    /// Fill `out` with the next `out.len()` bytes of output.
+    pub fn read(&mut self, out: &mut [u8]) {
+        for byte in out.iter_mut() {
+            if self.pos == BLOCK_LEN {
+                self.block = self.output.root_block(self.counter);
+                self.counter += 1;
+                self.pos = 0;
+            }
+
+            *byte = self.block[self.pos];
+            self.pos += 1;
+        }
+    }
+}
+
+/// BLAKE3 `hash` mode: 32-byte digest of `msg`.
+pub fn blake3(msg: &[u8]) -> [u8; OUT_LEN] {
+    let mut hasher = Blake3::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// BLAKE3 `hash` mode with `out_len` bytes of extendable output.
+pub fn blake3_xof(msg: &[u8], out_len: usize) -> Vec<u8> {
+    let mut hasher = Blake3::new();
+    hasher.update(msg);
+
+    let mut out = vec![0u8; out_len];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+/// BLAKE3 `keyed_hash` mode: 32-byte MAC of `msg` under `key`.
+pub fn blake3_keyed(key: &[u8; KEY_LEN], msg: &[u8]) -> [u8; OUT_LEN] {
+    let mut hasher = Blake3::new_keyed(key);
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// BLAKE3 `derive_key` mode: 32-byte key for `context` from
+/// `key_material`.
+pub fn blake3_derive_key(context: &str, key_material: &[u8]) -> [u8; OUT_LEN] {
+    let mut hasher = Blake3::new_derive_key(context);
+    hasher.update(key_material);
+    hasher.finalize()
+}
+
+// Chaining value of a complete, non-root subtree of a power-of-two number
+// of chunks. Both halves are hashed on their own thread while there are
+// threads left to hand out and the halves are big enough.
+fn hash_subtree(
+    input: &[u8], 
+    counter: u64, 
+    key: &[u32; 8], 
+    flags: u32, 
+    threads: usize
+) -> [u32; 8] {
+    if input.len() == CHUNK_LEN {
+        let mut chunk_state = ChunkState::new(key, counter, flags);
+        chunk_state.update(input);
+        return chunk_state.output().chaining_value();
+    }
+
+    let half = input.len() / 2;
+    let (left, right) = input.split_at(half);
+    let right_counter = counter + (half / CHUNK_LEN) as u64;
+
+    let (left_cv, right_cv) = if threads > 1 && half >= PARALLEL_MIN_LEN {
+        let left_threads = threads / 2;
+        let right_threads = threads - left_threads;
+        thread::scope(|scope| {
+            let left_handle = scope.spawn(|| {
+                hash_subtree(left, counter, key, flags, left_threads)
+            });
+            let right_cv = hash_subtree(
+                right, 
+                right_counter, 
+                key, 
+                flags, 
+                right_threads
+            );
+            (left_handle.join().unwrap(), right_cv)
+        })
+    } else {
+        (
+            hash_subtree(left, counter, key, flags, 1), 
+            hash_subtree(right, right_counter, key, flags, 1)
+        )
+    };
+
+    parent_output(&left_cv, &right_cv, key, flags).chaining_value()
+}
+
+// Parent node over two child chaining values.
+fn parent_output(
+    left: &[u32; 8], 
+    right: &[u32; 8], 
+    key: &[u32; 8], 
+    flags: u32
+) -> Output {
+    let mut block = [0u32; 16];
+    block[..8].copy_from_slice(left);
+    block[8..].copy_from_slice(right);
+
+    Output {
+        input_cv: *key,
+        block,
+        counter: 0,
+        block_len: BLOCK_LEN as u32,
+        flags: PARENT | flags,
+    }
+}
+
+fn first_8_words(v: &[u32; 16]) -> [u32; 8] {
+    let mut words = [0u32; 8];
+    words.copy_from_slice(&v[..8]);
+    words
+}
+
+// Little-endian key words.
+fn key_words(key: &[u8; KEY_LEN]) -> [u32; 8] {
+    let mut words = [0u32; 8];
+    for i in 0..8 {
+        let mut word = [0u8; 4];
+        word.copy_from_slice(&key[i * 4..i * 4 + 4]);
+        words[i] = u32::from_le_bytes(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    const CONTEXT: &str = "BLAKE3 2019-12-27 16:29:52 test vectors context";
+
+    // Input of the official BLAKE3 test vectors.
+    fn input(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn key() -> [u8; KEY_LEN] {
+        let mut key = [0u8; KEY_LEN];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        key
+    }
+
+    #[test]
+    fn blake3_hash_mode() {
+        // Lengths around chunk and tree boundaries.
+        let cases: [(usize, &[u8]); 15] = [
+            (0, b"af1349b9f5f9a1a6a0404dea36dcc949\
+                  9bcb25c9adc112b7cc9a93cae41f3262"),
+            (1, b"2d3adedff11b61f14c886e35afa03673\
+                  6dcd87a74d27b5c1510225d0f592e213"),
+            (1023, b"10108970eeda3eb932baac1428c7a216\
+                  3b0e924c9a9e25b35bba72b28f70bd11"),
+            (1024, b"42214739f095a406f3fc83deb889744a\
+                  c00df831c10daa55189b5d121c855af7"),
+            (1025, b"d00278ae47eb27b34faecf67b4fe263f\
+                  82d5412916c1ffd97c8cb7fb814b8444"),
+            (2048, b"e776b6028c7cd22a4d0ba182a8bf6220\
+                  5d2ef576467e838ed6f2529b85fba24a"),
+            (2049, b"5f4d72f40d7a5f82b15ca2b2e44b1de3\
+                  c2ef86c426c95c1af0b6879522563030"),
+            (3072, b"b98cb0ff3623be03326b373de6b90952\
+                  18513e64f1ee2edd2525c7ad1e5cffd2"),
+            (3073, b"7124b49501012f81cc7f11ca069ec922\
+                  6cecb8a2c850cfe644e327d22d3e1cd3"),
+            (4096, b"015094013f57a5277b59d8475c050104\
+                  2c0b642e531b0a1c8f58d2163229e969"),
+            (4097, b"9b4052b38f1c5fc8b1f9ff7ac7b27cd2\
+                  42487b3d890d15c96a1c25b8aa0fb995"),
+            (5120, b"9cadc15fed8b5d854562b26a9536d970\
+                  7cadeda9b143978f319ab34230535833"),
+            (5121, b"628bd2cb2004694adaab7bbd778a25df\
+                  25c47b9d4155a55f8fbd79f2fe154cff"),
+            (31744, b"62b6960e1a44bcc1eb1a611a8d6235b6\
+                  b4b78f32e7abc4fb4c6cdcce94895c47"),
+            (102400, b"bc3e3d41a1146b069abffad3c0d44860\
+                  cf664390afce4d9661f7902e7943e085"),
+        ];
+
+        for (len, expected) in cases {
+            let hex = bytes_to_hex(&blake3(&input(len)));
+            assert_eq!((hex), (expected.to_vec()), "input length {}", len);
+        }
+    }
+
+    #[test]
+    fn blake3_keyed_hash_mode() {
+        let cases: [(usize, &[u8]); 6] = [
+            (0, b"73492b19995d71cdb1e9d74decc09809\
+                  eb732f1b00bc95c27cb15f9dd4d6478f"),
+            (1, b"d08b45c6b127ee94f3f8527a0b82a5f8\
+                  0be1695a0eaec6022e772c0eb95a7e8b"),
+            (1024, b"f45a9249a627fdf1fcf13c0e6376f6a9\
+                  a9b2056d6e1b5693a4b119a3453665f9"),
+            (1025, b"82223147a9b804a0c3f9a921b8d8aee2\
+                  50d1a51bb76be72152e6d5e8f27349b3"),
+            (3073, b"66eabf3a0a1a262221ee9eed633621a5\
+                  065e4e73d098277c7de4162559edb9b4"),
+            (102400, b"ab2ecf0478e816065ba6039d8ec583cb\
+                  ce8a2335efe903e2d7313c04ba5330d2"),
+        ];
+
+        for (len, expected) in cases {
+            let hex = bytes_to_hex(&blake3_keyed(&key(), &input(len)));
+            assert_eq!((hex), (expected.to_vec()), "input length {}", len);
+        }
+    }
+
+    #[test]
+    fn blake3_derive_key_mode() {
+        let cases: [(usize, &[u8]); 6] = [
+            (0, b"2cc39783c223154fea8dfb7c1b1660f2\
+                  ac2dcbd1c1de8277b0b0dd39b7e50d7d"),
+            (1, b"b3e2e340a117a499c6cf2398a19ee0d2\
+                  9cca2bb7404c73063382693bf66cb06c"),
+            (1024, b"7356cd7720d5b66b6d0697eb3177d9f8\
+                  d73a4a5c5e968896eb6a689684302706"),
+            (1025, b"effaa245f065fbf82ac186839a249707\
+                  c3bddf6d3fdda22d1b95a3c970379bcb"),
+            (3073, b"72613c9ec9ff7e40f8f5c173784c532a\
+                  d852e827dba2bf85b2ab4b76f7079081"),
+            (102400, b"4652cff7a3f385a6103b5c260fc1593e\
+                  13c778dbe608efb092fe7ee69df6e9c6"),
+        ];
+
+        for (len, expected) in cases {
+            let hex = bytes_to_hex(&blake3_derive_key(CONTEXT, &input(len)));
+            assert_eq!((hex), (expected.to_vec()), "input length {}", len);
+        }
+    }
+
+    #[test]
+    fn blake3_extended_output_in_every_mode() {
+        // 131 bytes: three root blocks, the last one partially read.
+        let msg = input(2049);
+        let hex = bytes_to_hex(&blake3_xof(&msg, 131));
+        assert_eq!(
+            hex,
+            b"5f4d72f40d7a5f82b15ca2b2e44b1de3\
+              c2ef86c426c95c1af0b6879522563030\
+              96de31d71d74103403822a2e0bc1eb19\
+              3e7aecc9643a76b7bbc0c9f9c52e8783\
+              aae98764ca468962b5c2ec92f0c74eb5\
+              448d519713e09413719431c802f948dd\
+              5d90425a4ecdadece9eb178d80f26efc\
+              cae630734dff63340285adec2aed3b51\
+              073ad3"
+        );
+
+        let mut out = [0u8; 131];
+        let mut hasher = Blake3::new_keyed(&key());
+        hasher.update(&msg);
+        hasher.finalize_xof().read(&mut out);
+        assert_eq!(
+            (bytes_to_hex(&out)),
+            (b"5442eec85e3fd173dcff07c39cd8cff9\
+               689f17224471e655618ed728cf03b056\
+               a5d7f6e87626513da4eaef8370524001\
+               6b961daa230a01034b17dfb970482c8a\
+               1ef89de387e4337a6597e6bcbc4f9af0\
+               d77d48741e017b689203558a705ba7fb\
+               830865d4b8af4f57f6d46a6e09be76f5\
+               da92099edad499261517e9e4e09ff600\
+               b482a4".to_vec())
+        );
+
+        let mut hasher = Blake3::new_derive_key(CONTEXT);
+        hasher.update(&msg);
+        hasher.finalize_xof().read(&mut out);
+        assert_eq!(
+            (bytes_to_hex(&out)),
+            (b"2ea477c5515cc3dd606512ee72bb3e0e\
+               758cfae7232826f35fb98ca1bcbdf273\
+               16d8e9e79081a80b046b60f6a263616f\
+               33ca464bd78d79fa18200d06c7fc9bff\
+               d808cc4755277a7d5e09da0f29ed150f\
+               6537ea9bed946227ff184cc66a72a5f8\
+               c1e4bd8b04e81cf40fe6dc4427ad5678\
+               311a61f4ffc39d195589bdbc670f63ae\
+               70f4b6".to_vec())
+        );
+    }
+
+    #[test]
+    fn blake3_reads_continue_where_they_stopped() {
+        let expected = blake3_xof(b"abc", 200);
+
+        let mut hasher = Blake3::new();
+        hasher.update(b"abc");
+        let mut reader = hasher.finalize_xof();
+        let mut out = vec![0u8; 200];
+        let (first, rest) = out.split_at_mut(5);
+        let (second, third) = rest.split_at_mut(100);
+        reader.read(first);
+        reader.read(second);
+        reader.read(third);
+
+        assert_eq!((out), (expected));
+        assert_eq!((blake3(b"abc").to_vec()), (expected[..32].to_vec()));
+    }
+
+    #[test]
+    fn blake3_threaded_subtrees_match_the_serial_tree() {
+        // 1 MiB + 1 byte: one 1024-chunk subtree split across threads,
+        // then the last byte in its own chunk.
+        let msg = input(1_048_577);
+        let hex = bytes_to_hex(&blake3(&msg));
+        assert_eq!(
+            hex,
+            b"2f053cd7472cf0cd2f9adaf45c118025\
+              5b91b9a865404a63671a0ee5f792ed33"
+        );
+
+        // Updates that start mid-chunk only ever take the serial path.
+        let mut hasher = Blake3::new();
+        for chunk in msg.chunks(1_000) {
+            hasher.update(chunk);
+        }
+        assert_eq!((hasher.finalize()), (blake3(&msg)));
+    }
+
+    #[test]
+    fn blake3_streams_across_chunk_boundaries() {
+        let msg = input(31_744);
+        let expected = blake3_keyed(&key(), &msg);
+
+        for chunk_size in [1, 63, 64, 1023, 1024, 1025, 4096, 31_743] {
+            let mut hasher = Blake3::new_keyed(&key());
+            for chunk in msg.chunks(chunk_size) {
+                hasher.update(chunk);
+            }
+
+            assert_eq!((hasher.finalize()), (expected));
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::blake3::{ IV, MSG_PERMUTATION };
+use crate::utils::{ rotr, z };
+
+/// BLAKE3 compression function.
+///
+/// # Arguments
+/// - `cv`: 8-word input chaining value (the key words for the first block).
+/// - `block`: 16 little-endian message words.
+/// - `counter`: chunk index for chunk blocks, 0 for parents, output block
+///   index for root blocks.
+/// - `block_len`: number of message bytes in `block` (64 except for the
+///   last block of a short chunk).
+/// - `flags`: domain separation flags.
+///
+/// # Description
+/// Seven rounds of the BLAKE2s round function, with the message words
+/// permuted between rounds instead of a SIGMA table.
+///
+/// # Returns
+/// The full 16-word state: words 0..7 are the new chaining value, all 16
+/// are used for root output.
+///
+/// # Reference
+/// [BLAKE3 specification](https://github.com/BLAKE3-team/BLAKE3-specs),
+/// section 2.2.
+pub fn compress(
+    cv: &[u32; 8], 
+    block: &[u32; 16], 
+    counter: u64, 
+    block_len: u32, 
+    flags: u32
+) -> [u32; 16] {
+    let mut v = [
+        cv[0], cv[1], cv[2], cv[3], 
+        cv[4], cv[5], cv[6], cv[7], 
+        IV[0], IV[1], IV[2], IV[3], 
+        counter as u32, (counter >> 32) as u32, block_len, flags,
+    ];
+    let mut m = *block;
+
+    for round in 0..7 {
+        // Columns.
+        g(&mut v, 0, 4, 8, 12, m[0], m[1]);
+        g(&mut v, 1, 5, 9, 13, m[2], m[3]);
+        g(&mut v, 2, 6, 10, 14, m[4], m[5]);
+        g(&mut v, 3, 7, 11, 15, m[6], m[7]);
+
+        // Diagonals.
+        g(&mut v, 0, 5, 10, 15, m[8], m[9]);
+        g(&mut v, 1, 6, 11, 12, m[10], m[11]);
+        g(&mut v, 2, 7, 8, 13, m[12], m[13]);
+        g(&mut v, 3, 4, 9, 14, m[14], m[15]);
+
+        if round < 6 {
+            let mut permuted = [0u32; 16];
+            for i in 0..16 {
+                permuted[i] = m[MSG_PERMUTATION[i]];
+            }
+            m = permuted;
+        }
+    }
+
+    // Feed-forward: the first half becomes the chaining value, the second
+    // half keeps the extra output words for the XOF.
+    for i in 0..8 {
+        v[i] ^= v[i + 8];
+        v[i + 8] ^= cv[i];
+    }
+
+    v
+}
+
+// Mixing function G, the BLAKE2s quarter-round.
+fn g(
+    v: &mut [u32; 16], 
+    a: usize, 
+    b: usize, 
+    c: usize, 
+    d: usize, 
+    x: u32, 
+    y: u32
+) {
+    v[a] = z(z(v[a], v[b]), x);
+    v[d] = rotr(v[d] ^ v[a], 16);
+    v[c] = z(v[c], v[d]);
+    v[b] = rotr(v[b] ^ v[c], 12);
+    v[a] = z(z(v[a], v[b]), y);
+    v[d] = rotr(v[d] ^ v[a], 8);
+    v[c] = z(v[c], v[d]);
+    v[b] = rotr(v[b] ^ v[c], 7);
+}
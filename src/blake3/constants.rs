@@ -0,0 +1,31 @@
+/// BLAKE3 initialization vector, the same words as the SHA-256 H(0).
+pub const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 
+    0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Permutation applied to the message words between two rounds.
+pub const MSG_PERMUTATION: [usize; 16] = [
+    2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8
+];
+
+/// Block size in bytes.
+pub const BLOCK_LEN: usize = 64;
+
+/// Chunk size in bytes, the leaves of the tree.
+pub const CHUNK_LEN: usize = 1024;
+
+/// Default output and chaining value size in bytes.
+pub const OUT_LEN: usize = 32;
+
+/// Key size in bytes for `keyed_hash`.
+pub const KEY_LEN: usize = 32;
+
+// Domain separation flags, ORed into the last word of the compression input.
+pub const CHUNK_START: u32 = 1 << 0;
+pub const CHUNK_END: u32 = 1 << 1;
+pub const PARENT: u32 = 1 << 2;
+pub const ROOT: u32 = 1 << 3;
+pub const KEYED_HASH: u32 = 1 << 4;
+pub const DERIVE_KEY_CONTEXT: u32 = 1 << 5;
+pub const DERIVE_KEY_MATERIAL: u32 = 1 << 6;
@@ -0,0 +1,14 @@
+pub mod constants;
+pub mod compression;
+#[allow(clippy::module_inception)]
+pub mod blake3;
+
+pub use constants::{
+    BLOCK_LEN, CHUNK_END, CHUNK_LEN, CHUNK_START, DERIVE_KEY_CONTEXT,
+    DERIVE_KEY_MATERIAL, IV, KEYED_HASH, KEY_LEN, MSG_PERMUTATION, OUT_LEN,
+    PARENT, ROOT
+};
+pub use compression::compress;
+pub use blake3::{
+    blake3, blake3_derive_key, blake3_keyed, blake3_xof, Blake3, OutputReader
+};
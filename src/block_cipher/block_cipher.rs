@@ -0,0 +1,16 @@
+/// Common interface of every block cipher in the crate.
+///
+/// # Description
+/// - `BLOCK_SIZE`: Block length in bytes.
+/// - `encrypt_block`, `decrypt_block`: The keyed permutation and its
+///   inverse on exactly one block, in place.
+///
+/// The modes of operation take the cipher as a type parameter, e.g.
+/// `C: BlockCipher`, and read the block size from it.
+pub trait BlockCipher: Clone {
+    const BLOCK_SIZE: usize;
+
+    fn encrypt_block(&self, block: &mut [u8]);
+
+    fn decrypt_block(&self, block: &mut [u8]);
+}
@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod block_cipher;
+
+pub use block_cipher::BlockCipher;
@@ -0,0 +1,378 @@
+use crate::aead::Aead;
+use crate::aes::{ Aes, BLOCK_SIZE };
+use crate::ccm::CcmError;
+use crate::modes::Ctr;
+use crate::utils::ct_eq;
+
+/// AES-CCM authenticated encryption (counter with CBC-MAC).
+///
+/// # Description
+/// - The tag size t (an even 4 to 16 bytes) and the length field size q
+///   (2 to 8 bytes) are fixed in `new`, the nonce is then 15 - q bytes and
+///   the plaintext shorter than 2^(8q) bytes.
+/// - The tag is a CBC-MAC over B0 (flags, nonce and plaintext length), the
+///   length-prefixed associated data and the plaintext, each zero-padded
+///   to whole blocks.
+/// - The plaintext is encrypted in CTR mode from counter block 1, the
+///   CBC-MAC is masked with the keystream of counter block 0 and cut to t
+///   bytes.
+/// - `seal` returns ciphertext || tag. Since the MAC covers the plaintext,
+///   `open` has to decrypt first, the plaintext is only returned once the
+///   tag matches.
+///
+/// # Reference
+/// [NIST SP 800-38C](https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38c.pdf)
+#[derive(Clone)]
+pub struct AesCcm {
+    aes: Aes,
+    tag_size: usize,
+    length_size: usize,
+}
+
+impl AesCcm {
+    /// AES-128, AES-192 or AES-256-CCM, picked by the key length, with
+    /// `tag_size` byte tags and a `length_size` byte length field.
+    pub fn new(
+        key: &[u8], 
+        tag_size: usize, 
+        length_size: usize
+    ) -> Result<Self, CcmError> {
+        if !(4..=16).contains(&tag_size) || !tag_size.is_multiple_of(2) {
+            return Err(CcmError::InvalidTagSize { size: tag_size });
+        }
+
+        if !(2..=8).contains(&length_size) {
+            return Err(CcmError::InvalidLengthSize { size: length_size });
+        }
+
+        let aes = Aes::new(key)?;
+
+        Ok(AesCcm { aes, tag_size, length_size })
+    }
+
+    /// Nonce size in bytes, 15 minus the length field size.
+    pub fn nonce_size(&self) -> usize {
+        15 - self.length_size
+    }
+
+    /// Encrypt `plaintext` and authenticate it together with `aad`.
+    pub fn seal(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, CcmError> {
+        self.check_lengths(nonce, plaintext.len())?;
+
+        let mac = self.cbc_mac(nonce, aad, plaintext);
+        let (mut sealed, mask) = self.ctr(nonce, plaintext);
+        for i in 0..self.tag_size {
+            sealed.push(mac[i] ^ mask[i]);
+        }
+
+        Ok(sealed)
+    }
+
+    /// Verify and decrypt the output of `seal`.
+    ///
+    /// Fails closed: on a tag mismatch no plaintext is returned at all.
+    pub fn open(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, CcmError> {
+        if sealed.len() < self.tag_size {
+            let len = sealed.len();
+            return Err(CcmError::CiphertextTooShort { len });
+        }
+
+        let split = sealed.len() - self.tag_size;
+        let (ciphertext, tag) = sealed.split_at(split);
+        self.check_lengths(nonce, ciphertext.len())?;
+
+        let (plaintext, mask) = self.ctr(nonce, ciphertext);
+        let mac = self.cbc_mac(nonce, aad, &plaintext);
+
+        let mut expected = [0u8; BLOCK_SIZE];
+        for i in 0..BLOCK_SIZE {
+            expected[i] = mac[i] ^ mask[i];
+        }
+        if !ct_eq(&expected[..self.tag_size], tag) {
+            return Err(CcmError::AuthenticationFailed);
+        }
+
+        Ok(plaintext)
+    }
+
+    fn check_lengths(
+        &self, 
+        nonce: &[u8], 
+        text_len: usize
+    ) -> Result<(), CcmError> {
+        let expected = self.nonce_size();
+        if nonce.len() != expected {
+            let len = nonce.len();
+            return Err(CcmError::InvalidNonceLength { len, expected });
+        }
+
+        if (text_len as u128) >> (8 * self.length_size) != 0 {
+            return Err(CcmError::MessageTooLong);
+        }
+
+        Ok(())
+    }
+
+    // CBC-MAC of B0 || encoded AAD || plaintext, before masking.
+    fn cbc_mac(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> [u8; BLOCK_SIZE] {
+        // B0 flags: Adata, (t - 2) / 2 and q - 1, then N and Q.
+        let mut b0 = [0u8; BLOCK_SIZE];
+        let adata = if aad.is_empty() { 0x00 } else { 0x40 };
+        let tag_bits = (((self.tag_size - 2) / 2) as u8) << 3;
+        b0[0] = adata | tag_bits | (self.length_size - 1) as u8;
+        b0[1..1 + nonce.len()].copy_from_slice(nonce);
+        let q = (plaintext.len() as u64).to_be_bytes();
+        b0[1 + nonce.len()..].copy_from_slice(&q[8 - self.length_size..]);
+
+        let mut y = self.aes.encrypt_block(&b0);
+        if !aad.is_empty() {
+            let mut encoded = encode_aad_length(aad.len());
+            encoded.extend_from_slice(aad);
+            self.absorb_padded(&mut y, &encoded);
+        }
+        self.absorb_padded(&mut y, plaintext);
+
+        y
+    }
+
+    // Y_i = E(K, Y_(i-1) ^ B_i) for every (zero-padded) block of `data`.
+    fn absorb_padded(&self, y: &mut [u8; BLOCK_SIZE], data: &[u8]) {
+        for chunk in data.chunks(BLOCK_SIZE) {
+            for i in 0..chunk.len() {
+                y[i] ^= chunk[i];
+            }
+            *y = self.aes.encrypt_block(y);
+        }
+    }
+
+    // CTR from counter block 1 over `data`, and the tag mask S0 = E(Ctr0).
+    fn ctr(&self, nonce: &[u8], data: &[u8]) -> (Vec<u8>, [u8; BLOCK_SIZE]) {
+        let mut counter_block = [0u8; BLOCK_SIZE];
+        counter_block[0] = (self.length_size - 1) as u8;
+        counter_block[1..1 + nonce.len()].copy_from_slice(nonce);
+        let mask = self.aes.encrypt_block(&counter_block);
+
+        counter_block[BLOCK_SIZE - 1] = 0x01;
+        let q = self.length_size;
+        let mut ctr = Ctr::new(self.aes.clone(), &counter_block, q)
+            .expect("the counter block is one block");
+        let out = ctr.update(data)
+            .expect("message length is checked against the length field");
+
+        (out, mask)
+    }
+}
+
+impl Aead for AesCcm {
+    type Error = CcmError;
+
+    fn tag_size(&self) -> usize {
+        self.tag_size
+    }
+
+    fn seal(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, CcmError> {
+        AesCcm::seal(self, nonce, aad, plaintext)
+    }
+
+    fn open(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, CcmError> {
+        AesCcm::open(self, nonce, aad, sealed)
+    }
+}
+
+/// AES-CCM: encrypt and authenticate, returns ciphertext || tag.
+pub fn seal(
+    key: &[u8], 
+    tag_size: usize, 
+    length_size: usize, 
+    nonce: &[u8], 
+    aad: &[u8], 
+    plaintext: &[u8]
+) -> Result<Vec<u8>, CcmError> {
+    AesCcm::new(key, tag_size, length_size)?.seal(nonce, aad, plaintext)
+}
+
+/// AES-CCM: verify and decrypt ciphertext || tag.
+pub fn open(
+    key: &[u8], 
+    tag_size: usize, 
+    length_size: usize, 
+    nonce: &[u8], 
+    aad: &[u8], 
+    sealed: &[u8]
+) -> Result<Vec<u8>, CcmError> {
+    AesCcm::new(key, tag_size, length_size)?.open(nonce, aad, sealed)
+}
+
+// Length prefix of the associated data: 2 bytes below 2^16 - 2^8,
+// 0xfffe || 4 bytes below 2^32, otherwise 0xffff || 8 bytes.
+fn encode_aad_length(len: usize) -> Vec<u8> {
+    let len = len as u64;
+    if len < 0xff00 {
+        (len as u16).to_be_bytes().to_vec()
+    } else if len <= u32::MAX as u64 {
+        let mut encoded = vec![0xff, 0xfe];
+        encoded.extend_from_slice(&(len as u32).to_be_bytes());
+        encoded
+    } else {
+        let mut encoded = vec![0xff, 0xff];
+        encoded.extend_from_slice(&len.to_be_bytes());
+        encoded
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    const KEY: [u8; 16] = [
+        0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+        0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
+    ];
+
+    // Nonce 10 11 .. , associated data 00 01 .. and plaintext 20 21 .. of
+    // SP 800-38C appendix C.
+    fn counting(start: u8, len: usize) -> Vec<u8> {
+        (0..len).map(|i| start.wrapping_add(i as u8)).collect()
+    }
+
+    #[test]
+    fn ccm_sp800_38c_example_1() {
+        let ccm = AesCcm::new(&KEY, 4, 8).unwrap();
+        let nonce = counting(0x10, 7);
+        let aad = counting(0x00, 8);
+        let plaintext = counting(0x20, 4);
+
+        let sealed = ccm.seal(&nonce, &aad, &plaintext).unwrap();
+        assert_eq!((bytes_to_hex(&sealed)), (b"7162015b4dac255d"));
+
+        let opened = ccm.open(&nonce, &aad, &sealed).unwrap();
+        assert_eq!((opened), (plaintext));
+    }
+
+    #[test]
+    fn ccm_sp800_38c_example_2() {
+        let ccm = AesCcm::new(&KEY, 6, 7).unwrap();
+        let nonce = counting(0x10, 8);
+        let aad = counting(0x00, 16);
+        let plaintext = counting(0x20, 16);
+
+        let sealed = ccm.seal(&nonce, &aad, &plaintext).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&sealed)),
+            (b"d2a1f0e051ea5f62081a7792073d593d\
+               1fc64fbfaccd".to_vec())
+        );
+
+        let opened = ccm.open(&nonce, &aad, &sealed).unwrap();
+        assert_eq!((opened), (plaintext));
+    }
+
+    #[test]
+    fn ccm_sp800_38c_example_3() {
+        let ccm = AesCcm::new(&KEY, 8, 3).unwrap();
+        let nonce = counting(0x10, 12);
+        let aad = counting(0x00, 20);
+        let plaintext = counting(0x20, 24);
+
+        let sealed = ccm.seal(&nonce, &aad, &plaintext).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&sealed)),
+            (b"e3b201a9f5b71a7a9b1ceaeccd97e70b\
+               6176aad9a4428aa5484392fbc1b09951".to_vec())
+        );
+
+        let opened = ccm.open(&nonce, &aad, &sealed).unwrap();
+        assert_eq!((opened), (plaintext));
+    }
+
+    // 65536 bytes of associated data take the 0xfffe || 4-byte prefix.
+    #[test]
+    fn ccm_sp800_38c_example_4() {
+        let ccm = AesCcm::new(&KEY, 14, 2).unwrap();
+        let nonce = counting(0x10, 13);
+        let aad = counting(0x00, 65536);
+        let plaintext = counting(0x20, 32);
+
+        let sealed = ccm.seal(&nonce, &aad, &plaintext).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&sealed)),
+            (b"69915dad1e84c6376a68c2967e4dab61\
+               5ae0fd1faec44cc484828529463ccf72\
+               b4ac6bec93e8598e7f0dadbcea5b".to_vec())
+        );
+
+        let opened = ccm.open(&nonce, &aad, &sealed).unwrap();
+        assert_eq!((opened), (plaintext));
+    }
+
+    #[test]
+    fn ccm_open_fails_closed_on_any_change() {
+        let ccm = AesCcm::new(&KEY, 8, 3).unwrap();
+        let nonce = counting(0x10, 12);
+        let aad = counting(0x00, 20);
+        let sealed = ccm.seal(&nonce, &aad, &counting(0x20, 24)).unwrap();
+        let failed = Err(CcmError::AuthenticationFailed);
+
+        for i in [0, 23, 24, sealed.len() - 1] {
+            let mut bad = sealed.clone();
+            bad[i] ^= 0x01;
+            assert_eq!((ccm.open(&nonce, &aad, &bad)), (failed));
+        }
+
+        assert_eq!((ccm.open(&nonce, &aad[1..], &sealed)), (failed));
+        assert_eq!((ccm.open(&nonce, &[], &sealed)), (failed));
+    }
+
+    #[test]
+    fn ccm_rejects_bad_parameters() {
+        for size in [0, 3, 5, 18] {
+            let expected = Some(CcmError::InvalidTagSize { size });
+            assert_eq!((AesCcm::new(&KEY, size, 2).err()), (expected));
+        }
+        for size in [0, 1, 9] {
+            let expected = Some(CcmError::InvalidLengthSize { size });
+            assert_eq!((AesCcm::new(&KEY, 16, size).err()), (expected));
+        }
+
+        let ccm = AesCcm::new(&KEY, 16, 2).unwrap();
+        let expected = Err(CcmError::InvalidNonceLength {
+            len: 12,
+            expected: 13,
+        });
+        assert_eq!((ccm.seal(&[0u8; 12], &[], b"")), (expected));
+
+        // A 2-byte length field holds at most 65535 bytes.
+        let long = vec![0u8; 1 << 16];
+        let expected = Err(CcmError::MessageTooLong);
+        assert_eq!((ccm.seal(&[0u8; 13], &[], &long)), (expected));
+
+        let expected = Err(CcmError::CiphertextTooShort { len: 15 });
+        assert_eq!((ccm.open(&[0u8; 13], &[], &[0u8; 15])), (expected));
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+use crate::aes::AesError;
+
+/// Errors of AES-CCM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CcmError {
+    /// The tag must be 4, 6, 8, 10, 12, 14 or 16 bytes.
+    InvalidTagSize { size: usize },
+    /// The length field must be 2 up to 8 bytes.
+    InvalidLengthSize { size: usize },
+    /// The nonce must be 15 minus the length field size bytes.
+    InvalidNonceLength { len: usize, expected: usize },
+    /// The plaintext length does not fit in the length field.
+    MessageTooLong,
+    /// The sealed message is shorter than the tag.
+    CiphertextTooShort { len: usize },
+    /// The tag does not match, no plaintext is returned.
+    AuthenticationFailed,
+    /// Invalid AES key.
+    Aes(AesError),
+}
+
+impl fmt::Display for CcmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CcmError::InvalidTagSize { size } => {
+                write!(f, "CCM tag size {size} is not an even 4 to 16")
+            }
+            CcmError::InvalidLengthSize { size } => {
+                write!(f, "CCM length field size {size} is outside 2 to 8")
+            }
+            CcmError::InvalidNonceLength { len, expected } => {
+                write!(f, "CCM nonce length {len} is not {expected}")
+            }
+            CcmError::MessageTooLong => {
+                write!(f, "CCM plaintext is too long for the length field")
+            }
+            CcmError::CiphertextTooShort { len } => {
+                write!(f, "CCM ciphertext of {len} bytes has no full tag")
+            }
+            CcmError::AuthenticationFailed => {
+                write!(f, "CCM tag mismatch")
+            }
+            CcmError::Aes(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for CcmError {}
+
+impl From<AesError> for CcmError {
+    fn from(error: AesError) -> Self {
+        CcmError::Aes(error)
+    }
+}
@@ -0,0 +1,6 @@
+pub mod error;
+#[allow(clippy::module_inception)]
+pub mod ccm;
+
+pub use error::CcmError;
+pub use ccm::{ open, seal, AesCcm };
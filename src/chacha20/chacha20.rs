@@ -0,0 +1,304 @@
+use crate::chacha20::ChaCha20Error;
+use crate::padd_pars::little_endian_pars_block;
+use crate::utils::{ rotl, z };
+
+/// ChaCha20 key size in bytes.
+pub const KEY_SIZE: usize = 32;
+
+/// ChaCha20 nonce size in bytes (RFC 8439 variant).
+pub const NONCE_SIZE: usize = 12;
+
+/// ChaCha20 keystream block size in bytes.
+pub const BLOCK_SIZE: usize = 64;
+
+// "expand 32-byte k" as four little-endian words.
+const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+/// ChaCha20 stream cipher context, the same for encryption and decryption.
+///
+/// # Description
+/// - The state is the constant, the 256-bit key, a 32-bit block counter
+///   and the 96-bit nonce, all as little-endian words.
+/// - Every keystream block is 20 rounds over the state plus the state
+///   itself, after which the block counter goes up by one.
+/// - Once the counter has used up all 2^32 values, `update` returns
+///   `CounterOverflow` instead of repeating the keystream.
+///
+/// # Reference
+/// [RFC 8439](https://www.rfc-editor.org/rfc/rfc8439), sections 2.3 and 2.4.
+#[derive(Clone)]
+pub struct ChaCha20 {
+    state: [u32; 16],
+    keystream: [u8; BLOCK_SIZE],
+    pos: usize,
+    blocks_left: u64,
+}
+
+impl ChaCha20 {
+    /// Start the keystream at block `counter`.
+    pub fn new(
+        key: &[u8; KEY_SIZE], 
+        nonce: &[u8; NONCE_SIZE], 
+        counter: u32
+    ) -> Self {
+        ChaCha20 {
+            state: initial_state(key, counter, nonce),
+            keystream: [0u8; BLOCK_SIZE],
+            pos: BLOCK_SIZE,
+            blocks_left: (1u64 << 32) - counter as u64,
+        }
+    }
+
+    /// Encrypt or decrypt the next chunk.
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, ChaCha20Error> {
+        // Check the counter space up front, nothing is processed if the
+        // chunk does not fit.
+        let buffered = (BLOCK_SIZE - self.pos) as u64;
+        let needed = (data.len() as u64).saturating_sub(buffered);
+        if needed.div_ceil(BLOCK_SIZE as u64) > self.blocks_left {
+            return Err(ChaCha20Error::CounterOverflow);
+        }
+
+        let mut out = Vec::with_capacity(data.len());
+        for &byte in data {
+            if self.pos == BLOCK_SIZE {
+                self.next_keystream_block();
+            }
+
+            out.push(byte ^ self.keystream[self.pos]);
+            self.pos += 1;
+        }
+        Ok(out)
+    }
+
+    fn next_keystream_block(&mut self) {
+        self.keystream = serialize(&block(&self.state));
+        self.pos = 0;
+        self.blocks_left -= 1;
+        self.state[12] = self.state[12].wrapping_add(1);
+    }
+}
+
+/// ChaCha20: encrypt or decrypt a complete message, starting at block
+/// `counter`.
+pub fn chacha20(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; NONCE_SIZE], 
+    counter: u32, 
+    data: &[u8]
+) -> Result<Vec<u8>, ChaCha20Error> {
+    ChaCha20::new(key, nonce, counter).update(data)
+}
+
+/// ChaCha20 block function: the 64-byte keystream block number `counter`.
+pub fn chacha20_block(
+    key: &[u8; KEY_SIZE], 
+    counter: u32, 
+    nonce: &[u8; NONCE_SIZE]
+) -> [u8; BLOCK_SIZE] {
+    serialize(&block(&initial_state(key, counter, nonce)))
+}
+
+/// The 20 ChaCha rounds (10 column and 10 diagonal rounds) over a state,
+/// without the final addition of the input.
+pub fn chacha20_rounds(input: &[u32; 16]) -> [u32; 16] {
+    let mut x = *input;
+
+    for _ in 0..10 {
+        // Column round.
+        quarter_round(&mut x, 0, 4, 8, 12);
+        quarter_round(&mut x, 1, 5, 9, 13);
+        quarter_round(&mut x, 2, 6, 10, 14);
+        quarter_round(&mut x, 3, 7, 11, 15);
+
+        // Diagonal round.
+        quarter_round(&mut x, 0, 5, 10, 15);
+        quarter_round(&mut x, 1, 6, 11, 12);
+        quarter_round(&mut x, 2, 7, 8, 13);
+        quarter_round(&mut x, 3, 4, 9, 14);
+    }
+
+    x
+}
+
+// constants || key || counter || nonce.
+fn initial_state(
+    key: &[u8; KEY_SIZE], 
+    counter: u32, 
+    nonce: &[u8; NONCE_SIZE]
+) -> [u32; 16] {
+    let mut input = [0u8; 16];
+    input[..4].copy_from_slice(&counter.to_le_bytes());
+    input[4..].copy_from_slice(nonce);
+
+    key_setup(key, &input)
+}
+
+// constants || key || input, where the last four words are the counter
+// and nonce for ChaCha20, or the 128-bit nonce for HChaCha20.
+pub(crate) fn key_setup(key: &[u8; KEY_SIZE], input: &[u8; 16]) -> [u32; 16] {
+    let mut bytes = [0u8; 64];
+    for i in 0..16 {
+        bytes[i] = CONSTANTS[i / 4].to_le_bytes()[i % 4];
+    }
+    bytes[16..48].copy_from_slice(key);
+    bytes[48..].copy_from_slice(input);
+
+    little_endian_pars_block(&bytes)
+}
+
+// Rounds plus the feed-forward addition of the input state.
+fn block(state: &[u32; 16]) -> [u32; 16] {
+    let x = chacha20_rounds(state);
+
+    let mut out = [0u32; 16];
+    for i in 0..16 {
+        out[i] = z(x[i], state[i]);
+    }
+
+    out
+}
+
+// State words to little-endian bytes.
+fn serialize(words: &[u32; 16]) -> [u8; BLOCK_SIZE] {
+    let mut out = [0u8; BLOCK_SIZE];
+    for i in 0..16 {
+        out[4 * i..4 * i + 4].copy_from_slice(&words[i].to_le_bytes());
+    }
+
+    out
+}
+
+// ChaCha quarter round on the words at positions (a, b, c, d).
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[a] = z(x[a], x[b]);
+    x[d] = rotl(x[d] ^ x[a], 16);
+    x[c] = z(x[c], x[d]);
+    x[b] = rotl(x[b] ^ x[c], 12);
+    x[a] = z(x[a], x[b]);
+    x[d] = rotl(x[d] ^ x[a], 8);
+    x[c] = z(x[c], x[d]);
+    x[b] = rotl(x[b] ^ x[c], 7);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    const KEY: [u8; KEY_SIZE] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+        0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+        0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+    ];
+
+    const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: \
+                               If I could offer you only one tip for the \
+                               future, sunscreen would be it.";
+
+    #[test]
+    fn quarter_round_rfc8439_section_2_1_1() {
+        let mut x = [0u32; 16];
+        x[0] = 0x11111111;
+        x[1] = 0x01020304;
+        x[2] = 0x9b8d6f43;
+        x[3] = 0x01234567;
+
+        quarter_round(&mut x, 0, 1, 2, 3);
+
+        let expected = [0xea2a92f4, 0xcb1cf8ce, 0x4581472e, 0x5881c4bb];
+        assert_eq!((x[..4]), (expected));
+    }
+
+    #[test]
+    fn quarter_round_on_state_rfc8439_section_2_2_1() {
+        let mut x = [
+            0x879531e0, 0xc5ecf37d, 0x516461b1, 0xc9a62f8a,
+            0x44c20ef3, 0x3390af7f, 0xd9fc690b, 0x2a5f714c,
+            0x53372767, 0xb00a5631, 0x974c541a, 0x359e9963,
+            0x5c971061, 0x3d631689, 0x2098d9d6, 0x91dbd320,
+        ];
+
+        quarter_round(&mut x, 2, 7, 8, 13);
+
+        assert_eq!((x[2]), (0xbdb886dc));
+        assert_eq!((x[7]), (0xcfacafd2));
+        assert_eq!((x[8]), (0xe46bea80));
+        assert_eq!((x[13]), (0xccc07c79));
+    }
+
+    #[test]
+    fn block_rfc8439_section_2_3_2() {
+        let nonce = [0, 0, 0, 0x09, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+
+        let result = chacha20_block(&KEY, 1, &nonce);
+
+        assert_eq!(
+            (bytes_to_hex(&result)),
+            (b"10f1e7e4d13b5915500fdd1fa32071c4\
+               c7d1f4c733c068030422aa9ac3d46c4e\
+               d2826446079faa0914c2d705d98b02a2\
+               b5129cd1de164eb9cbd083e8a2503c4e")
+        );
+    }
+
+    #[test]
+    fn block_rfc8439_appendix_a1_zero_key() {
+        let result = chacha20_block(&[0u8; KEY_SIZE], 0, &[0u8; NONCE_SIZE]);
+
+        assert_eq!(
+            (bytes_to_hex(&result)),
+            (b"76b8e0ada0f13d90405d6ae55386bd28\
+               bdd219b8a08ded1aa836efcc8b770dc7\
+               da41597c5157488d7724e03fb8d84a37\
+               6a43b8f41518a11cc387b669b2ee6586")
+        );
+    }
+
+    #[test]
+    fn encryption_rfc8439_section_2_4_2() {
+        let nonce = [0, 0, 0, 0, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+
+        let ciphertext = chacha20(&KEY, &nonce, 1, SUNSCREEN).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"6e2e359a2568f98041ba0728dd0d6981\
+               e97e7aec1d4360c20a27afccfd9fae0b\
+               f91b65c5524733ab8f593dabcd62b357\
+               1639d624e65152ab8f530c359f0861d8\
+               07ca0dbf500d6a6156a38e088a22b65e\
+               52bc514d16ccf806818ce91ab7793736\
+               5af90bbf74a35be6b40b8eedf2785e42\
+               874d".to_vec())
+        );
+
+        let plaintext = chacha20(&KEY, &nonce, 1, &ciphertext).unwrap();
+        assert_eq!((plaintext), (SUNSCREEN.to_vec()));
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        let nonce = [0, 0, 0, 0, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+
+        let mut context = ChaCha20::new(&KEY, &nonce, 1);
+        let mut result = Vec::new();
+        for chunk in SUNSCREEN.chunks(7) {
+            result.extend(context.update(chunk).unwrap());
+        }
+
+        assert_eq!((result), (chacha20(&KEY, &nonce, 1, SUNSCREEN).unwrap()));
+    }
+
+    #[test]
+    fn counter_overflow_is_an_error() {
+        let nonce = [0u8; NONCE_SIZE];
+
+        // The last counter value still gives one whole block.
+        let mut context = ChaCha20::new(&KEY, &nonce, u32::MAX);
+        let overflow = Err(ChaCha20Error::CounterOverflow);
+        assert_eq!((context.update(&[0u8; 65])), (overflow));
+        assert!(context.update(&[0u8; 64]).is_ok());
+        assert_eq!((context.update(&[0u8])), (overflow));
+    }
+}
@@ -0,0 +1,385 @@
+use crate::chacha20::{ chacha20_block, ChaCha20, ChaCha20Error };
+use crate::chacha20::{ BLOCK_SIZE, KEY_SIZE, NONCE_SIZE };
+use crate::poly1305::Poly1305;
+use crate::utils::ct_eq;
+
+/// ChaCha20-Poly1305 tag size in bytes.
+pub const TAG_SIZE: usize = 16;
+
+// Counter values 1 to 2^32 - 1 encrypt the plaintext.
+const MAX_PLAINTEXT_LEN: u64 = ((1 << 32) - 1) * BLOCK_SIZE as u64;
+
+/// ChaCha20-Poly1305 authenticated encryption.
+///
+/// # Description
+/// - The one-time Poly1305 key is the first half of ChaCha20 block 0.
+/// - The plaintext is encrypted with ChaCha20 starting at block 1.
+/// - The tag is Poly1305 over aad || pad16 || ciphertext || pad16 ||
+///   len(aad) || len(ciphertext), the lengths as 64-bit little-endian.
+/// - `seal` returns ciphertext || tag; `open` checks the tag in constant
+///   time before anything is decrypted.
+///
+/// # Reference
+/// [RFC 8439](https://www.rfc-editor.org/rfc/rfc8439), section 2.8.
+#[derive(Clone)]
+pub struct ChaCha20Poly1305 {
+    key: [u8; KEY_SIZE],
+}
+
+impl ChaCha20Poly1305 {
+    pub fn new(key: &[u8; KEY_SIZE]) -> Self {
+        ChaCha20Poly1305 { key: *key }
+    }
+
+    /// Encrypt `plaintext` and authenticate it together with `aad`.
+    pub fn seal(
+        &self, 
+        nonce: &[u8; NONCE_SIZE], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, ChaCha20Error> {
+        if plaintext.len() as u64 > MAX_PLAINTEXT_LEN {
+            return Err(ChaCha20Error::MessageTooLong);
+        }
+
+        let mut sealed = ChaCha20::new(&self.key, nonce, 1).update(plaintext)?;
+        let tag = self.tag(nonce, aad, &sealed);
+        sealed.extend_from_slice(&tag);
+
+        Ok(sealed)
+    }
+
+    /// Verify and decrypt the output of `seal`.
+    ///
+    /// Fails closed: on a tag mismatch no plaintext is produced at all.
+    pub fn open(
+        &self, 
+        nonce: &[u8; NONCE_SIZE], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, ChaCha20Error> {
+        if sealed.len() < TAG_SIZE {
+            let len = sealed.len();
+            return Err(ChaCha20Error::CiphertextTooShort { len });
+        }
+
+        let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_SIZE);
+        if ciphertext.len() as u64 > MAX_PLAINTEXT_LEN {
+            return Err(ChaCha20Error::MessageTooLong);
+        }
+
+        let expected = self.tag(nonce, aad, ciphertext);
+        if !ct_eq(&expected, tag) {
+            return Err(ChaCha20Error::AuthenticationFailed);
+        }
+
+        ChaCha20::new(&self.key, nonce, 1).update(ciphertext)
+    }
+
+    fn tag(
+        &self, 
+        nonce: &[u8; NONCE_SIZE], 
+        aad: &[u8], 
+        ciphertext: &[u8]
+    ) -> [u8; TAG_SIZE] {
+        let mut mac = Poly1305::new(&poly1305_key_gen(&self.key, nonce));
+        mac.update_padded(aad);
+        mac.update(ciphertext);
+
+        let (aad_len, text_len) = (aad.len() as u64, ciphertext.len() as u64);
+        finalize_tag(mac, aad_len, text_len)
+    }
+}
+
+/// Chunked ChaCha20-Poly1305 encryption of one message.
+///
+/// # Description
+/// - `new` takes the associated data up front, `update` encrypts the
+///   plaintext chunk by chunk and `finalize` returns the tag.
+/// - The concatenated chunks followed by the tag are exactly the output of
+///   `seal` on the whole plaintext.
+#[derive(Clone)]
+pub struct ChaCha20Poly1305Encryptor {
+    cipher: ChaCha20,
+    mac: Poly1305,
+    aad_len: u64,
+    text_len: u64,
+}
+
+impl ChaCha20Poly1305Encryptor {
+    pub fn new(
+        key: &[u8; KEY_SIZE], 
+        nonce: &[u8; NONCE_SIZE], 
+        aad: &[u8]
+    ) -> Self {
+        let mut mac = Poly1305::new(&poly1305_key_gen(key, nonce));
+        mac.update_padded(aad);
+
+        ChaCha20Poly1305Encryptor {
+            cipher: ChaCha20::new(key, nonce, 1),
+            mac,
+            aad_len: aad.len() as u64,
+            text_len: 0,
+        }
+    }
+
+    /// Encrypt the next chunk of plaintext.
+    pub fn update(
+        &mut self, 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, ChaCha20Error> {
+        let ciphertext = self.cipher.update(plaintext)
+            .map_err(|_| ChaCha20Error::MessageTooLong)?;
+        self.mac.update(&ciphertext);
+        self.text_len += ciphertext.len() as u64;
+
+        Ok(ciphertext)
+    }
+
+    /// Tag over the associated data and all ciphertext chunks.
+    pub fn finalize(self) -> [u8; TAG_SIZE] {
+        finalize_tag(self.mac, self.aad_len, self.text_len)
+    }
+}
+
+/// Chunked ChaCha20-Poly1305 decryption of one message.
+///
+/// # Description
+/// - `new` takes the associated data up front, `update` decrypts the
+///   ciphertext chunk by chunk and `finalize` checks the tag in constant
+///   time.
+/// - The plaintext chunks are NOT authenticated until `finalize` returns
+///   `Ok`. On an error all of them must be discarded, so write them
+///   somewhere temporary rather than acting on them straight away.
+#[derive(Clone)]
+pub struct ChaCha20Poly1305Decryptor {
+    cipher: ChaCha20,
+    mac: Poly1305,
+    aad_len: u64,
+    text_len: u64,
+}
+
+impl ChaCha20Poly1305Decryptor {
+    pub fn new(
+        key: &[u8; KEY_SIZE], 
+        nonce: &[u8; NONCE_SIZE], 
+        aad: &[u8]
+    ) -> Self {
+        let mut mac = Poly1305::new(&poly1305_key_gen(key, nonce));
+        mac.update_padded(aad);
+
+        ChaCha20Poly1305Decryptor {
+            cipher: ChaCha20::new(key, nonce, 1),
+            mac,
+            aad_len: aad.len() as u64,
+            text_len: 0,
+        }
+    }
+
+    /// Decrypt the next chunk of ciphertext, unauthenticated until
+    /// `finalize`.
+    pub fn update(
+        &mut self, 
+        ciphertext: &[u8]
+    ) -> Result<Vec<u8>, ChaCha20Error> {
+        let plaintext = self.cipher.update(ciphertext)
+            .map_err(|_| ChaCha20Error::MessageTooLong)?;
+        self.mac.update(ciphertext);
+        self.text_len += ciphertext.len() as u64;
+
+        Ok(plaintext)
+    }
+
+    /// Check `tag` against the associated data and all ciphertext chunks.
+    pub fn finalize(self, tag: &[u8]) -> Result<(), ChaCha20Error> {
+        let expected = finalize_tag(self.mac, self.aad_len, self.text_len);
+        if !ct_eq(&expected, tag) {
+            return Err(ChaCha20Error::AuthenticationFailed);
+        }
+
+        Ok(())
+    }
+}
+
+/// Poly1305 one-time key: the first 32 bytes of ChaCha20 block 0.
+pub fn poly1305_key_gen(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; NONCE_SIZE]
+) -> [u8; 32] {
+    let block = chacha20_block(key, 0, nonce);
+
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&block[..32]);
+    out
+}
+
+// Pad the ciphertext, absorb both lengths as 64-bit little-endian and
+// return the tag.
+fn finalize_tag(
+    mut mac: Poly1305, 
+    aad_len: u64, 
+    text_len: u64
+) -> [u8; TAG_SIZE] {
+    let rem = (text_len % 16) as usize;
+    if rem != 0 {
+        mac.update(&[0u8; 16][rem..]);
+    }
+
+    mac.update(&aad_len.to_le_bytes());
+    mac.update(&text_len.to_le_bytes());
+    mac.finalize()
+}
+
+/// ChaCha20-Poly1305: encrypt and authenticate, returns ciphertext || tag.
+pub fn seal(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; NONCE_SIZE], 
+    aad: &[u8], 
+    plaintext: &[u8]
+) -> Result<Vec<u8>, ChaCha20Error> {
+    ChaCha20Poly1305::new(key).seal(nonce, aad, plaintext)
+}
+
+/// ChaCha20-Poly1305: verify and decrypt ciphertext || tag.
+pub fn open(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; NONCE_SIZE], 
+    aad: &[u8], 
+    sealed: &[u8]
+) -> Result<Vec<u8>, ChaCha20Error> {
+    ChaCha20Poly1305::new(key).open(nonce, aad, sealed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    const KEY: [u8; KEY_SIZE] = [
+        0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+        0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e, 0x8f,
+        0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
+        0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d, 0x9e, 0x9f,
+    ];
+
+    const NONCE: [u8; NONCE_SIZE] = [
+        0x07, 0x00, 0x00, 0x00, 0x40, 0x41, 0x42, 0x43,
+        0x44, 0x45, 0x46, 0x47,
+    ];
+
+    const AAD: [u8; 12] = [
+        0x50, 0x51, 0x52, 0x53, 0xc0, 0xc1, 0xc2, 0xc3,
+        0xc4, 0xc5, 0xc6, 0xc7,
+    ];
+
+    const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: \
+                               If I could offer you only one tip for the \
+                               future, sunscreen would be it.";
+
+    #[test]
+    fn poly1305_key_gen_rfc8439_section_2_6_2() {
+        let nonce = [0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7];
+
+        let result = poly1305_key_gen(&KEY, &nonce);
+
+        assert_eq!(
+            (bytes_to_hex(&result)),
+            (b"8ad5a08b905f81cc815040274ab29471\
+               a833b637e3fd0da508dbb8e2fdd1a646")
+        );
+    }
+
+    #[test]
+    fn seal_rfc8439_section_2_8_2() {
+        let sealed = seal(&KEY, &NONCE, &AAD, SUNSCREEN).unwrap();
+
+        assert_eq!(
+            (bytes_to_hex(&sealed)),
+            (b"d31a8d34648e60db7b86afbc53ef7ec2\
+               a4aded51296e08fea9e2b5a736ee62d6\
+               3dbea45e8ca9671282fafb69da92728b\
+               1a71de0a9e060b2905d6a5b67ecd3b36\
+               92ddbd7f2d778b8c9803aee328091b58\
+               fab324e4fad675945585808b4831d7bc\
+               3ff4def08e4b7a9de576d26586cec64b\
+               6116\
+               1ae10b594f09e26a7e902ecbd0600691".to_vec())
+        );
+
+        let opened = open(&KEY, &NONCE, &AAD, &sealed).unwrap();
+        assert_eq!((opened), (SUNSCREEN.to_vec()));
+    }
+
+    #[test]
+    fn empty_plaintext_is_only_a_tag() {
+        let sealed = seal(&KEY, &NONCE, &AAD, b"").unwrap();
+        assert_eq!((sealed.len()), (TAG_SIZE));
+
+        let opened = open(&KEY, &NONCE, &AAD, &sealed).unwrap();
+        assert_eq!((opened), (Vec::<u8>::new()));
+    }
+
+    #[test]
+    fn chunked_matches_one_shot() {
+        let sealed = seal(&KEY, &NONCE, &AAD, SUNSCREEN).unwrap();
+
+        let mut encryptor = ChaCha20Poly1305Encryptor::new(&KEY, &NONCE, &AAD);
+        let mut result = Vec::new();
+        for chunk in SUNSCREEN.chunks(13) {
+            result.extend(encryptor.update(chunk).unwrap());
+        }
+        result.extend_from_slice(&encryptor.finalize());
+        assert_eq!((result), (sealed));
+
+        let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_SIZE);
+        let mut decryptor = ChaCha20Poly1305Decryptor::new(&KEY, &NONCE, &AAD);
+        let mut plaintext = Vec::new();
+        for chunk in ciphertext.chunks(29) {
+            plaintext.extend(decryptor.update(chunk).unwrap());
+        }
+        assert_eq!((decryptor.finalize(tag)), (Ok(())));
+        assert_eq!((plaintext), (SUNSCREEN.to_vec()));
+    }
+
+    #[test]
+    fn chunked_decryption_rejects_a_bad_tag() {
+        let sealed = seal(&KEY, &NONCE, &AAD, SUNSCREEN).unwrap();
+        let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_SIZE);
+        let mut bad = tag.to_vec();
+        bad[0] ^= 0x01;
+
+        let mut decryptor = ChaCha20Poly1305Decryptor::new(&KEY, &NONCE, &AAD);
+        decryptor.update(ciphertext).unwrap();
+
+        let failed = Err(ChaCha20Error::AuthenticationFailed);
+        assert_eq!((decryptor.finalize(&bad)), (failed));
+    }
+
+    #[test]
+    fn tampering_fails_authentication() {
+        let sealed = seal(&KEY, &NONCE, &AAD, SUNSCREEN).unwrap();
+        let failed = Err(ChaCha20Error::AuthenticationFailed);
+
+        for i in [0, 57, sealed.len() - 1] {
+            let mut bad = sealed.clone();
+            bad[i] ^= 0x01;
+            assert_eq!((open(&KEY, &NONCE, &AAD, &bad)), (failed));
+        }
+
+        let mut aad = AAD;
+        aad[0] ^= 0x80;
+        assert_eq!((open(&KEY, &NONCE, &aad, &sealed)), (failed));
+
+        let mut nonce = NONCE;
+        nonce[11] ^= 0x01;
+        assert_eq!((open(&KEY, &nonce, &AAD, &sealed)), (failed));
+    }
+
+    #[test]
+    fn short_ciphertext_is_an_error() {
+        let result = open(&KEY, &NONCE, &AAD, &[0u8; 15]);
+
+        let expected = Err(ChaCha20Error::CiphertextTooShort { len: 15 });
+        assert_eq!((result), (expected));
+    }
+}
@@ -0,0 +1,36 @@
+use std::fmt;
+
+/// Errors of ChaCha20 and ChaCha20-Poly1305.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaCha20Error {
+    /// The 32-bit block counter would wrap around and repeat keystream.
+    CounterOverflow,
+    /// Plaintexts are limited to 2^32 - 1 blocks of 64 bytes.
+    MessageTooLong,
+    /// The sealed message is shorter than the 16-byte tag.
+    CiphertextTooShort { len: usize },
+    /// The tag does not match, nothing is decrypted.
+    AuthenticationFailed,
+}
+
+impl fmt::Display for ChaCha20Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChaCha20Error::CounterOverflow => {
+                write!(f, "ChaCha20 block counter overflow")
+            }
+            ChaCha20Error::MessageTooLong => {
+                write!(f, "ChaCha20-Poly1305 plaintext is too long")
+            }
+            ChaCha20Error::CiphertextTooShort { len } => {
+                write!(f, "ChaCha20-Poly1305 ciphertext of {len} bytes has no \
+                           full tag")
+            }
+            ChaCha20Error::AuthenticationFailed => {
+                write!(f, "ChaCha20-Poly1305 tag mismatch")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChaCha20Error {}
@@ -0,0 +1,20 @@
+pub mod error;
+#[allow(clippy::module_inception)]
+pub mod chacha20;
+pub mod chacha20_poly1305;
+pub mod xchacha20;
+pub mod xchacha20_poly1305;
+
+pub use error::ChaCha20Error;
+pub use chacha20::{
+    chacha20, chacha20_block, chacha20_rounds, ChaCha20, BLOCK_SIZE, KEY_SIZE,
+    NONCE_SIZE
+};
+pub use chacha20_poly1305::{
+    open, poly1305_key_gen, seal, ChaCha20Poly1305, ChaCha20Poly1305Decryptor,
+    ChaCha20Poly1305Encryptor, TAG_SIZE
+};
+pub use xchacha20::{ hchacha20, xchacha20, XNONCE_SIZE };
+pub use xchacha20_poly1305::{
+    xchacha20_poly1305_open, xchacha20_poly1305_seal, XChaCha20Poly1305
+};
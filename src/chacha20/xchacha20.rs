@@ -0,0 +1,147 @@
+use crate::chacha20::chacha20::key_setup;
+use crate::chacha20::{
+    chacha20_rounds, ChaCha20, ChaCha20Error, KEY_SIZE, NONCE_SIZE
+};
+
+/// XChaCha20 nonce size in bytes.
+pub const XNONCE_SIZE: usize = 24;
+
+/// HChaCha20: derive a 256-bit subkey from a key and a 128-bit nonce.
+///
+/// # Description
+/// - The state is set up like ChaCha20, with the 16-byte nonce in place of
+///   the block counter and nonce.
+/// - After the 20 rounds, without the feed-forward addition, the first and
+///   last rows (words 0..4 and 12..16) are the subkey.
+///
+/// # Reference
+/// [draft-irtf-cfrg-xchacha](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-xchacha-03),
+/// section 2.2.
+pub fn hchacha20(key: &[u8; KEY_SIZE], nonce: &[u8; 16]) -> [u8; KEY_SIZE] {
+    let x = chacha20_rounds(&key_setup(key, nonce));
+
+    let mut out = [0u8; KEY_SIZE];
+    for i in 0..4 {
+        out[4 * i..4 * i + 4].copy_from_slice(&x[i].to_le_bytes());
+        out[16 + 4 * i..20 + 4 * i].copy_from_slice(&x[12 + i].to_le_bytes());
+    }
+
+    out
+}
+
+impl ChaCha20 {
+    /// XChaCha20: ChaCha20 under the HChaCha20 subkey of the first 16 nonce
+    /// bytes, with 0^32 || the last 8 nonce bytes as the 96-bit nonce.
+    pub fn new_xchacha(
+        key: &[u8; KEY_SIZE], 
+        nonce: &[u8; XNONCE_SIZE], 
+        counter: u32
+    ) -> Self {
+        let (subkey, chacha_nonce) = xchacha20_setup(key, nonce);
+        ChaCha20::new(&subkey, &chacha_nonce, counter)
+    }
+}
+
+/// XChaCha20: encrypt or decrypt a complete message, starting at block
+/// `counter`.
+pub fn xchacha20(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; XNONCE_SIZE], 
+    counter: u32, 
+    data: &[u8]
+) -> Result<Vec<u8>, ChaCha20Error> {
+    ChaCha20::new_xchacha(key, nonce, counter).update(data)
+}
+
+// Subkey and 96-bit nonce of the inner ChaCha20.
+pub(crate) fn xchacha20_setup(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; XNONCE_SIZE]
+) -> ([u8; KEY_SIZE], [u8; NONCE_SIZE]) {
+    let mut hnonce = [0u8; 16];
+    hnonce.copy_from_slice(&nonce[..16]);
+
+    let mut chacha_nonce = [0u8; NONCE_SIZE];
+    chacha_nonce[4..].copy_from_slice(&nonce[16..]);
+
+    (hchacha20(key, &hnonce), chacha_nonce)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    #[test]
+    fn hchacha20_draft_section_2_2_1() {
+        let key = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+        ];
+        let nonce = [
+            0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x4a,
+            0x00, 0x00, 0x00, 0x00, 0x31, 0x41, 0x59, 0x27,
+        ];
+
+        let result = hchacha20(&key, &nonce);
+
+        assert_eq!(
+            (bytes_to_hex(&result)),
+            (b"82413b4227b27bfed30e42508a877d73\
+               a0f9e4d58a74a853c12ec41326d3ecdc")
+        );
+    }
+
+    #[test]
+    fn xchacha20_draft_appendix_a_3_2() {
+        let key = [
+            0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+            0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e, 0x8f,
+            0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
+            0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d, 0x9e, 0x9f,
+        ];
+        // The last byte really is 0x58 in the draft.
+        let nonce = [
+            0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+            0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
+            0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x58,
+        ];
+        let plaintext = b"The dhole (pronounced \"dole\") is also known as \
+                          the Asiatic wild dog, red dog, and whistling dog. \
+                          It is about the size of a German shepherd but looks \
+                          more like a long-legged fox. This highly elusive \
+                          and skilled jumper is classified with wolves, \
+                          coyotes, jackals, and foxes in the taxonomic family \
+                          Canidae.";
+
+        // The draft skips the first keystream block.
+        let ciphertext = xchacha20(&key, &nonce, 1, plaintext).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"7d0a2e6b7f7c65a236542630294e063b\
+               7ab9b555a5d5149aa21e4ae1e4fbce87\
+               ecc8e08a8b5e350abe622b2ffa617b20\
+               2cfad72032a3037e76ffdcdc4376ee05\
+               3a190d7e46ca1de04144850381b9cb29\
+               f051915386b8a710b8ac4d027b8b050f\
+               7cba5854e028d564e453b8a968824173\
+               fc16488b8970cac828f11ae53cabd201\
+               12f87107df24ee6183d2274fe4c8b148\
+               5534ef2c5fbc1ec24bfc3663efaa08bc\
+               047d29d25043532db8391a8a3d776bf4\
+               372a6955827ccb0cdd4af403a7ce4c63\
+               d595c75a43e045f0cce1f29c8b93bd65\
+               afc5974922f214a40b7c402cdb91ae73\
+               c0b63615cdad0480680f16515a7ace9d\
+               39236464328a37743ffc28f4ddb324f4\
+               d0f5bbdc270c65b1749a6efff1fbaa09\
+               536175ccd29fb9e6057b307320d31683\
+               8a9c71f70b5b5907a66f7ea49aadc409".to_vec())
+        );
+
+        let decrypted = xchacha20(&key, &nonce, 1, &ciphertext).unwrap();
+        assert_eq!((decrypted), (plaintext.to_vec()));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::chacha20::xchacha20::xchacha20_setup;
+use crate::chacha20::{
+    ChaCha20Error, ChaCha20Poly1305, ChaCha20Poly1305Decryptor,
+    ChaCha20Poly1305Encryptor, KEY_SIZE, XNONCE_SIZE
+};
+
+/// XChaCha20-Poly1305 authenticated encryption with 192-bit nonces.
+///
+/// # Description
+/// - HChaCha20 of the key and the first 16 nonce bytes gives a subkey.
+/// - The message is sealed with ChaCha20-Poly1305 under that subkey and
+///   the 96-bit nonce 0^32 || the last 8 nonce bytes.
+/// - 192-bit nonces are long enough to be picked at random for every
+///   message, no counter has to be kept.
+/// - `encryptor` and `decryptor` give the chunked API for large inputs,
+///   with the same output as `seal` and `open`.
+///
+/// # Reference
+/// [draft-irtf-cfrg-xchacha](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-xchacha-03),
+/// section 2.
+#[derive(Clone)]
+pub struct XChaCha20Poly1305 {
+    key: [u8; KEY_SIZE],
+}
+
+impl XChaCha20Poly1305 {
+    pub fn new(key: &[u8; KEY_SIZE]) -> Self {
+        XChaCha20Poly1305 { key: *key }
+    }
+
+    /// Encrypt `plaintext` and authenticate it together with `aad`.
+    pub fn seal(
+        &self, 
+        nonce: &[u8; XNONCE_SIZE], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, ChaCha20Error> {
+        let (subkey, chacha_nonce) = xchacha20_setup(&self.key, nonce);
+        ChaCha20Poly1305::new(&subkey).seal(&chacha_nonce, aad, plaintext)
+    }
+
+    /// Verify and decrypt the output of `seal`.
+    ///
+    /// Fails closed: on a tag mismatch no plaintext is produced at all.
+    pub fn open(
+        &self, 
+        nonce: &[u8; XNONCE_SIZE], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, ChaCha20Error> {
+        let (subkey, chacha_nonce) = xchacha20_setup(&self.key, nonce);
+        ChaCha20Poly1305::new(&subkey).open(&chacha_nonce, aad, sealed)
+    }
+
+    /// Chunked encryption of one message, see `ChaCha20Poly1305Encryptor`.
+    pub fn encryptor(
+        &self, 
+        nonce: &[u8; XNONCE_SIZE], 
+        aad: &[u8]
+    ) -> ChaCha20Poly1305Encryptor {
+        let (subkey, chacha_nonce) = xchacha20_setup(&self.key, nonce);
+        ChaCha20Poly1305Encryptor::new(&subkey, &chacha_nonce, aad)
+    }
+
+    /// Chunked decryption of one message, see `ChaCha20Poly1305Decryptor`.
+    pub fn decryptor(
+        &self, 
+        nonce: &[u8; XNONCE_SIZE], 
+        aad: &[u8]
+    ) -> ChaCha20Poly1305Decryptor {
+        let (subkey, chacha_nonce) = xchacha20_setup(&self.key, nonce);
+        ChaCha20Poly1305Decryptor::new(&subkey, &chacha_nonce, aad)
+    }
+}
+
+/// XChaCha20-Poly1305: encrypt and authenticate, returns
+/// ciphertext || tag.
+pub fn xchacha20_poly1305_seal(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; XNONCE_SIZE], 
+    aad: &[u8], 
+    plaintext: &[u8]
+) -> Result<Vec<u8>, ChaCha20Error> {
+    XChaCha20Poly1305::new(key).seal(nonce, aad, plaintext)
+}
+
+/// XChaCha20-Poly1305: verify and decrypt ciphertext || tag.
+pub fn xchacha20_poly1305_open(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; XNONCE_SIZE], 
+    aad: &[u8], 
+    sealed: &[u8]
+) -> Result<Vec<u8>, ChaCha20Error> {
+    XChaCha20Poly1305::new(key).open(nonce, aad, sealed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chacha20::TAG_SIZE;
+    use crate::sha3::bytes_to_hex;
+
+    const KEY: [u8; KEY_SIZE] = [
+        0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+        0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e, 0x8f,
+        0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
+        0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d, 0x9e, 0x9f,
+    ];
+
+    const NONCE: [u8; XNONCE_SIZE] = [
+        0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+        0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
+        0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57,
+    ];
+
+    const AAD: [u8; 12] = [
+        0x50, 0x51, 0x52, 0x53, 0xc0, 0xc1, 0xc2, 0xc3,
+        0xc4, 0xc5, 0xc6, 0xc7,
+    ];
+
+    const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: \
+                               If I could offer you only one tip for the \
+                               future, sunscreen would be it.";
+
+    #[test]
+    fn seal_draft_appendix_a_3_1() {
+        let sealed = xchacha20_poly1305_seal(&KEY, &NONCE, &AAD, SUNSCREEN);
+        let sealed = sealed.unwrap();
+
+        assert_eq!(
+            (bytes_to_hex(&sealed)),
+            (b"bd6d179d3e83d43b9576579493c0e939\
+               572a1700252bfaccbed2902c21396cbb\
+               731c7f1b0b4aa6440bf3a82f4eda7e39\
+               ae64c6708c54c216cb96b72e1213b452\
+               2f8c9ba40db5d945b11b69b982c1bb9e\
+               3f3fac2bc369488f76b2383565d3fff9\
+               21f9664c97637da9768812f615c68b13\
+               b52e\
+               c0875924c1c7987947deafd8780acf49".to_vec())
+        );
+
+        let opened = xchacha20_poly1305_open(&KEY, &NONCE, &AAD, &sealed);
+        assert_eq!((opened.unwrap()), (SUNSCREEN.to_vec()));
+    }
+
+    #[test]
+    fn chunked_matches_one_shot() {
+        let aead = XChaCha20Poly1305::new(&KEY);
+        let msg = SUNSCREEN.repeat(10);
+        let sealed = aead.seal(&NONCE, &AAD, &msg).unwrap();
+
+        let mut encryptor = aead.encryptor(&NONCE, &AAD);
+        let mut result = Vec::new();
+        for chunk in msg.chunks(100) {
+            result.extend(encryptor.update(chunk).unwrap());
+        }
+        result.extend_from_slice(&encryptor.finalize());
+        assert_eq!((result), (sealed));
+
+        let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_SIZE);
+        let mut decryptor = aead.decryptor(&NONCE, &AAD);
+        let mut plaintext = Vec::new();
+        for chunk in ciphertext.chunks(77) {
+            plaintext.extend(decryptor.update(chunk).unwrap());
+        }
+        assert_eq!((decryptor.finalize(tag)), (Ok(())));
+        assert_eq!((plaintext), (msg));
+    }
+
+    #[test]
+    fn every_nonce_byte_is_authenticated() {
+        let aead = XChaCha20Poly1305::new(&KEY);
+        let sealed = aead.seal(&NONCE, &AAD, SUNSCREEN).unwrap();
+        let failed = Err(ChaCha20Error::AuthenticationFailed);
+
+        // Bytes 0..16 go into the subkey, 16..24 into the ChaCha20 nonce.
+        for i in [0, 15, 16, 23] {
+            let mut nonce = NONCE;
+            nonce[i] ^= 0x01;
+            assert_eq!((aead.open(&nonce, &AAD, &sealed)), (failed));
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use crate::aes::{ Aes, BLOCK_SIZE };
+use crate::cmac::CmacError;
+use crate::utils::ct_eq;
+
+/// CMAC tag size in bytes, only full tags are produced and accepted.
+pub const TAG_SIZE: usize = 16;
+
+// R_128 = x^128 + x^7 + x^2 + x + 1, without the x^128 term.
+const R_128: u128 = 0x87;
+
+/// AES-CMAC message authentication code.
+///
+/// # Description
+/// - `new` derives the subkeys K1 and K2 from L = E(K, 0^128).
+/// - Every block but the last is chained through AES as in CBC-MAC.
+/// - A complete last block is XORed with K1; an incomplete (or empty) one
+///   is padded with 10* and XORed with K2 before the last encryption.
+/// - `update` always holds back the last block, since it can only be
+///   processed once it is known to be the last.
+///
+/// # Reference
+/// [NIST SP 800-38B](https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-38B.pdf)
+#[derive(Clone)]
+pub struct Cmac {
+    aes: Aes,
+    k1: [u8; BLOCK_SIZE],
+    k2: [u8; BLOCK_SIZE],
+    state: [u8; BLOCK_SIZE],
+    buffer: [u8; BLOCK_SIZE],
+    len: usize,
+}
+
+impl Cmac {
+    /// AES-128, AES-192 or AES-256-CMAC, picked by the key length.
+    pub fn new(key: &[u8]) -> Result<Self, CmacError> {
+        let aes = Aes::new(key)?;
+        let (k1, k2) = generate_subkeys(&aes);
+
+        Ok(Cmac {
+            aes,
+            k1,
+            k2,
+            state: [0u8; BLOCK_SIZE],
+            buffer: [0u8; BLOCK_SIZE],
+            len: 0,
+        })
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            // A full buffer is only processed once more data follows.
+            if self.len == BLOCK_SIZE {
+                self.process_buffer();
+            }
+
+            self.buffer[self.len] = byte;
+            self.len += 1;
+        }
+    }
+
+    /// Process the last block and return the 16-byte tag.
+    pub fn finalize(mut self) -> [u8; TAG_SIZE] {
+        let subkey = if self.len == BLOCK_SIZE {
+            self.k1
+        } else {
+            // 10* padding.
+            self.buffer[self.len] = 0x80;
+            for i in self.len + 1..BLOCK_SIZE {
+                self.buffer[i] = 0x00;
+            }
+            self.k2
+        };
+
+        for (i, byte) in self.state.iter_mut().enumerate() {
+            *byte ^= self.buffer[i] ^ subkey[i];
+        }
+        self.aes.encrypt_block(&self.state)
+    }
+
+    /// Check `tag` against the message in constant time.
+    pub fn verify(self, tag: &[u8]) -> Result<(), CmacError> {
+        if !ct_eq(&self.finalize(), tag) {
+            return Err(CmacError::AuthenticationFailed);
+        }
+
+        Ok(())
+    }
+
+    fn process_buffer(&mut self) {
+        for i in 0..BLOCK_SIZE {
+            self.state[i] ^= self.buffer[i];
+        }
+        self.state = self.aes.encrypt_block(&self.state);
+        self.len = 0;
+    }
+}
+
+/// CMAC subkeys K1 = dbl(L) and K2 = dbl(K1), with L = E(K, 0^128).
+pub fn generate_subkeys(aes: &Aes) -> ([u8; BLOCK_SIZE], [u8; BLOCK_SIZE]) {
+    let l = aes.encrypt_block(&[0u8; BLOCK_SIZE]);
+    let k1 = dbl(&l);
+    let k2 = dbl(&k1);
+
+    (k1, k2)
+}
+
+/// Doubling in GF(2^128): shift left by one bit and, if the bit shifted
+/// out was set, reduce by XORing 0x87 into the last byte.
+///
+/// The reduction is masked rather than branched on, since the block is
+/// secret key material.
+pub fn dbl(block: &[u8; BLOCK_SIZE]) -> [u8; BLOCK_SIZE] {
+    let x = u128::from_be_bytes(*block);
+    let carry = x >> 127;
+
+    ((x << 1) ^ (carry.wrapping_neg() & R_128)).to_be_bytes()
+}
+
+/// AES-CMAC: one-shot tag of a complete message.
+pub fn cmac(key: &[u8], msg: &[u8]) -> Result<[u8; TAG_SIZE], CmacError> {
+    let mut mac = Cmac::new(key)?;
+    mac.update(msg);
+    Ok(mac.finalize())
+}
+
+/// AES-CMAC: check `tag` against a complete message in constant time.
+pub fn cmac_verify(
+    key: &[u8],
+    msg: &[u8],
+    tag: &[u8]
+) -> Result<(), CmacError> {
+    let mut mac = Cmac::new(key)?;
+    mac.update(msg);
+    mac.verify(tag)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aes::AesError;
+    use crate::modes::sp800_38a::{ KEY_128, KEY_256, PLAINTEXT };
+    use crate::sha3::bytes_to_hex;
+
+    #[test]
+    fn subkeys_sp800_38b_d_1() {
+        let aes = Aes::new(&KEY_128).unwrap();
+
+        let (k1, k2) = generate_subkeys(&aes);
+
+        assert_eq!((bytes_to_hex(&k1)), (b"fbeed618357133667c85e08f7236a8de"));
+        assert_eq!((bytes_to_hex(&k2)), (b"f7ddac306ae266ccf90bc11ee46d513b"));
+    }
+
+    // SP 800-38B appendix D, messages of 0, 16, 40 and 64 bytes.
+    #[test]
+    fn aes128_sp800_38b_d_1() {
+        let tags: [&[u8]; 4] = [
+            b"bb1d6929e95937287fa37d129b756746",
+            b"070a16b46b4d4144f79bdd9dd04a287c",
+            b"dfa66747de9ae63030ca32611497c827",
+            b"51f0bebf7e3b9d92fc49741779363cfe",
+        ];
+
+        for (len, tag) in [0, 16, 40, 64].into_iter().zip(tags) {
+            let result = cmac(&KEY_128, &PLAINTEXT[..len]).unwrap();
+            assert_eq!((bytes_to_hex(&result)), (tag.to_vec()));
+        }
+    }
+
+    #[test]
+    fn aes192_sp800_38b_d_2() {
+        let key = [
+            0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52,
+            0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90, 0x79, 0xe5,
+            0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b,
+        ];
+        let tags: [&[u8]; 4] = [
+            b"d17ddf46adaacde531cac483de7a9367",
+            b"9e99a7bf31e710900662f65e617c5184",
+            b"8a1de5be2eb31aad089a82e6ee908b0e",
+            b"a1d5df0eed790f794d77589659f39a11",
+        ];
+
+        for (len, tag) in [0, 16, 40, 64].into_iter().zip(tags) {
+            let result = cmac(&key, &PLAINTEXT[..len]).unwrap();
+            assert_eq!((bytes_to_hex(&result)), (tag.to_vec()));
+        }
+    }
+
+    #[test]
+    fn aes256_sp800_38b_d_3() {
+        let tags: [&[u8]; 4] = [
+            b"028962f61b7bf89efc6b551f4667d983",
+            b"28a7023f452e8f82bd4bf28d8c37c35c",
+            b"aaf3d8f1de5640c232f5b169b9c911e6",
+            b"e1992190549f6ed5696a2c056c315410",
+        ];
+
+        for (len, tag) in [0, 16, 40, 64].into_iter().zip(tags) {
+            let result = cmac(&KEY_256, &PLAINTEXT[..len]).unwrap();
+            assert_eq!((bytes_to_hex(&result)), (tag.to_vec()));
+        }
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        for chunk_size in [1, 7, 16, 17] {
+            let mut mac = Cmac::new(&KEY_128).unwrap();
+            for chunk in PLAINTEXT.chunks(chunk_size) {
+                mac.update(chunk);
+            }
+
+            let expected = cmac(&KEY_128, &PLAINTEXT).unwrap();
+            assert_eq!((mac.finalize()), (expected));
+        }
+    }
+
+    #[test]
+    fn verify_checks_the_tag() {
+        let tag = cmac(&KEY_128, &PLAINTEXT).unwrap();
+        assert_eq!((cmac_verify(&KEY_128, &PLAINTEXT, &tag)), (Ok(())));
+
+        let mut bad = tag;
+        bad[15] ^= 0x01;
+        let failed = Err(CmacError::AuthenticationFailed);
+        assert_eq!((cmac_verify(&KEY_128, &PLAINTEXT, &bad)), (failed));
+        assert_eq!((cmac_verify(&KEY_128, &PLAINTEXT, &tag[..8])), (failed));
+    }
+
+    #[test]
+    fn dbl_reduces_on_carry() {
+        let mut block = [0u8; BLOCK_SIZE];
+        block[0] = 0x80;
+        block[15] = 0x01;
+
+        let mut expected = [0u8; BLOCK_SIZE];
+        expected[15] = 0x02 ^ 0x87;
+        assert_eq!((dbl(&block)), (expected));
+    }
+
+    #[test]
+    fn invalid_key_length_is_an_error() {
+        let expected = Err(CmacError::Aes(AesError::InvalidKeyLength {
+            len: 15,
+        }));
+
+        assert_eq!((cmac(&[0u8; 15], b"")), (expected));
+    }
+}
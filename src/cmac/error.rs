@@ -0,0 +1,30 @@
+use std::fmt;
+use crate::aes::AesError;
+
+/// Errors of AES-CMAC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmacError {
+    /// The tag does not match.
+    AuthenticationFailed,
+    /// Invalid AES key.
+    Aes(AesError),
+}
+
+impl fmt::Display for CmacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CmacError::AuthenticationFailed => {
+                write!(f, "CMAC tag mismatch")
+            }
+            CmacError::Aes(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for CmacError {}
+
+impl From<AesError> for CmacError {
+    fn from(error: AesError) -> Self {
+        CmacError::Aes(error)
+    }
+}
@@ -0,0 +1,6 @@
+pub mod error;
+#[allow(clippy::module_inception)]
+pub mod cmac;
+
+pub use error::CmacError;
+pub use cmac::{ cmac, cmac_verify, dbl, generate_subkeys, Cmac, TAG_SIZE };
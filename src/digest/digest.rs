@@ -0,0 +1,90 @@
+/// Common interface of every hash function in the crate.
+///
+/// # Description
+/// - `OUTPUT_SIZE`: Digest length in bytes.
+/// - `BLOCK_SIZE`: Bytes absorbed per compression (or the sponge rate), as
+///   needed by constructions such as HMAC.
+/// - `new`, `update`, `finalize`: Streaming use, one chunk at a time.
+/// - `reset`: Start over with a fresh state, ready for a new message.
+/// - `digest`: One-shot hash of a complete message.
+///
+/// Generic code (HMAC, KDFs) takes the hash as a type parameter, e.g.
+/// `D: Digest`, and reads both sizes from it.
+pub trait Digest: Clone {
+    const OUTPUT_SIZE: usize;
+    const BLOCK_SIZE: usize;
+
+    /// Fixed-size digest, e.g. `[u8; 32]`.
+    type Output: AsRef<[u8]> + Copy;
+
+    fn new() -> Self;
+
+    fn update(&mut self, data: &[u8]);
+
+    fn finalize(self) -> Self::Output;
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn digest(data: &[u8]) -> Self::Output {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::blake2::{ blake2b, blake2s, Blake2b512, Blake2s256 };
+    use crate::blake3::{ blake3, Blake3 };
+    use crate::md4::{ md4, Md4 };
+    use crate::md5::{ md5, Md5 };
+    use crate::ripemd::{ ripemd160, Ripemd160 };
+    use crate::sha1::{ sha1, Sha1 };
+    use crate::sha256::{ sha256, Sha256 };
+    use crate::sha3::{ sha3_512, sponge, Sha3_256, Sha3_512 };
+    use crate::sha512::{ sha512, Sha512 };
+
+    // Hash `msg` in uneven chunks, after a reset of a dirty state.
+    fn chunked<D: Digest>(msg: &[u8]) -> Vec<u8> {
+        let mut hasher = D::new();
+        hasher.update(b"discarded by reset");
+        hasher.reset();
+
+        for chunk in msg.chunks(D::BLOCK_SIZE / 3 + 1) {
+            hasher.update(chunk);
+        }
+
+        let out = hasher.finalize();
+        assert_eq!((out.as_ref().len()), (D::OUTPUT_SIZE));
+
+        out.as_ref().to_vec()
+    }
+
+    #[test]
+    fn every_hash_agrees_with_its_one_shot_function() {
+        let msg = b"The quick brown fox jumps over the lazy dog".repeat(9);
+
+        assert_eq!((chunked::<Md4>(&msg)), (md4(&msg).to_vec()));
+        assert_eq!((chunked::<Md5>(&msg)), (md5(&msg).to_vec()));
+        assert_eq!((chunked::<Ripemd160>(&msg)), (ripemd160(&msg).to_vec()));
+        assert_eq!((chunked::<Sha1>(&msg)), (sha1(&msg).to_vec()));
+        assert_eq!((chunked::<Sha256>(&msg)), (sha256(&msg).to_vec()));
+        assert_eq!((chunked::<Sha512>(&msg)), (sha512(&msg).to_vec()));
+        assert_eq!((chunked::<Sha3_256>(&msg)), (sponge(&msg)));
+        assert_eq!((chunked::<Sha3_512>(&msg)), (sha3_512(&msg).to_vec()));
+        assert_eq!((chunked::<Blake2b512>(&msg)), (blake2b(&msg, 64).unwrap()));
+        assert_eq!((chunked::<Blake2s256>(&msg)), (blake2s(&msg, 32).unwrap()));
+        assert_eq!((chunked::<Blake3>(&msg)), (blake3(&msg).to_vec()));
+    }
+
+    #[test]
+    fn digest_hashes_a_complete_message() {
+        let result = Sha256::digest(b"abc");
+        let expected = sha256(b"abc");
+
+        assert_eq!((result), (expected));
+    }
+}
\ No newline at end of file
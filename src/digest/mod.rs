@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod digest;
+
+pub use digest::Digest;
\ No newline at end of file
@@ -0,0 +1,46 @@
+use std::fmt;
+use crate::aes::AesError;
+
+/// Errors of AES-GCM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcmError {
+    /// The nonce must be at least one byte.
+    EmptyNonce,
+    /// Plaintexts are limited to 2^36 - 32 bytes, associated data to
+    /// 2^61 - 1 bytes.
+    MessageTooLong,
+    /// The sealed message is shorter than the 16-byte tag.
+    CiphertextTooShort { len: usize },
+    /// The tag does not match, nothing is decrypted.
+    AuthenticationFailed,
+    /// Invalid AES key.
+    Aes(AesError),
+}
+
+impl fmt::Display for GcmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GcmError::EmptyNonce => {
+                write!(f, "GCM nonce must not be empty")
+            }
+            GcmError::MessageTooLong => {
+                write!(f, "GCM plaintext or associated data is too long")
+            }
+            GcmError::CiphertextTooShort { len } => {
+                write!(f, "GCM ciphertext of {len} bytes has no full tag")
+            }
+            GcmError::AuthenticationFailed => {
+                write!(f, "GCM tag mismatch")
+            }
+            GcmError::Aes(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for GcmError {}
+
+impl From<AesError> for GcmError {
+    fn from(error: AesError) -> Self {
+        GcmError::Aes(error)
+    }
+}
@@ -0,0 +1,478 @@
+use crate::aead::Aead;
+use crate::aes::Aes;
+use crate::gcm::{ GcmError, Ghash };
+use crate::modes::Ctr;
+use crate::utils::ct_eq;
+
+/// GCM tag size in bytes, only full tags are produced and accepted.
+pub const TAG_SIZE: usize = 16;
+
+// Plaintext limit of 2^39 - 256 bits, i.e. 2^32 - 2 counter blocks.
+const MAX_PLAINTEXT_LEN: u64 = (1 << 36) - 32;
+
+// Associated data limit of 2^64 - 1 bits.
+const MAX_AAD_LEN: u64 = (1 << 61) - 1;
+
+/// AES-GCM authenticated encryption.
+///
+/// # Description
+/// - The hash subkey H = E(K, 0^128) is computed once in `new`.
+/// - A 96-bit nonce gives J0 = nonce || 0^31 || 1, any other length is
+///   hashed into J0 with GHASH.
+/// - The plaintext is encrypted in CTR mode with a 32-bit counter starting
+///   at inc32(J0), the tag is E(K, J0) ^ GHASH(A, C).
+/// - `seal` returns ciphertext || tag; `open` checks the tag in constant
+///   time before anything is decrypted.
+///
+/// # Reference
+/// [NIST SP 800-38D](https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38d.pdf)
+#[derive(Clone)]
+pub struct AesGcm {
+    aes: Aes,
+    h: [u8; 16],
+}
+
+impl AesGcm {
+    /// AES-128, AES-192 or AES-256-GCM, picked by the key length.
+    pub fn new(key: &[u8]) -> Result<Self, GcmError> {
+        let aes = Aes::new(key)?;
+        let h = aes.encrypt_block(&[0u8; 16]);
+
+        Ok(AesGcm { aes, h })
+    }
+
+    /// Encrypt `plaintext` and authenticate it together with `aad`.
+    pub fn seal(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, GcmError> {
+        check_lengths(nonce, aad, plaintext.len())?;
+
+        let j0 = self.pre_counter_block(nonce);
+        let mut sealed = self.gctr(&j0, plaintext);
+        let tag = self.tag(&j0, aad, &sealed);
+        sealed.extend_from_slice(&tag);
+
+        Ok(sealed)
+    }
+
+    /// Verify and decrypt the output of `seal`.
+    ///
+    /// Fails closed: on a tag mismatch no plaintext is produced at all.
+    pub fn open(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, GcmError> {
+        if sealed.len() < TAG_SIZE {
+            let len = sealed.len();
+            return Err(GcmError::CiphertextTooShort { len });
+        }
+
+        let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_SIZE);
+        check_lengths(nonce, aad, ciphertext.len())?;
+
+        let j0 = self.pre_counter_block(nonce);
+        let expected = self.tag(&j0, aad, ciphertext);
+        if !ct_eq(&expected, tag) {
+            return Err(GcmError::AuthenticationFailed);
+        }
+
+        Ok(self.gctr(&j0, ciphertext))
+    }
+
+    // J0: nonce || 0^31 || 1 for 96-bit nonces, otherwise
+    // GHASH(nonce || 0^s || 0^64 || [len(nonce)]_64).
+    fn pre_counter_block(&self, nonce: &[u8]) -> [u8; 16] {
+        if nonce.len() == 12 {
+            let mut j0 = [0u8; 16];
+            j0[..12].copy_from_slice(nonce);
+            j0[15] = 0x01;
+            return j0;
+        }
+
+        let mut ghash = Ghash::new(&self.h);
+        ghash.update_padded(nonce);
+        ghash.update_padded(&((nonce.len() as u128) * 8).to_be_bytes());
+        ghash.finalize()
+    }
+
+    // GCTR starting at inc32(J0), CTR with a 32-bit counter.
+    fn gctr(&self, j0: &[u8; 16], data: &[u8]) -> Vec<u8> {
+        let mut icb = *j0;
+        inc32(&mut icb);
+
+        let mut ctr = Ctr::new(self.aes.clone(), &icb, 4)
+            .expect("J0 is one block");
+        ctr.update(data)
+            .expect("message length is checked against the counter space")
+    }
+
+    // T = E(K, J0) ^ GHASH(A || 0^v || C || 0^u || [len(A)]_64 ||
+    // [len(C)]_64), lengths in bits.
+    fn tag(&self, j0: &[u8; 16], aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+        let mut ghash = Ghash::new(&self.h);
+        ghash.update_padded(aad);
+        ghash.update_padded(ciphertext);
+
+        let aad_bits = aad.len() as u64 * 8;
+        let ciphertext_bits = ciphertext.len() as u64 * 8;
+        let mut lengths = [0u8; 16];
+        lengths[..8].copy_from_slice(&aad_bits.to_be_bytes());
+        lengths[8..].copy_from_slice(&ciphertext_bits.to_be_bytes());
+        ghash.update_padded(&lengths);
+
+        let s = ghash.finalize();
+        let mask = self.aes.encrypt_block(j0);
+
+        let mut tag = [0u8; 16];
+        for i in 0..16 {
+            tag[i] = s[i] ^ mask[i];
+        }
+        tag
+    }
+}
+
+impl Aead for AesGcm {
+    type Error = GcmError;
+
+    fn tag_size(&self) -> usize {
+        TAG_SIZE
+    }
+
+    fn seal(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, GcmError> {
+        AesGcm::seal(self, nonce, aad, plaintext)
+    }
+
+    fn open(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, GcmError> {
+        AesGcm::open(self, nonce, aad, sealed)
+    }
+}
+
+/// AES-GCM: encrypt and authenticate, returns ciphertext || tag.
+pub fn seal(
+    key: &[u8], 
+    nonce: &[u8], 
+    aad: &[u8], 
+    plaintext: &[u8]
+) -> Result<Vec<u8>, GcmError> {
+    AesGcm::new(key)?.seal(nonce, aad, plaintext)
+}
+
+/// AES-GCM: verify and decrypt ciphertext || tag.
+pub fn open(
+    key: &[u8], 
+    nonce: &[u8], 
+    aad: &[u8], 
+    sealed: &[u8]
+) -> Result<Vec<u8>, GcmError> {
+    AesGcm::new(key)?.open(nonce, aad, sealed)
+}
+
+fn check_lengths(
+    nonce: &[u8], 
+    aad: &[u8], 
+    text_len: usize
+) -> Result<(), GcmError> {
+    if nonce.is_empty() {
+        return Err(GcmError::EmptyNonce);
+    }
+
+    if text_len as u64 > MAX_PLAINTEXT_LEN || aad.len() as u64 > MAX_AAD_LEN {
+        return Err(GcmError::MessageTooLong);
+    }
+
+    Ok(())
+}
+
+// inc32: increment the last 32 bits modulo 2^32.
+fn inc32(block: &mut [u8; 16]) {
+    let mut counter = [0u8; 4];
+    counter.copy_from_slice(&block[12..]);
+    let next = u32::from_be_bytes(counter).wrapping_add(1);
+    block[12..].copy_from_slice(&next.to_be_bytes());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::{ bytes_to_hex, hex_to_bytes };
+
+    struct GcmCase {
+        name: &'static str,
+        key: &'static [u8],
+        nonce: &'static [u8],
+        aad: &'static [u8],
+        plaintext: &'static [u8],
+        sealed: &'static [u8],
+    }
+
+    // Test cases of the GCM specification (McGrew and Viega), hex encoded:
+    // 1-6 AES-128, 7-10 AES-192, 13-18 AES-256. Cases 5, 6, 17 and 18 use
+    // 64-bit and 480-bit nonces.
+    const CASES: [GcmCase; 16] = [
+        GcmCase {
+            name: "test case 1",
+            key: b"00000000000000000000000000000000",
+            nonce: b"000000000000000000000000",
+            aad: b"",
+            plaintext: b"",
+            sealed: b"58e2fccefa7e3061367f1d57a4e7455a",
+        },
+        GcmCase {
+            name: "test case 2",
+            key: b"00000000000000000000000000000000",
+            nonce: b"000000000000000000000000",
+            aad: b"",
+            plaintext: b"00000000000000000000000000000000",
+            sealed: b"0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bd\
+                    f53a67b21257bddf",
+        },
+        GcmCase {
+            name: "test case 3",
+            key: b"feffe9928665731c6d6a8f9467308308",
+            nonce: b"cafebabefacedbaddecaf888",
+            aad: b"",
+            plaintext: b"d9313225f88406e5a55909c5aff5269a86a7a9531534f7da\
+                       2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525\
+                       b16aedf5aa0de657ba637b391aafd255",
+            sealed: b"42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e0\
+                    35c17e2329aca12e21d514b25466931c7d8f6a5aac84aa05\
+                    1ba30b396a0aac973d58e091473f59854d5c2af327cd64a6\
+                    2cf35abd2ba6fab4",
+        },
+        GcmCase {
+            name: "test case 4",
+            key: b"feffe9928665731c6d6a8f9467308308",
+            nonce: b"cafebabefacedbaddecaf888",
+            aad: b"feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            plaintext: b"d9313225f88406e5a55909c5aff5269a86a7a9531534f7da\
+                       2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525\
+                       b16aedf5aa0de657ba637b39",
+            sealed: b"42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e0\
+                    35c17e2329aca12e21d514b25466931c7d8f6a5aac84aa05\
+                    1ba30b396a0aac973d58e0915bc94fbc3221a5db94fae95a\
+                    e7121a47",
+        },
+        GcmCase {
+            name: "test case 5",
+            key: b"feffe9928665731c6d6a8f9467308308",
+            nonce: b"cafebabefacedbad",
+            aad: b"feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            plaintext: b"d9313225f88406e5a55909c5aff5269a86a7a9531534f7da\
+                       2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525\
+                       b16aedf5aa0de657ba637b39",
+            sealed: b"61353b4c2806934a777ff51fa22a4755699b2a714fcdc6f8\
+                    3766e5f97b6c742373806900e49f24b22b097544d4896b42\
+                    4989b5e1ebac0f07c23f45983612d2e79e3b0785561be14a\
+                    aca2fccb",
+        },
+        GcmCase {
+            name: "test case 6",
+            key: b"feffe9928665731c6d6a8f9467308308",
+            nonce: b"9313225df88406e555909c5aff5269aa6a7a9538534f7da1\
+                   e4c303d2a318a728c3c0c95156809539fcf0e2429a6b5254\
+                   16aedbf5a0de6a57a637b39b",
+            aad: b"feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            plaintext: b"d9313225f88406e5a55909c5aff5269a86a7a9531534f7da\
+                       2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525\
+                       b16aedf5aa0de657ba637b39",
+            sealed: b"8ce24998625615b603a033aca13fb894be9112a5c3a211a8\
+                    ba262a3cca7e2ca701e4a9a4fba43c90ccdcb281d48c7c6f\
+                    d62875d2aca417034c34aee5619cc5aefffe0bfa462af43c\
+                    1699d050",
+        },
+        GcmCase {
+            name: "test case 7",
+            key: b"000000000000000000000000000000000000000000000000",
+            nonce: b"000000000000000000000000",
+            aad: b"",
+            plaintext: b"",
+            sealed: b"cd33b28ac773f74ba00ed1f312572435",
+        },
+        GcmCase {
+            name: "test case 8",
+            key: b"000000000000000000000000000000000000000000000000",
+            nonce: b"000000000000000000000000",
+            aad: b"",
+            plaintext: b"00000000000000000000000000000000",
+            sealed: b"98e7247c07f0fe411c267e4384b0f6002ff58d80033927ab\
+                    8ef4d4587514f0fb",
+        },
+        GcmCase {
+            name: "test case 9",
+            key: b"feffe9928665731c6d6a8f9467308308feffe9928665731c",
+            nonce: b"cafebabefacedbaddecaf888",
+            aad: b"",
+            plaintext: b"d9313225f88406e5a55909c5aff5269a86a7a9531534f7da\
+                       2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525\
+                       b16aedf5aa0de657ba637b391aafd255",
+            sealed: b"3980ca0b3c00e841eb06fac4872a2757859e1ceaa6efd984\
+                    628593b40ca1e19c7d773d00c144c525ac619d18c84a3f47\
+                    18e2448b2fe324d9ccda2710acade2569924a7c8587336bf\
+                    b118024db8674a14",
+        },
+        GcmCase {
+            name: "test case 10",
+            key: b"feffe9928665731c6d6a8f9467308308feffe9928665731c",
+            nonce: b"cafebabefacedbaddecaf888",
+            aad: b"feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            plaintext: b"d9313225f88406e5a55909c5aff5269a86a7a9531534f7da\
+                       2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525\
+                       b16aedf5aa0de657ba637b39",
+            sealed: b"3980ca0b3c00e841eb06fac4872a2757859e1ceaa6efd984\
+                    628593b40ca1e19c7d773d00c144c525ac619d18c84a3f47\
+                    18e2448b2fe324d9ccda27102519498e80f1478f37ba55bd\
+                    6d27618c",
+        },
+        GcmCase {
+            name: "test case 13",
+            key: b"000000000000000000000000000000000000000000000000\
+                 0000000000000000",
+            nonce: b"000000000000000000000000",
+            aad: b"",
+            plaintext: b"",
+            sealed: b"530f8afbc74536b9a963b4f1c4cb738b",
+        },
+        GcmCase {
+            name: "test case 14",
+            key: b"000000000000000000000000000000000000000000000000\
+                 0000000000000000",
+            nonce: b"000000000000000000000000",
+            aad: b"",
+            plaintext: b"00000000000000000000000000000000",
+            sealed: b"cea7403d4d606b6e074ec5d3baf39d18d0d1c8a799996bf0\
+                    265b98b5d48ab919",
+        },
+        GcmCase {
+            name: "test case 15",
+            key: b"feffe9928665731c6d6a8f9467308308feffe9928665731c\
+                 6d6a8f9467308308",
+            nonce: b"cafebabefacedbaddecaf888",
+            aad: b"",
+            plaintext: b"d9313225f88406e5a55909c5aff5269a86a7a9531534f7da\
+                       2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525\
+                       b16aedf5aa0de657ba637b391aafd255",
+            sealed: b"522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c9\
+                    7598a2bd2555d1aa8cb08e48590dbb3da7b08b1056828838\
+                    c5f61e6393ba7a0abcc9f662898015adb094dac5d93471bd\
+                    ec1a502270e3cc6c",
+        },
+        GcmCase {
+            name: "test case 16",
+            key: b"feffe9928665731c6d6a8f9467308308feffe9928665731c\
+                 6d6a8f9467308308",
+            nonce: b"cafebabefacedbaddecaf888",
+            aad: b"feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            plaintext: b"d9313225f88406e5a55909c5aff5269a86a7a9531534f7da\
+                       2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525\
+                       b16aedf5aa0de657ba637b39",
+            sealed: b"522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c9\
+                    7598a2bd2555d1aa8cb08e48590dbb3da7b08b1056828838\
+                    c5f61e6393ba7a0abcc9f66276fc6ece0f4e1768cddf8853\
+                    bb2d551b",
+        },
+        GcmCase {
+            name: "test case 17",
+            key: b"feffe9928665731c6d6a8f9467308308feffe9928665731c\
+                 6d6a8f9467308308",
+            nonce: b"cafebabefacedbad",
+            aad: b"feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            plaintext: b"d9313225f88406e5a55909c5aff5269a86a7a9531534f7da\
+                       2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525\
+                       b16aedf5aa0de657ba637b39",
+            sealed: b"c3762df1ca787d32ae47c13bf19844cbaf1ae14d0b976afa\
+                    c52ff7d79bba9de0feb582d33934a4f0954cc2363bc73f78\
+                    62ac430e64abe499f47c9b1f3a337dbf46a792c45e454913\
+                    fe2ea8f2",
+        },
+        GcmCase {
+            name: "test case 18",
+            key: b"feffe9928665731c6d6a8f9467308308feffe9928665731c\
+                 6d6a8f9467308308",
+            nonce: b"9313225df88406e555909c5aff5269aa6a7a9538534f7da1\
+                   e4c303d2a318a728c3c0c95156809539fcf0e2429a6b5254\
+                   16aedbf5a0de6a57a637b39b",
+            aad: b"feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            plaintext: b"d9313225f88406e5a55909c5aff5269a86a7a9531534f7da\
+                       2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525\
+                       b16aedf5aa0de657ba637b39",
+            sealed: b"5a8def2f0c9e53f1f75d7853659e2a20eeb2b22aafde6419\
+                    a058ab4f6f746bf40fc0c3b780f244452da3ebf1c5d82cde\
+                    a2418997200ef82e44ae7e3fa44a8266ee1c8eb0c8b5d4cf\
+                    5ae9f19a",
+        },
+    ];
+
+    #[test]
+    fn gcm_spec_test_cases() {
+        for case in CASES.iter() {
+            let key = hex_to_bytes(case.key);
+            let nonce = hex_to_bytes(case.nonce);
+            let aad = hex_to_bytes(case.aad);
+            let plaintext = hex_to_bytes(case.plaintext);
+
+            let sealed = seal(&key, &nonce, &aad, &plaintext).unwrap();
+            assert_eq!((bytes_to_hex(&sealed)), (case.sealed), "{}", case.name);
+
+            let opened = open(&key, &nonce, &aad, &sealed).unwrap();
+            assert_eq!((opened), (plaintext), "{}", case.name);
+        }
+    }
+
+    #[test]
+    fn gcm_open_fails_closed_on_any_change() {
+        let case = &CASES[3];
+        let key = hex_to_bytes(case.key);
+        let nonce = hex_to_bytes(case.nonce);
+        let aad = hex_to_bytes(case.aad);
+        let sealed = hex_to_bytes(case.sealed);
+        let gcm = AesGcm::new(&key).unwrap();
+
+        // Flip one bit of the ciphertext, of the tag, and of the AAD.
+        for i in [0, sealed.len() - 1] {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 0x01;
+            assert_eq!(
+                (gcm.open(&nonce, &aad, &tampered)),
+                (Err(GcmError::AuthenticationFailed))
+            );
+        }
+
+        let mut aad = aad;
+        aad[0] ^= 0x80;
+        assert_eq!(
+            (gcm.open(&nonce, &aad, &sealed)),
+            (Err(GcmError::AuthenticationFailed))
+        );
+    }
+
+    #[test]
+    fn gcm_rejects_bad_inputs() {
+        let gcm = AesGcm::new(&[0u8; 16]).unwrap();
+        assert_eq!((gcm.seal(&[], &[], b"abc")), (Err(GcmError::EmptyNonce)));
+        assert_eq!(
+            (gcm.open(&[0u8; 12], &[], &[0u8; 15])),
+            (Err(GcmError::CiphertextTooShort { len: 15 }))
+        );
+        assert_eq!(
+            (AesGcm::new(&[0u8; 15]).err()),
+            (Some(GcmError::Aes(crate::aes::AesError::InvalidKeyLength {
+                len: 15
+            })))
+        );
+    }
+}
@@ -0,0 +1,8 @@
+pub mod error;
+pub mod ghash;
+#[allow(clippy::module_inception)]
+pub mod gcm;
+
+pub use error::GcmError;
+pub use ghash::{ gf128_mul, Ghash };
+pub use gcm::{ open, seal, AesGcm, TAG_SIZE };
@@ -0,0 +1,40 @@
+use std::fmt;
+
+/// Errors of AES-GCM-SIV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcmSivError {
+    /// The key must be 16 or 32 bytes, there is no AES-192-GCM-SIV.
+    InvalidKeyLength { len: usize },
+    /// The nonce must be 12 bytes.
+    InvalidNonceLength { len: usize },
+    /// Plaintexts and associated data are limited to 2^36 bytes each.
+    MessageTooLong,
+    /// The sealed message is shorter than the 16-byte tag.
+    CiphertextTooShort { len: usize },
+    /// The tag does not match, no plaintext is returned.
+    AuthenticationFailed,
+}
+
+impl fmt::Display for GcmSivError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GcmSivError::InvalidKeyLength { len } => {
+                write!(f, "GCM-SIV key length {len} is not 16 or 32")
+            }
+            GcmSivError::InvalidNonceLength { len } => {
+                write!(f, "GCM-SIV nonce length {len} is not 12")
+            }
+            GcmSivError::MessageTooLong => {
+                write!(f, "GCM-SIV plaintext or associated data is too long")
+            }
+            GcmSivError::CiphertextTooShort { len } => {
+                write!(f, "GCM-SIV ciphertext of {len} bytes has no full tag")
+            }
+            GcmSivError::AuthenticationFailed => {
+                write!(f, "GCM-SIV tag mismatch")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GcmSivError {}
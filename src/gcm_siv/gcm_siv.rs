@@ -0,0 +1,400 @@
+use crate::aead::Aead;
+use crate::aes::{ Aes, BLOCK_SIZE };
+use crate::gcm_siv::{ GcmSivError, Polyval };
+use crate::utils::ct_eq;
+
+/// GCM-SIV nonce size in bytes.
+pub const NONCE_SIZE: usize = 12;
+
+/// GCM-SIV tag size in bytes.
+pub const TAG_SIZE: usize = 16;
+
+// Plaintext and associated data limit of 2^36 bytes.
+const MAX_TEXT_LEN: u64 = 1 << 36;
+
+/// AES-GCM-SIV nonce-misuse-resistant authenticated encryption.
+///
+/// # Description
+/// - Every nonce derives its own message-authentication key and
+///   message-encryption key from the key-generating key, taking the first
+///   8 bytes of E(K, le32(i) || nonce) for i = 0, 1, ...
+/// - POLYVAL of the associated data, the plaintext and their bit lengths
+///   is xored with the nonce, its top bit cleared and encrypted into the
+///   tag.
+/// - The plaintext is encrypted in CTR mode from the tag with its top bit
+///   set, the first 4 bytes are a little-endian 32-bit counter.
+/// - Repeating a nonce only reveals whether the same message was sealed
+///   twice. `seal` returns ciphertext || tag; `open` decrypts first and
+///   returns the plaintext only once the recomputed tag matches.
+///
+/// # Reference
+/// [RFC 8452](https://www.rfc-editor.org/rfc/rfc8452)
+#[derive(Clone)]
+pub struct AesGcmSiv {
+    aes: Aes,
+    key_len: usize,
+}
+
+impl AesGcmSiv {
+    /// AES-128 or AES-256-GCM-SIV, picked by the key length.
+    pub fn new(key: &[u8]) -> Result<Self, GcmSivError> {
+        if key.len() != 16 && key.len() != 32 {
+            return Err(GcmSivError::InvalidKeyLength { len: key.len() });
+        }
+
+        let aes = Aes::new(key).expect("16 and 32 bytes are AES key sizes");
+
+        Ok(AesGcmSiv { aes, key_len: key.len() })
+    }
+
+    /// Encrypt `plaintext` and authenticate it together with `aad`.
+    pub fn seal(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, GcmSivError> {
+        check_lengths(nonce, aad, plaintext.len())?;
+
+        let (auth_key, enc) = self.derive_keys(nonce);
+        let tag = tag(&auth_key, &enc, nonce, aad, plaintext);
+        let mut sealed = ctr(&enc, &tag, plaintext);
+        sealed.extend_from_slice(&tag);
+
+        Ok(sealed)
+    }
+
+    /// Verify and decrypt the output of `seal`.
+    ///
+    /// Fails closed: on a tag mismatch no plaintext is returned at all.
+    pub fn open(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, GcmSivError> {
+        if sealed.len() < TAG_SIZE {
+            let len = sealed.len();
+            return Err(GcmSivError::CiphertextTooShort { len });
+        }
+
+        let (ciphertext, tag_bytes) = sealed.split_at(sealed.len() - TAG_SIZE);
+        check_lengths(nonce, aad, ciphertext.len())?;
+
+        let mut expected = [0u8; TAG_SIZE];
+        expected.copy_from_slice(tag_bytes);
+
+        let (auth_key, enc) = self.derive_keys(nonce);
+        let plaintext = ctr(&enc, &expected, ciphertext);
+        let tag = tag(&auth_key, &enc, nonce, aad, &plaintext);
+        if !ct_eq(&tag, &expected) {
+            return Err(GcmSivError::AuthenticationFailed);
+        }
+
+        Ok(plaintext)
+    }
+
+    // Message-authentication key (16 bytes) and message-encryption key
+    // (the length of the key-generating key), 8 bytes per AES block.
+    fn derive_keys(&self, nonce: &[u8]) -> ([u8; 16], Aes) {
+        let mut keys = Vec::with_capacity(16 + self.key_len);
+        for i in 0..(16 + self.key_len) / 8 {
+            let mut block = [0u8; BLOCK_SIZE];
+            block[..4].copy_from_slice(&(i as u32).to_le_bytes());
+            block[4..].copy_from_slice(nonce);
+            keys.extend_from_slice(&self.aes.encrypt_block(&block)[..8]);
+        }
+
+        let mut auth_key = [0u8; 16];
+        auth_key.copy_from_slice(&keys[..16]);
+        let enc = Aes::new(&keys[16..]).expect("derived key has the AES size");
+
+        (auth_key, enc)
+    }
+}
+
+impl Aead for AesGcmSiv {
+    type Error = GcmSivError;
+
+    fn tag_size(&self) -> usize {
+        TAG_SIZE
+    }
+
+    fn seal(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, GcmSivError> {
+        AesGcmSiv::seal(self, nonce, aad, plaintext)
+    }
+
+    fn open(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, GcmSivError> {
+        AesGcmSiv::open(self, nonce, aad, sealed)
+    }
+}
+
+/// AES-GCM-SIV: encrypt and authenticate, returns ciphertext || tag.
+pub fn seal(
+    key: &[u8], 
+    nonce: &[u8], 
+    aad: &[u8], 
+    plaintext: &[u8]
+) -> Result<Vec<u8>, GcmSivError> {
+    AesGcmSiv::new(key)?.seal(nonce, aad, plaintext)
+}
+
+/// AES-GCM-SIV: verify and decrypt ciphertext || tag.
+pub fn open(
+    key: &[u8], 
+    nonce: &[u8], 
+    aad: &[u8], 
+    sealed: &[u8]
+) -> Result<Vec<u8>, GcmSivError> {
+    AesGcmSiv::new(key)?.open(nonce, aad, sealed)
+}
+
+fn check_lengths(
+    nonce: &[u8], 
+    aad: &[u8], 
+    text_len: usize
+) -> Result<(), GcmSivError> {
+    if nonce.len() != NONCE_SIZE {
+        return Err(GcmSivError::InvalidNonceLength { len: nonce.len() });
+    }
+
+    if text_len as u64 > MAX_TEXT_LEN || aad.len() as u64 > MAX_TEXT_LEN {
+        return Err(GcmSivError::MessageTooLong);
+    }
+
+    Ok(())
+}
+
+// Tag = E(K_enc, (POLYVAL(K_auth, A, P, lengths) ^ nonce) & ~msb), with
+// the lengths in bits as two little-endian 64-bit integers.
+fn tag(
+    auth_key: &[u8; 16], 
+    enc: &Aes, 
+    nonce: &[u8], 
+    aad: &[u8], 
+    plaintext: &[u8]
+) -> [u8; TAG_SIZE] {
+    let mut polyval = Polyval::new(auth_key);
+    polyval.update_padded(aad);
+    polyval.update_padded(plaintext);
+
+    let mut lengths = [0u8; 16];
+    lengths[..8].copy_from_slice(&(aad.len() as u64 * 8).to_le_bytes());
+    lengths[8..].copy_from_slice(&(plaintext.len() as u64 * 8).to_le_bytes());
+    polyval.update_padded(&lengths);
+
+    let mut s = polyval.finalize();
+    for i in 0..NONCE_SIZE {
+        s[i] ^= nonce[i];
+    }
+    s[15] &= 0x7f;
+
+    enc.encrypt_block(&s)
+}
+
+// CTR from the tag with its msb set, incrementing the first 4 bytes as a
+// little-endian counter modulo 2^32.
+fn ctr(enc: &Aes, tag: &[u8; TAG_SIZE], data: &[u8]) -> Vec<u8> {
+    let mut counter_block = *tag;
+    counter_block[15] |= 0x80;
+
+    let mut out = Vec::with_capacity(data.len());
+    for chunk in data.chunks(BLOCK_SIZE) {
+        let keystream = enc.encrypt_block(&counter_block);
+        for i in 0..chunk.len() {
+            out.push(chunk[i] ^ keystream[i]);
+        }
+
+        let mut counter = [0u8; 4];
+        counter.copy_from_slice(&counter_block[..4]);
+        let next = u32::from_le_bytes(counter).wrapping_add(1);
+        counter_block[..4].copy_from_slice(&next.to_le_bytes());
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::{ bytes_to_hex, hex_to_bytes };
+
+    struct GcmSivCase {
+        name: &'static str,
+        key: &'static [u8],
+        nonce: &'static [u8],
+        aad: &'static [u8],
+        plaintext: &'static [u8],
+        sealed: &'static [u8],
+    }
+
+    // RFC 8452 appendix C, hex encoded: C.1 AES-128, C.2 AES-256 and the
+    // C.3 counter wrap tests.
+    const CASES: [GcmSivCase; 10] = [
+        GcmSivCase {
+            name: "C.1 empty",
+            key: b"01000000000000000000000000000000",
+            nonce: b"030000000000000000000000",
+            aad: b"",
+            plaintext: b"",
+            sealed: b"dc20e2d83f25705bb49e439eca56de25",
+        },
+        GcmSivCase {
+            name: "C.1 64-byte plaintext",
+            key: b"01000000000000000000000000000000",
+            nonce: b"030000000000000000000000",
+            aad: b"",
+            plaintext: b"010000000000000000000000000000000200000000000000\
+                       000000000000000003000000000000000000000000000000\
+                       04000000000000000000000000000000",
+            sealed: b"2433668f1058190f6d43e360f4f35cd8e475127cfca7028e\
+                    a8ab5c20f7ab2af02516a2bdcbc08d521be37ff28c152bba\
+                    36697f25b4cd169c6590d1dd39566d3f8a263dd317aa88d5\
+                    6bdf3936dba75bb8",
+        },
+        GcmSivCase {
+            name: "C.1 partial blocks",
+            key: b"01000000000000000000000000000000",
+            nonce: b"030000000000000000000000",
+            aad: b"0100000000000000000000000000000002000000",
+            plaintext: b"030000000000000000000000000000000400",
+            sealed: b"44d0aaf6fb2f1f34add5e8064e83e12a2adabff9b2ef00fb\
+                    47920cc72a0c0f13b9fd",
+        },
+        GcmSivCase {
+            name: "C.1 random",
+            key: b"f901cfe8a69615a93fdf7a98cad48179",
+            nonce: b"6245709fb18853f68d833640",
+            aad: b"7576f7028ec6eb5ea7e298342a94d4b202b370ef9768ec65\
+                 61c4fe6b7e7296fa859c21",
+            plaintext: b"e42a3c02c25b64869e146d7b233987bddfc240871d",
+            sealed: b"391cc328d484a4f46406181bcd62efd9b3ee197d052d1550\
+                    6c84a9edd65e13e9d24a2a6e70",
+        },
+        GcmSivCase {
+            name: "C.2 empty",
+            key: b"01000000000000000000000000000000\
+                 00000000000000000000000000000000",
+            nonce: b"030000000000000000000000",
+            aad: b"",
+            plaintext: b"",
+            sealed: b"07f5f4169bbf55a8400cd47ea6fd400f",
+        },
+        GcmSivCase {
+            name: "C.2 64-byte plaintext",
+            key: b"01000000000000000000000000000000\
+                 00000000000000000000000000000000",
+            nonce: b"030000000000000000000000",
+            aad: b"01",
+            plaintext: b"020000000000000000000000000000000300000000000000\
+                       000000000000000004000000000000000000000000000000\
+                       05000000000000000000000000000000",
+            sealed: b"67fd45e126bfb9a79930c43aad2d36967d3f0e4d217c1e55\
+                    1f59727870beefc98cb933a8fce9de887b1e40799988db1f\
+                    c3f91880ed405b2dd298318858467c895bde0285037c5de8\
+                    1e5b570a049b62a0",
+        },
+        GcmSivCase {
+            name: "C.2 partial blocks",
+            key: b"01000000000000000000000000000000\
+                 00000000000000000000000000000000",
+            nonce: b"030000000000000000000000",
+            aad: b"010000000000000000000000000000000200",
+            plaintext: b"0300000000000000000000000000000004000000",
+            sealed: b"43dd0163cdb48f9fe3212bf61b201976067f342bb879ad97\
+                    6d8242acc188ab59cabfe307",
+        },
+        GcmSivCase {
+            name: "C.2 random",
+            key: b"3c535de192eaed3822a2fbbe2ca9dfc8\
+                 8255e14a661b8aa82cc54236093bbc23",
+            nonce: b"688089e55540db1872504e1c",
+            aad: b"734320ccc9d9bbbb19cb81b2af4ecbc3e72834321f7aa0f7\
+                 0b7282b4f33df23f167541",
+            plaintext: b"ced532ce4159b035277d4dfbb7db62968b13cd4eec",
+            sealed: b"626660c26ea6612fb17ad91e8e767639edd6c9faee9d6c70\
+                    29675b89eaf4ba1ded1a286594",
+        },
+        GcmSivCase {
+            name: "C.3 counter wrap 1",
+            key: b"00000000000000000000000000000000\
+                 00000000000000000000000000000000",
+            nonce: b"000000000000000000000000",
+            aad: b"",
+            plaintext: b"000000000000000000000000000000004db923dc793ee649\
+                       7c76dcc03a98e108",
+            sealed: b"f3f80f2cf0cb2dd9c5984fcda908456cc537703b5ba70324\
+                    a6793a7bf218d3eaffffffff000000000000000000000000",
+        },
+        GcmSivCase {
+            name: "C.3 counter wrap 2",
+            key: b"00000000000000000000000000000000\
+                 00000000000000000000000000000000",
+            nonce: b"000000000000000000000000",
+            aad: b"",
+            plaintext: b"eb3640277c7ffd1303c7a542d02d3e4c0000000000000000",
+            sealed: b"18ce4f0b8cb4d0cac65fea8f79257b20888e53e72299e56d\
+                    ffffffff000000000000000000000000",
+        },
+    ];
+
+    #[test]
+    fn gcm_siv_rfc8452_appendix_c() {
+        for case in CASES.iter() {
+            let key = hex_to_bytes(case.key);
+            let nonce = hex_to_bytes(case.nonce);
+            let aad = hex_to_bytes(case.aad);
+            let plaintext = hex_to_bytes(case.plaintext);
+
+            let sealed = seal(&key, &nonce, &aad, &plaintext).unwrap();
+            assert_eq!((bytes_to_hex(&sealed)), (case.sealed), "{}", case.name);
+
+            let opened = open(&key, &nonce, &aad, &sealed).unwrap();
+            assert_eq!((opened), (plaintext), "{}", case.name);
+        }
+    }
+
+    #[test]
+    fn gcm_siv_open_fails_closed_on_any_change() {
+        let case = &CASES[3];
+        let key = hex_to_bytes(case.key);
+        let nonce = hex_to_bytes(case.nonce);
+        let aad = hex_to_bytes(case.aad);
+        let sealed = hex_to_bytes(case.sealed);
+        let gcm_siv = AesGcmSiv::new(&key).unwrap();
+        let failed = Err(GcmSivError::AuthenticationFailed);
+
+        for i in [0, 20, 21, sealed.len() - 1] {
+            let mut bad = sealed.clone();
+            bad[i] ^= 0x01;
+            assert_eq!((gcm_siv.open(&nonce, &aad, &bad)), (failed));
+        }
+
+        let mut nonce = nonce;
+        nonce[11] ^= 0x01;
+        assert_eq!((gcm_siv.open(&nonce, &aad, &sealed)), (failed));
+    }
+
+    #[test]
+    fn gcm_siv_rejects_bad_inputs() {
+        let expected = Some(GcmSivError::InvalidKeyLength { len: 24 });
+        assert_eq!((AesGcmSiv::new(&[0u8; 24]).err()), (expected));
+
+        let gcm_siv = AesGcmSiv::new(&[0u8; 16]).unwrap();
+        let expected = Err(GcmSivError::InvalidNonceLength { len: 16 });
+        assert_eq!((gcm_siv.seal(&[0u8; 16], &[], b"abc")), (expected));
+
+        let expected = Err(GcmSivError::CiphertextTooShort { len: 15 });
+        assert_eq!((gcm_siv.open(&[0u8; 12], &[], &[0u8; 15])), (expected));
+    }
+}
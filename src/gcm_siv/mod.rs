@@ -0,0 +1,8 @@
+pub mod error;
+pub mod polyval;
+#[allow(clippy::module_inception)]
+pub mod gcm_siv;
+
+pub use error::GcmSivError;
+pub use polyval::Polyval;
+pub use gcm_siv::{ open, seal, AesGcmSiv, NONCE_SIZE, TAG_SIZE };
@@ -0,0 +1,81 @@
+use crate::gcm::gf128_mul;
+
+/// POLYVAL under the key H, the little-endian counterpart of GHASH.
+///
+/// # Description
+/// - POLYVAL works in GF(2^128) modulo x^128 + x^127 + x^126 + x^121 + 1
+///   with blocks read little-endian, and multiplies by H * x^-128.
+/// - It is computed through GHASH: reading the blocks little-endian into
+///   a u128 is the byte reversal GHASH expects, and the key becomes
+///   mulX_GHASH(ByteReverse(H)).
+/// - `update_padded` absorbs one input string, zero-padded to whole
+///   blocks, the same as `Ghash`.
+///
+/// # Reference
+/// [RFC 8452](https://www.rfc-editor.org/rfc/rfc8452), section 3 and
+/// appendix A.
+#[derive(Clone)]
+pub struct Polyval {
+    h: u128,
+    y: u128,
+}
+
+impl Polyval {
+    pub fn new(h: &[u8; 16]) -> Self {
+        Polyval {
+            h: mul_x_ghash(u128::from_le_bytes(*h)),
+            y: 0,
+        }
+    }
+
+    /// S_j = (S_(j-1) ^ X_j) * H for every (zero-padded) block of `data`.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        for chunk in data.chunks(16) {
+            let mut block = [0u8; 16];
+            block[..chunk.len()].copy_from_slice(chunk);
+
+            self.y = gf128_mul(self.y ^ u128::from_le_bytes(block), self.h);
+        }
+    }
+
+    pub fn finalize(self) -> [u8; 16] {
+        self.y.to_le_bytes()
+    }
+}
+
+// mulX_GHASH: multiply by x in GHASH's bit order, masked like gf128_mul.
+fn mul_x_ghash(v: u128) -> u128 {
+    const R: u128 = 0xe1 << 120;
+
+    let lsb = v & 1;
+    (v >> 1) ^ (R & lsb.wrapping_neg())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    // RFC 8452 appendix A.
+    #[test]
+    fn polyval_rfc8452_appendix_a() {
+        let h = [
+            0x25, 0x62, 0x93, 0x47, 0x58, 0x92, 0x42, 0x76,
+            0x1d, 0x31, 0xf8, 0x26, 0xba, 0x4b, 0x75, 0x7b,
+        ];
+        let x = [
+            0x4f, 0x4f, 0x95, 0x66, 0x8c, 0x83, 0xdf, 0xb6,
+            0x40, 0x17, 0x62, 0xbb, 0x2d, 0x01, 0xa2, 0x62,
+            0xd1, 0xa2, 0x4d, 0xdd, 0x27, 0x21, 0xd0, 0x06,
+            0xbb, 0xe4, 0x5f, 0x20, 0xd3, 0xc9, 0xf3, 0x62,
+        ];
+
+        let mut polyval = Polyval::new(&h);
+        polyval.update_padded(&x);
+
+        assert_eq!(
+            (bytes_to_hex(&polyval.finalize())),
+            (b"f7a3b47b846119fae5b7866cf5e5b77e")
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use std::fmt;
+
+/// Errors of HKDF-Expand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HkdfError {
+    /// More output was requested than 255 hash blocks can provide.
+    OutputTooLong { len: usize, max: usize },
+}
+
+impl fmt::Display for HkdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HkdfError::OutputTooLong { len, max } => write!(
+                f, 
+                "HKDF output of {len} bytes exceeds the maximum of {max} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HkdfError {}
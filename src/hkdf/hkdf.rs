@@ -0,0 +1,307 @@
+use crate::digest::Digest;
+use crate::hkdf::HkdfError;
+use crate::hmac::Hmac;
+
+/// HKDF-Extract: concentrate the entropy of `ikm` into a pseudorandom key.
+///
+/// # Arguments
+/// - `salt`: Optional non-secret random value; an empty salt stands for
+///   `D::OUTPUT_SIZE` zero bytes.
+/// - `ikm`: Input keying material.
+///
+/// # Returns
+/// `PRK = HMAC-Hash(salt, IKM)`, one hash output long.
+///
+/// # Reference
+/// [RFC 5869](https://www.rfc-editor.org/rfc/rfc5869), section 2.2.
+pub fn hkdf_extract<D: Digest>(salt: &[u8], ikm: &[u8]) -> D::Output {
+    let zeros = vec![0x00u8; D::OUTPUT_SIZE];
+    let salt = if salt.is_empty() { &zeros[..] } else { salt };
+
+    let mut mac = Hmac::<D>::new(salt);
+    mac.update(ikm);
+    mac.finalize()
+}
+
+/// HKDF-Expand: stretch a pseudorandom key into `len` bytes of output
+/// keying material, bound to the context string `info`.
+///
+/// # Description
+/// - `T(0)` is empty, `T(i) = HMAC-Hash(PRK, T(i - 1) || info || i)`.
+/// - The output is the first `len` bytes of `T(1) || T(2) || ...`.
+/// - The block counter `i` is a single byte, so at most 255 blocks can be
+///   produced; longer requests return `HkdfError::OutputTooLong`.
+///
+/// # Reference
+/// [RFC 5869](https://www.rfc-editor.org/rfc/rfc5869), section 2.3.
+pub fn hkdf_expand<D: Digest>(
+    prk: &[u8], 
+    info: &[u8], 
+    len: usize
+) -> Result<Vec<u8>, HkdfError> {
+    let max = 255 * D::OUTPUT_SIZE;
+    if len > max {
+        return Err(HkdfError::OutputTooLong { len, max });
+    }
+
+    let keyed = Hmac::<D>::new(prk);
+    let mut okm: Vec<u8> = Vec::with_capacity(len);
+    let mut t: Vec<u8> = Vec::new();
+    let mut counter: u8 = 1;
+
+    while okm.len() < len {
+        let mut mac = keyed.clone();
+        mac.update(&t);
+        mac.update(info);
+        mac.update(&[counter]);
+        t = mac.finalize().as_ref().to_vec();
+
+        let take = (len - okm.len()).min(t.len());
+        okm.extend_from_slice(&t[..take]);
+        counter = counter.wrapping_add(1);
+    }
+
+    Ok(okm)
+}
+
+/// HKDF: extract-then-expand in one call.
+pub fn hkdf<D: Digest>(
+    salt: &[u8], 
+    ikm: &[u8], 
+    info: &[u8], 
+    len: usize
+) -> Result<Vec<u8>, HkdfError> {
+    let prk = hkdf_extract::<D>(salt, ikm);
+    hkdf_expand::<D>(prk.as_ref(), info, len)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha1::Sha1;
+    use crate::sha256::Sha256;
+
+    #[test]
+    fn rfc5869_case1_sha256() {
+        let ikm: Vec<u8> = vec![0x0b; 22];
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+
+        let prk = hkdf_extract::<Sha256>(&salt, &ikm);
+        let expected_prk = [
+            0x07, 0x77, 0x09, 0x36, 0x2c, 0x2e, 0x32, 0xdf,
+            0x0d, 0xdc, 0x3f, 0x0d, 0xc4, 0x7b, 0xba, 0x63,
+            0x90, 0xb6, 0xc7, 0x3b, 0xb5, 0x0f, 0x9c, 0x31,
+            0x22, 0xec, 0x84, 0x4a, 0xd7, 0xc2, 0xb3, 0xe5,
+        ];
+        assert_eq!((prk), (expected_prk));
+
+        let okm = hkdf_expand::<Sha256>(&prk, &info, 42).unwrap();
+        let expected_okm = vec![
+            0x3c, 0xb2, 0x5f, 0x25, 0xfa, 0xac, 0xd5, 0x7a,
+            0x90, 0x43, 0x4f, 0x64, 0xd0, 0x36, 0x2f, 0x2a,
+            0x2d, 0x2d, 0x0a, 0x90, 0xcf, 0x1a, 0x5a, 0x4c,
+            0x5d, 0xb0, 0x2d, 0x56, 0xec, 0xc4, 0xc5, 0xbf,
+            0x34, 0x00, 0x72, 0x08, 0xd5, 0xb8, 0x87, 0x18,
+            0x58, 0x65,
+        ];
+        assert_eq!((okm), (expected_okm));
+    }
+
+    #[test]
+    fn rfc5869_case2_sha256() {
+        let ikm: Vec<u8> = (0x00..=0x4f).collect();
+        let salt: Vec<u8> = (0x60..=0xaf).collect();
+        let info: Vec<u8> = (0xb0..=0xff).collect();
+
+        let prk = hkdf_extract::<Sha256>(&salt, &ikm);
+        let expected_prk = [
+            0x06, 0xa6, 0xb8, 0x8c, 0x58, 0x53, 0x36, 0x1a,
+            0x06, 0x10, 0x4c, 0x9c, 0xeb, 0x35, 0xb4, 0x5c,
+            0xef, 0x76, 0x00, 0x14, 0x90, 0x46, 0x71, 0x01,
+            0x4a, 0x19, 0x3f, 0x40, 0xc1, 0x5f, 0xc2, 0x44,
+        ];
+        assert_eq!((prk), (expected_prk));
+
+        let okm = hkdf_expand::<Sha256>(&prk, &info, 82).unwrap();
+        let expected_okm = vec![
+            0xb1, 0x1e, 0x39, 0x8d, 0xc8, 0x03, 0x27, 0xa1,
+            0xc8, 0xe7, 0xf7, 0x8c, 0x59, 0x6a, 0x49, 0x34,
+            0x4f, 0x01, 0x2e, 0xda, 0x2d, 0x4e, 0xfa, 0xd8,
+            0xa0, 0x50, 0xcc, 0x4c, 0x19, 0xaf, 0xa9, 0x7c,
+            0x59, 0x04, 0x5a, 0x99, 0xca, 0xc7, 0x82, 0x72,
+            0x71, 0xcb, 0x41, 0xc6, 0x5e, 0x59, 0x0e, 0x09,
+            0xda, 0x32, 0x75, 0x60, 0x0c, 0x2f, 0x09, 0xb8,
+            0x36, 0x77, 0x93, 0xa9, 0xac, 0xa3, 0xdb, 0x71,
+            0xcc, 0x30, 0xc5, 0x81, 0x79, 0xec, 0x3e, 0x87,
+            0xc1, 0x4c, 0x01, 0xd5, 0xc1, 0xf3, 0x43, 0x4f,
+            0x1d, 0x87,
+        ];
+        assert_eq!((okm), (expected_okm));
+    }
+
+    #[test]
+    fn rfc5869_case3_sha256() {
+        let ikm: Vec<u8> = vec![0x0b; 22];
+        let salt: Vec<u8> = vec![];
+        let info: Vec<u8> = vec![];
+
+        let prk = hkdf_extract::<Sha256>(&salt, &ikm);
+        let expected_prk = [
+            0x19, 0xef, 0x24, 0xa3, 0x2c, 0x71, 0x7b, 0x16,
+            0x7f, 0x33, 0xa9, 0x1d, 0x6f, 0x64, 0x8b, 0xdf,
+            0x96, 0x59, 0x67, 0x76, 0xaf, 0xdb, 0x63, 0x77,
+            0xac, 0x43, 0x4c, 0x1c, 0x29, 0x3c, 0xcb, 0x04,
+        ];
+        assert_eq!((prk), (expected_prk));
+
+        let okm = hkdf_expand::<Sha256>(&prk, &info, 42).unwrap();
+        let expected_okm = vec![
+            0x8d, 0xa4, 0xe7, 0x75, 0xa5, 0x63, 0xc1, 0x8f,
+            0x71, 0x5f, 0x80, 0x2a, 0x06, 0x3c, 0x5a, 0x31,
+            0xb8, 0xa1, 0x1f, 0x5c, 0x5e, 0xe1, 0x87, 0x9e,
+            0xc3, 0x45, 0x4e, 0x5f, 0x3c, 0x73, 0x8d, 0x2d,
+            0x9d, 0x20, 0x13, 0x95, 0xfa, 0xa4, 0xb6, 0x1a,
+            0x96, 0xc8,
+        ];
+        assert_eq!((okm), (expected_okm));
+    }
+
+    #[test]
+    fn rfc5869_case4_sha1() {
+        let ikm: Vec<u8> = vec![0x0b; 11];
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+
+        let prk = hkdf_extract::<Sha1>(&salt, &ikm);
+        let expected_prk = [
+            0x9b, 0x6c, 0x18, 0xc4, 0x32, 0xa7, 0xbf, 0x8f,
+            0x0e, 0x71, 0xc8, 0xeb, 0x88, 0xf4, 0xb3, 0x0b,
+            0xaa, 0x2b, 0xa2, 0x43,
+        ];
+        assert_eq!((prk), (expected_prk));
+
+        let okm = hkdf_expand::<Sha1>(&prk, &info, 42).unwrap();
+        let expected_okm = vec![
+            0x08, 0x5a, 0x01, 0xea, 0x1b, 0x10, 0xf3, 0x69,
+            0x33, 0x06, 0x8b, 0x56, 0xef, 0xa5, 0xad, 0x81,
+            0xa4, 0xf1, 0x4b, 0x82, 0x2f, 0x5b, 0x09, 0x15,
+            0x68, 0xa9, 0xcd, 0xd4, 0xf1, 0x55, 0xfd, 0xa2,
+            0xc2, 0x2e, 0x42, 0x24, 0x78, 0xd3, 0x05, 0xf3,
+            0xf8, 0x96,
+        ];
+        assert_eq!((okm), (expected_okm));
+    }
+
+    #[test]
+    fn rfc5869_case5_sha1() {
+        let ikm: Vec<u8> = (0x00..=0x4f).collect();
+        let salt: Vec<u8> = (0x60..=0xaf).collect();
+        let info: Vec<u8> = (0xb0..=0xff).collect();
+
+        let prk = hkdf_extract::<Sha1>(&salt, &ikm);
+        let expected_prk = [
+            0x8a, 0xda, 0xe0, 0x9a, 0x2a, 0x30, 0x70, 0x59,
+            0x47, 0x8d, 0x30, 0x9b, 0x26, 0xc4, 0x11, 0x5a,
+            0x22, 0x4c, 0xfa, 0xf6,
+        ];
+        assert_eq!((prk), (expected_prk));
+
+        let okm = hkdf_expand::<Sha1>(&prk, &info, 82).unwrap();
+        let expected_okm = vec![
+            0x0b, 0xd7, 0x70, 0xa7, 0x4d, 0x11, 0x60, 0xf7,
+            0xc9, 0xf1, 0x2c, 0xd5, 0x91, 0x2a, 0x06, 0xeb,
+            0xff, 0x6a, 0xdc, 0xae, 0x89, 0x9d, 0x92, 0x19,
+            0x1f, 0xe4, 0x30, 0x56, 0x73, 0xba, 0x2f, 0xfe,
+            0x8f, 0xa3, 0xf1, 0xa4, 0xe5, 0xad, 0x79, 0xf3,
+            0xf3, 0x34, 0xb3, 0xb2, 0x02, 0xb2, 0x17, 0x3c,
+            0x48, 0x6e, 0xa3, 0x7c, 0xe3, 0xd3, 0x97, 0xed,
+            0x03, 0x4c, 0x7f, 0x9d, 0xfe, 0xb1, 0x5c, 0x5e,
+            0x92, 0x73, 0x36, 0xd0, 0x44, 0x1f, 0x4c, 0x43,
+            0x00, 0xe2, 0xcf, 0xf0, 0xd0, 0x90, 0x0b, 0x52,
+            0xd3, 0xb4,
+        ];
+        assert_eq!((okm), (expected_okm));
+    }
+
+    #[test]
+    fn rfc5869_case6_sha1() {
+        let ikm: Vec<u8> = vec![0x0b; 22];
+        let salt: Vec<u8> = vec![];
+        let info: Vec<u8> = vec![];
+
+        let prk = hkdf_extract::<Sha1>(&salt, &ikm);
+        let expected_prk = [
+            0xda, 0x8c, 0x8a, 0x73, 0xc7, 0xfa, 0x77, 0x28,
+            0x8e, 0xc6, 0xf5, 0xe7, 0xc2, 0x97, 0x78, 0x6a,
+            0xa0, 0xd3, 0x2d, 0x01,
+        ];
+        assert_eq!((prk), (expected_prk));
+
+        let okm = hkdf_expand::<Sha1>(&prk, &info, 42).unwrap();
+        let expected_okm = vec![
+            0x0a, 0xc1, 0xaf, 0x70, 0x02, 0xb3, 0xd7, 0x61,
+            0xd1, 0xe5, 0x52, 0x98, 0xda, 0x9d, 0x05, 0x06,
+            0xb9, 0xae, 0x52, 0x05, 0x72, 0x20, 0xa3, 0x06,
+            0xe0, 0x7b, 0x6b, 0x87, 0xe8, 0xdf, 0x21, 0xd0,
+            0xea, 0x00, 0x03, 0x3d, 0xe0, 0x39, 0x84, 0xd3,
+            0x49, 0x18,
+        ];
+        assert_eq!((okm), (expected_okm));
+    }
+
+    #[test]
+    fn rfc5869_case7_sha1() {
+        // Salt not provided: defaults to HashLen zero bytes.
+        let ikm: Vec<u8> = vec![0x0c; 22];
+        let salt: Vec<u8> = vec![];
+        let info: Vec<u8> = vec![];
+
+        let prk = hkdf_extract::<Sha1>(&salt, &ikm);
+        let expected_prk = [
+            0x2a, 0xdc, 0xca, 0xda, 0x18, 0x77, 0x9e, 0x7c,
+            0x20, 0x77, 0xad, 0x2e, 0xb1, 0x9d, 0x3f, 0x3e,
+            0x73, 0x13, 0x85, 0xdd,
+        ];
+        assert_eq!((prk), (expected_prk));
+
+        let okm = hkdf_expand::<Sha1>(&prk, &info, 42).unwrap();
+        let expected_okm = vec![
+            0x2c, 0x91, 0x11, 0x72, 0x04, 0xd7, 0x45, 0xf3,
+            0x50, 0x0d, 0x63, 0x6a, 0x62, 0xf6, 0x4f, 0x0a,
+            0xb3, 0xba, 0xe5, 0x48, 0xaa, 0x53, 0xd4, 0x23,
+            0xb0, 0xd1, 0xf2, 0x7e, 0xbb, 0xa6, 0xf5, 0xe5,
+            0x67, 0x3a, 0x08, 0x1d, 0x70, 0xcc, 0xe7, 0xac,
+            0xfc, 0x48,
+        ];
+        assert_eq!((okm), (expected_okm));
+    }
+
+    #[test]
+    fn expands_up_to_255_blocks() {
+        let prk = hkdf_extract::<Sha256>(b"salt", b"ikm");
+        let okm = hkdf_expand::<Sha256>(&prk, b"info", 255 * 32).unwrap();
+        assert_eq!((okm.len()), (8_160));
+
+        // Shorter output is a prefix of longer output.
+        let short = hkdf_expand::<Sha256>(&prk, b"info", 33).unwrap();
+        assert_eq!((&okm[..33]), (&short[..]));
+    }
+
+    #[test]
+    fn rejects_more_than_255_blocks() {
+        let prk = hkdf_extract::<Sha1>(b"salt", b"ikm");
+        let result = hkdf_expand::<Sha1>(&prk, b"info", 255 * 20 + 1);
+        let error = HkdfError::OutputTooLong { len: 5_101, max: 5_100 };
+
+        assert_eq!((result), (Err(error)));
+    }
+
+    #[test]
+    fn one_call_matches_extract_then_expand() {
+        let prk = hkdf_extract::<Sha256>(b"salt", b"ikm");
+        let expected = hkdf_expand::<Sha256>(&prk, b"info", 64);
+
+        assert_eq!((hkdf::<Sha256>(b"salt", b"ikm", b"info", 64)), (expected));
+    }
+}
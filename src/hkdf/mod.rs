@@ -0,0 +1,6 @@
+pub mod error;
+#[allow(clippy::module_inception)]
+pub mod hkdf;
+
+pub use error::HkdfError;
+pub use hkdf::{ hkdf, hkdf_expand, hkdf_extract };
@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// Errors of the `Hmac` context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HmacError {
+    /// Requested tag length is longer than the hash output, or shorter than
+    /// RFC 2104 allows (half the output, and at least 10 bytes).
+    InvalidTagLength { len: usize, min: usize, max: usize },
+}
+
+impl fmt::Display for HmacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HmacError::InvalidTagLength { len, min, max } => write!(
+                f, 
+                "invalid HMAC tag length {len}, expected {min} to {max} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HmacError {}
@@ -1,4 +1,7 @@
-use crate::sha256::sha256;
+use crate::digest::Digest;
+use crate::hmac::{ get_right_block_size_with, HmacError };
+use crate::sha256::Sha256;
+use crate::utils::ct_eq;
 
 /// HMAC (Hash-based Message Authentication Code) using SHA-256.
 /// 
@@ -19,35 +22,131 @@ use crate::sha256::sha256;
 /// - [RFC 2104](https://www.rfc-editor.org/rfc/rfc2104)  
 /// - [RFC 4231](https://www.rfc-editor.org/rfc/rfc4231)
 pub fn hmac(k: &[u8], m: &[u8]) -> [u8; 32] {
-    let mut ipad: Vec<u8> = vec![0x36u8; 64];
-    let mut opad: Vec<u8> = vec![0x5cu8; 64];
-
-    // Vector ipad[i] and opad[i] XORed with k[i].
-    for i in 0..64 {
-        ipad[i] ^= k[i];
-        opad[i] ^= k[i];
-    };
+    hmac_with::<Sha256>(k, m)
+}
 
-    let msg: Vec<u8> = m.to_vec();
-    let msg_len = m.len();
+/// HMAC over any hash `D` implementing `Digest`.
+///
+/// # Arguments
+/// - `k`: Key of any length, normalized to `K0` with
+///   `get_right_block_size_with::<D>` (a `K0` passes through unchanged).
+/// - `m`: Message as a byte slice (`&[u8]`).
+///
+/// # Description
+/// Same construction as `hmac`, with the pads sized to `D::BLOCK_SIZE` and
+/// both hashes computed with `D`.
+///
+/// # Returns
+/// The `D::OUTPUT_SIZE`-byte authentication tag.
+pub fn hmac_with<D: Digest>(k: &[u8], m: &[u8]) -> D::Output {
+    let block_size = D::BLOCK_SIZE;
+    let k0 = get_right_block_size_with::<D>(k);
+    let mut ipad: Vec<u8> = vec![0x36u8; block_size];
+    let mut opad: Vec<u8> = vec![0x5cu8; block_size];
 
-    // ((K0 ^ ipad) || text)).
-    let mut j = 0;
-    while j < msg_len {
-        ipad.push(msg[j]);
-        j += 1;
+    // Vector ipad[i] and opad[i] XORed with k0[i].
+    for i in 0..block_size {
+        ipad[i] ^= k0[i];
+        opad[i] ^= k0[i];
     };
 
     // H((K0 ^ ipad) || text)).
-    let sha = sha256(&ipad);
+    let mut inner = D::new();
+    inner.update(&ipad);
+    inner.update(m);
+    let inner_hash = inner.finalize();
 
-    // ((K0 ^ opad )|| H((K0 ^ ipad) || text))
-    for k in 0..32 {
-        opad.push(sha[k]);
-    };
-    
     // h((K0 ^ opad )|| H((K0 ^ ipad) || text)).
-    sha256(&opad)
+    let mut outer = D::new();
+    outer.update(&opad);
+    outer.update(inner_hash.as_ref());
+    outer.finalize()
+}
+
+/// HMAC context over the hash `D`, for keys of any length.
+///
+/// # Description
+/// - `new` normalizes the key to `K0` itself and absorbs `K0 ^ ipad` and
+///   `K0 ^ opad` into two hash states up front.
+/// - `update` streams the message into the inner hash.
+/// - `finalize` returns the full tag, `finalize_truncated` its leftmost
+///   bytes.
+/// - `verify` recomputes the tag and compares it in constant time.
+///
+/// Cloning a keyed context reuses both pad states, so the key is only
+/// processed once when many messages are MACed under it.
+#[derive(Clone)]
+pub struct Hmac<D: Digest> {
+    inner: D,
+    outer: D,
+}
+
+impl<D: Digest> Hmac<D> {
+    pub fn new(key: &[u8]) -> Self {
+        let k0 = get_right_block_size_with::<D>(key);
+
+        let mut ipad: Vec<u8> = vec![0x36u8; D::BLOCK_SIZE];
+        let mut opad: Vec<u8> = vec![0x5cu8; D::BLOCK_SIZE];
+        for i in 0..D::BLOCK_SIZE {
+            ipad[i] ^= k0[i];
+            opad[i] ^= k0[i];
+        };
+
+        let mut inner = D::new();
+        inner.update(&ipad);
+        let mut outer = D::new();
+        outer.update(&opad);
+
+        Hmac { inner, outer }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Full `D::OUTPUT_SIZE`-byte tag.
+    pub fn finalize(self) -> D::Output {
+        let inner_hash = self.inner.finalize();
+
+        let mut outer = self.outer;
+        outer.update(inner_hash.as_ref());
+        outer.finalize()
+    }
+
+    /// Leftmost `len` bytes of the tag.
+    ///
+    /// RFC 2104 allows truncation down to half the output, but not below
+    /// 80 bits; anything outside that range is an error.
+    pub fn finalize_truncated(self, len: usize) -> Result<Vec<u8>, HmacError> {
+        check_tag_len::<D>(len)?;
+
+        let tag = self.finalize();
+        Ok(tag.as_ref()[..len].to_vec())
+    }
+
+    /// Check a full or truncated tag in constant time.
+    pub fn verify(self, tag: &[u8]) -> bool {
+        if check_tag_len::<D>(tag.len()).is_err() {
+            return false;
+        }
+
+        let expected = self.finalize();
+        ct_eq(&expected.as_ref()[..tag.len()], tag)
+    }
+}
+
+// Accepted tag lengths: half the output (at least 10 bytes) up to the full
+// output.
+fn check_tag_len<D: Digest>(len: usize) -> Result<(), HmacError> {
+    let max = D::OUTPUT_SIZE;
+    let min = (max / 2).max(10).min(max);
+
+    if len < min || len > max {
+        return Err(HmacError::InvalidTagLength { len, min, max });
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -124,4 +223,97 @@ mod test {
         ];
         assert_eq!(mac, expected);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn hmac_type_takes_raw_keys() {
+        // RFC 4231 test case 2 (short key) and 6 (131-byte key).
+        let mut mac = Hmac::<Sha256>::new(b"Jefe");
+        mac.update(b"what do ya want for nothing?");
+        let expected = [
+            0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e,
+            0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75, 0xc7,
+            0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83,
+            0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec, 0x38, 0x43,
+        ];
+        assert_eq!((mac.finalize()), (expected));
+
+        let long_key = vec![0xaa; 131];
+        let msg = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        let mut mac = Hmac::<Sha256>::new(&long_key);
+        mac.update(msg);
+        assert_eq!(
+            (mac.finalize()), 
+            (hmac(&get_right_block_size(&long_key), msg))
+        );
+    }
+
+    #[test]
+    fn hmac_type_streams_in_chunks() {
+        let key = b"key";
+        let msg = b"a".repeat(1_000);
+        let expected = hmac(&get_right_block_size(key), &msg);
+
+        for chunk_size in [1, 63, 64, 65, 999] {
+            let mut mac = Hmac::<Sha256>::new(key);
+            for chunk in msg.chunks(chunk_size) {
+                mac.update(chunk);
+            }
+
+            assert_eq!((mac.finalize()), (expected));
+        }
+    }
+
+    #[test]
+    fn hmac_type_reuses_keyed_state() {
+        let keyed = Hmac::<Sha256>::new(b"key");
+
+        let mut first = keyed.clone();
+        first.update(b"first");
+        let mut second = keyed.clone();
+        second.update(b"second");
+
+        let k0 = get_right_block_size(b"key");
+        assert_eq!((first.finalize()), (hmac(&k0, b"first")));
+        assert_eq!((second.finalize()), (hmac(&k0, b"second")));
+    }
+
+    #[test]
+    fn truncates_tag_rfc4231_case5() {
+        let mut mac = Hmac::<Sha256>::new(&[0x0c; 20]);
+        mac.update(b"Test With Truncation");
+        let expected = vec![
+            0xa3, 0xb6, 0x16, 0x74, 0x73, 0x10, 0x0e, 0xe0,
+            0x6e, 0x0c, 0x79, 0x6c, 0x29, 0x55, 0x55, 0x2b,
+        ];
+
+        assert_eq!((mac.finalize_truncated(16)), (Ok(expected)));
+    }
+
+    #[test]
+    fn rejects_tag_lengths_outside_rfc2104_bounds() {
+        let mac = Hmac::<Sha256>::new(b"key");
+        let error = HmacError::InvalidTagLength { len: 15, min: 16, max: 32 };
+        assert_eq!((mac.clone().finalize_truncated(15)), (Err(error)));
+
+        let error = HmacError::InvalidTagLength { len: 33, min: 16, max: 32 };
+        assert_eq!((mac.finalize_truncated(33)), (Err(error)));
+    }
+
+    #[test]
+    fn verifies_full_and_truncated_tags() {
+        let mut mac = Hmac::<Sha256>::new(b"key");
+        mac.update(b"The quick brown fox jumps over the lazy dog");
+        let tag = mac.clone().finalize();
+
+        assert!(mac.clone().verify(&tag));
+        assert!(mac.clone().verify(&tag[..16]));
+
+        let mut forged = tag;
+        forged[31] ^= 0x01;
+        assert!(!mac.clone().verify(&forged));
+
+        // Too short to be an accepted truncation, even if it matches.
+        assert!(!mac.clone().verify(&tag[..8]));
+        assert!(!mac.verify(&[]));
+    }
+}
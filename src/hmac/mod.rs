@@ -1,5 +1,13 @@
+pub mod error;
+#[allow(clippy::module_inception)]
 pub mod hmac;
 pub mod size_util;
+pub mod variants;
 
-pub use hmac::hmac;
-pub use size_util::get_right_block_size;
\ No newline at end of file
+pub use error::HmacError;
+pub use hmac::{ hmac, hmac_with, Hmac };
+pub use size_util::{ get_right_block_size, get_right_block_size_with };
+pub use variants::{
+    hmac_md5, hmac_ripemd160, hmac_sha1, hmac_sha224, hmac_sha384,
+    hmac_sha512, hmac_sha3_224, hmac_sha3_256, hmac_sha3_384, hmac_sha3_512
+};
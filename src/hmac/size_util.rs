@@ -1,4 +1,5 @@
-use crate::sha256::sha256;
+use crate::digest::Digest;
+use crate::sha256::Sha256;
 
 /// Normalize a key to the correct block size (64 bytes for SHA-256).
 ///
@@ -14,9 +15,17 @@ use crate::sha256::sha256;
 /// # Returns
 /// A vector of bytes (`Vec<u8>`) representing the normalized key (`K0`).
 pub fn get_right_block_size(k: &[u8]) -> Vec<u8> {
+    get_right_block_size_with::<Sha256>(k)
+}
+
+/// Normalize a key to the block size of the hash `D`.
+///
+/// Same rules as `get_right_block_size`, with `D::BLOCK_SIZE` as the block
+/// size and `D` hashing keys that are too long.
+pub fn get_right_block_size_with<D: Digest>(k: &[u8]) -> Vec<u8> {
     let mut k0: Vec<u8> = k.to_vec();
     let key_length = k0.len();
-    let block_size = 64;
+    let block_size = D::BLOCK_SIZE;
     
     // k = k0 if key length == block_size.
     if key_length == block_size {
@@ -25,7 +34,7 @@ pub fn get_right_block_size(k: &[u8]) -> Vec<u8> {
     
     // Hash k0 and append 0s if key_length > block_size.
     if key_length > block_size {
-        k0 = sha256(&k0).to_vec();
+        k0 = D::digest(&k0).as_ref().to_vec();
         let mut k_l = k0.len();
         while k_l < block_size {
             k0.push(0x00);
@@ -48,6 +57,8 @@ pub fn get_right_block_size(k: &[u8]) -> Vec<u8> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::sha512::Sha512;
+    use crate::sha3::Sha3_256;
 
     #[test]
     fn computes_k_as_k0_since_k_length_is_block_length() {
@@ -109,4 +120,22 @@ mod test {
 
         assert_eq!((result), (expected));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn pads_to_the_block_size_of_the_given_hash() {
+        let k0 = get_right_block_size_with::<Sha512>(b"abc");
+        assert_eq!((k0.len()), (128));
+
+        let k0 = get_right_block_size_with::<Sha3_256>(b"abc");
+        assert_eq!((k0.len()), (136));
+    }
+
+    #[test]
+    fn hashes_long_keys_with_the_given_hash() {
+        let k = [0xaa; 129];
+        let k0 = get_right_block_size_with::<Sha512>(&k);
+
+        assert_eq!((&k0[..64]), (&Sha512::digest(&k)[..]));
+        assert_eq!((&k0[64..]), (&[0x00; 64][..]));
+    }
+}
@@ -0,0 +1,381 @@
+use crate::hmac::hmac_with;
+use crate::md5::Md5;
+use crate::ripemd::Ripemd160;
+use crate::sha1::Sha1;
+use crate::sha256::Sha224;
+use crate::sha512::{ Sha384, Sha512 };
+use crate::sha3::{ Sha3_224, Sha3_256, Sha3_384, Sha3_512 };
+
+// Named HMAC instantiations. Like `hmac`, each takes a key of any length
+// and normalizes it to K0 for its own hash D.
+
+/// HMAC-MD5: 16-byte tag.
+pub fn hmac_md5(k: &[u8], m: &[u8]) -> [u8; 16] {
+    hmac_with::<Md5>(k, m)
+}
+
+/// HMAC-SHA-1: 20-byte tag.
+pub fn hmac_sha1(k: &[u8], m: &[u8]) -> [u8; 20] {
+    hmac_with::<Sha1>(k, m)
+}
+
+/// HMAC-SHA-224: 28-byte tag.
+pub fn hmac_sha224(k: &[u8], m: &[u8]) -> [u8; 28] {
+    hmac_with::<Sha224>(k, m)
+}
+
+/// HMAC-SHA-384: 48-byte tag.
+pub fn hmac_sha384(k: &[u8], m: &[u8]) -> [u8; 48] {
+    hmac_with::<Sha384>(k, m)
+}
+
+/// HMAC-SHA-512: 64-byte tag.
+pub fn hmac_sha512(k: &[u8], m: &[u8]) -> [u8; 64] {
+    hmac_with::<Sha512>(k, m)
+}
+
+/// HMAC-RIPEMD-160: 20-byte tag.
+pub fn hmac_ripemd160(k: &[u8], m: &[u8]) -> [u8; 20] {
+    hmac_with::<Ripemd160>(k, m)
+}
+
+/// HMAC-SHA3-224: 28-byte tag.
+pub fn hmac_sha3_224(k: &[u8], m: &[u8]) -> [u8; 28] {
+    hmac_with::<Sha3_224>(k, m)
+}
+
+/// HMAC-SHA3-256: 32-byte tag.
+pub fn hmac_sha3_256(k: &[u8], m: &[u8]) -> [u8; 32] {
+    hmac_with::<Sha3_256>(k, m)
+}
+
+/// HMAC-SHA3-384: 48-byte tag.
+pub fn hmac_sha3_384(k: &[u8], m: &[u8]) -> [u8; 48] {
+    hmac_with::<Sha3_384>(k, m)
+}
+
+/// HMAC-SHA3-512: 64-byte tag.
+pub fn hmac_sha3_512(k: &[u8], m: &[u8]) -> [u8; 64] {
+    hmac_with::<Sha3_512>(k, m)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hmac::hmac;
+    use crate::sha3::bytes_to_hex;
+
+    // Key and data of RFC 4231 test cases 1 to 7.
+    fn rfc4231_cases() -> Vec<(Vec<u8>, Vec<u8>)> {
+        vec![
+            (vec![0x0b; 20], b"Hi There".to_vec()),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+            (vec![0xaa; 20], vec![0xdd; 50]),
+            ((0x01..=0x19).collect(), vec![0xcd; 50]),
+            (vec![0x0c; 20], b"Test With Truncation".to_vec()),
+            (
+                vec![0xaa; 131], 
+                b"Test Using Larger Than Block-Size Key - \
+                Hash Key First".to_vec()
+            ),
+            (
+                vec![0xaa; 131], 
+                b"This is a test using a larger than block-size key and a \
+                larger than block-size data. The key needs to be hashed \
+                before being used by the HMAC algorithm.".to_vec()
+            ),
+        ]
+    }
+
+    // Key and data of RFC 2202 test cases 1 to 7; the short keys are
+    // `key_len` bytes (16 for MD5, 20 for SHA-1 and RIPEMD-160).
+    fn rfc2202_cases(key_len: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+        vec![
+            (vec![0x0b; key_len], b"Hi There".to_vec()),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+            (vec![0xaa; key_len], vec![0xdd; 50]),
+            ((0x01..=0x19).collect(), vec![0xcd; 50]),
+            (vec![0x0c; key_len], b"Test With Truncation".to_vec()),
+            (
+                vec![0xaa; 80], 
+                b"Test Using Larger Than Block-Size Key - \
+                Hash Key First".to_vec()
+            ),
+            (
+                vec![0xaa; 80], 
+                b"Test Using Larger Than Block-Size Key and Larger \
+                Than One Block-Size Data".to_vec()
+            ),
+        ]
+    }
+
+    // Key and data of the NIST HMAC-SHA3 examples (RFC 4231 has no SHA-3
+    // vectors): keys 00 01 02 ... shorter than, equal to and longer than the
+    // block size. See "Examples with Intermediate Values",
+    // https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+    fn nist_sha3_cases(key_lens: [usize; 3]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let msgs: [&[u8]; 3] = [
+            b"Sample message for keylen<blocklen",
+            b"Sample message for keylen=blocklen",
+            b"Sample message for keylen>blocklen",
+        ];
+
+        key_lens.iter()
+            .zip(msgs)
+            .map(|(&len, msg)| ((0..len as u8).collect(), msg.to_vec()))
+            .collect()
+    }
+
+    // MAC the data under the raw keys and compare hex tags. An expected tag
+    // shorter than the output checks a truncated tag.
+    fn check(
+        cases: &[(Vec<u8>, Vec<u8>)], 
+        expected: &[&[u8]],
+        mac: fn(&[u8], &[u8]) -> Vec<u8>
+    ) {
+        assert_eq!((cases.len()), (expected.len()));
+        for ((key, data), tag) in cases.iter().zip(expected) {
+            let hex = bytes_to_hex(&mac(key, data));
+            assert_eq!((&hex[..tag.len()]), (*tag));
+        }
+    }
+
+    #[test]
+    fn hmac_sha256_rfc4231() {
+        let expected: [&[u8]; 7] = [
+            b"b0344c61d8db38535ca8afceaf0bf12b\
+              881dc200c9833da726e9376c2e32cff7",
+            b"5bdcc146bf60754e6a042426089575c7\
+              5a003f089d2739839dec58b964ec3843",
+            b"773ea91e36800e46854db8ebd09181a7\
+              2959098b3ef8c122d9635514ced565fe",
+            b"82558a389a443c0ea4cc819899f2083a\
+              85f0faa3e578f8077a2e3ff46729665b",
+            b"a3b6167473100ee06e0c796c2955552b",
+            b"60e431591ee0b67f0d8a26aacbf5b77f\
+              8e0bc6213728c5140546040f0ee37f54",
+            b"9b09ffa71b942fcb27635fbcd5b0e944\
+              bfdc63644f0713938a7f51535c3a35e2",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac(k, m).to_vec();
+        check(&rfc4231_cases(), &expected, mac);
+    }
+
+    #[test]
+    fn hmac_md5_rfc2202() {
+        let expected: [&[u8]; 7] = [
+            b"9294727a3638bb1c13f48ef8158bfc9d",
+            b"750c783e6ab0b503eaa86e310a5db738",
+            b"56be34521d144c88dbb8c733f0e8b3f6",
+            b"697eaf0aca3a3aea3a75164746ffaa79",
+            b"56461ef2342edc00f9bab995690efd4c",
+            b"6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd",
+            b"6f630fad67cda0ee1fb1f562db3aa53e",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac_md5(k, m).to_vec();
+        check(&rfc2202_cases(16), &expected, mac);
+    }
+
+    #[test]
+    fn hmac_sha1_rfc2202() {
+        let expected: [&[u8]; 7] = [
+            b"b617318655057264e28bc0b6fb378c8e\
+              f146be00",
+            b"effcdf6ae5eb2fa2d27416d5f184df9c\
+              259a7c79",
+            b"125d7342b9ac11cd91a39af48aa17b4f\
+              63f175d3",
+            b"4c9007f4026250c6bc8414f9bf50c86c\
+              2d7235da",
+            b"4c1a03424b55e07fe7f27be1d58bb932",
+            b"aa4ae5e15272d00e95705637ce8a3b55\
+              ed402112",
+            b"e8e99d0f45237d786d6bbaa7965c7808\
+              bbff1a91",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac_sha1(k, m).to_vec();
+        check(&rfc2202_cases(20), &expected, mac);
+    }
+
+    #[test]
+    fn hmac_sha224_rfc4231() {
+        let expected: [&[u8]; 7] = [
+            b"896fb1128abbdf196832107cd49df33f\
+              47b4b1169912ba4f53684b22",
+            b"a30e01098bc6dbbf45690f3a7e9e6d0f\
+              8bbea2a39e6148008fd05e44",
+            b"7fb3cb3588c6c1f6ffa9694d7d6ad264\
+              9365b0c1f65d69d1ec8333ea",
+            b"6c11506874013cac6a2abc1bb382627c\
+              ec6a90d86efc012de7afec5a",
+            b"0e2aea68a90c8d37c988bcdb9fca6fa8",
+            b"95e9a0db962095adaebe9b2d6f0dbce2\
+              d499f112f2d2b7273fa6870e",
+            b"3a854166ac5d9f023f54d517d0b39dbd\
+              946770db9c2b95c9f6f565d1",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac_sha224(k, m).to_vec();
+        check(&rfc4231_cases(), &expected, mac);
+    }
+
+    #[test]
+    fn hmac_sha384_rfc4231() {
+        let expected: [&[u8]; 7] = [
+            b"afd03944d84895626b0825f4ab46907f\
+              15f9dadbe4101ec682aa034c7cebc59c\
+              faea9ea9076ede7f4af152e8b2fa9cb6",
+            b"af45d2e376484031617f78d2b58a6b1b\
+              9c7ef464f5a01b47e42ec3736322445e\
+              8e2240ca5e69e2c78b3239ecfab21649",
+            b"88062608d3e6ad8a0aa2ace014c8a86f\
+              0aa635d947ac9febe83ef4e55966144b\
+              2a5ab39dc13814b94e3ab6e101a34f27",
+            b"3e8a69b7783c25851933ab6290af6ca7\
+              7a9981480850009cc5577c6e1f573b4e\
+              6801dd23c4a7d679ccf8a386c674cffb",
+            b"3abf34c3503b2a23a46efc619baef897",
+            b"4ece084485813e9088d2c63a041bc5b4\
+              4f9ef1012a2b588f3cd11f05033ac4c6\
+              0c2ef6ab4030fe8296248df163f44952",
+            b"6617178e941f020d351e2f254e8fd32c\
+              602420feb0b8fb9adccebb82461e99c5\
+              a678cc31e799176d3860e6110c46523e",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac_sha384(k, m).to_vec();
+        check(&rfc4231_cases(), &expected, mac);
+    }
+
+    #[test]
+    fn hmac_sha512_rfc4231() {
+        let expected: [&[u8]; 7] = [
+            b"87aa7cdea5ef619d4ff0b4241a1d6cb0\
+              2379f4e2ce4ec2787ad0b30545e17cde\
+              daa833b7d6b8a702038b274eaea3f4e4\
+              be9d914eeb61f1702e696c203a126854",
+            b"164b7a7bfcf819e2e395fbe73b56e0a3\
+              87bd64222e831fd610270cd7ea250554\
+              9758bf75c05a994a6d034f65f8f0e6fd\
+              caeab1a34d4a6b4b636e070a38bce737",
+            b"fa73b0089d56a284efb0f0756c890be9\
+              b1b5dbdd8ee81a3655f83e33b2279d39\
+              bf3e848279a722c806b485a47e67c807\
+              b946a337bee8942674278859e13292fb",
+            b"b0ba465637458c6990e5a8c5f61d4af7\
+              e576d97ff94b872de76f8050361ee3db\
+              a91ca5c11aa25eb4d679275cc5788063\
+              a5f19741120c4f2de2adebeb10a298dd",
+            b"415fad6271580a531d4179bc891d87a6",
+            b"80b24263c7c1a3ebb71493c1dd7be8b4\
+              9b46d1f41b4aeec1121b013783f8f352\
+              6b56d037e05f2598bd0fd2215d6a1e52\
+              95e64f73f63f0aec8b915a985d786598",
+            b"e37b6a775dc87dbaa4dfa9f96e5e3ffd\
+              debd71f8867289865df5a32d20cdc944\
+              b6022cac3c4982b10d5eeb55c3e4de15\
+              134676fb6de0446065c97440fa8c6a58",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac_sha512(k, m).to_vec();
+        check(&rfc4231_cases(), &expected, mac);
+    }
+
+    #[test]
+    fn hmac_ripemd160_rfc2286() {
+        let expected: [&[u8]; 7] = [
+            b"24cb4bd67d20fc1a5d2ed7732dcc3937\
+              7f0a5668",
+            b"dda6c0213a485a9e24f4742064a7f033\
+              b43c4069",
+            b"b0b105360de759960ab4f35298e116e2\
+              95d8e7c1",
+            b"d5ca862f4d21d5e610e18b4cf1beb97a\
+              4365ecf4",
+            b"7619693978f91d90539ae786500ff3d8",
+            b"6466ca07ac5eac29e1bd523e5ada7605\
+              b791fd8b",
+            b"69ea60798d71616cce5fd0871e23754c\
+              d75d5a0a",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac_ripemd160(k, m).to_vec();
+        check(&rfc2202_cases(20), &expected, mac);
+    }
+
+    #[test]
+    fn hmac_sha3_224_nist_examples() {
+        let expected: [&[u8]; 3] = [
+            b"332cfd59347fdb8e576e77260be4aba2\
+              d6dc53117b3bfb52c6d18c04",
+            b"d8b733bcf66c644a12323d564e24dcf3\
+              fc75f231f3b67968359100c7",
+            b"078695eecc227c636ad31d063a15dd05\
+              a7e819a66ec6d8de1e193e59",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac_sha3_224(k, m).to_vec();
+        let cases = nist_sha3_cases([28, 144, 172]);
+        check(&cases, &expected, mac);
+    }
+
+    #[test]
+    fn hmac_sha3_256_nist_examples() {
+        let expected: [&[u8]; 3] = [
+            b"4fe8e202c4f058e8dddc23d8c34e4673\
+              43e23555e24fc2f025d598f558f67205",
+            b"68b94e2e538a9be4103bebb5aa016d47\
+              961d4d1aa906061313b557f8af2c3faa",
+            b"9bcf2c238e235c3ce88404e813bd2f3a\
+              97185ac6f238c63d6229a00b07974258",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac_sha3_256(k, m).to_vec();
+        let cases = nist_sha3_cases([32, 136, 168]);
+        check(&cases, &expected, mac);
+    }
+
+    #[test]
+    fn hmac_sha3_384_nist_examples() {
+        let expected: [&[u8]; 3] = [
+            b"d588a3c51f3f2d906e8298c1199aa8ff\
+              6296218127f6b38a90b6afe2c5617725\
+              bc99987f79b22a557b6520db710b7f42",
+            b"a27d24b592e8c8cbf6d4ce6fc5bf62d8\
+              fc98bf2d486640d9eb8099e24047837f\
+              5f3bffbe92dcce90b4ed5b1e7e44fa90",
+            b"e5ae4c739f455279368ebf36d4f5354c\
+              95aa184c899d3870e460ebc288ef1f94\
+              70053f73f7c6da2a71bcaec38ce7d6ac",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac_sha3_384(k, m).to_vec();
+        let cases = nist_sha3_cases([48, 104, 152]);
+        check(&cases, &expected, mac);
+    }
+
+    #[test]
+    fn hmac_sha3_512_nist_examples() {
+        let expected: [&[u8]; 3] = [
+            b"4efd629d6c71bf86162658f29943b1c3\
+              08ce27cdfa6db0d9c3ce81763f9cbce5\
+              f7ebe9868031db1a8f8eb7b6b95e5c5e\
+              3f657a8996c86a2f6527e307f0213196",
+            b"544e257ea2a3e5ea19a590e6a24b724c\
+              e6327757723fe2751b75bf007d80f6b3\
+              60744bf1b7a88ea585f9765b47911976\
+              d3191cf83c039f5ffab0d29cc9d9b6da",
+            b"5f464f5e5b7848e3885e49b2c385f069\
+              4985d0e38966242dc4a5fe3fea4b37d4\
+              6b65ceced5dcf59438dd840bab22269f\
+              0ba7febdb9fcf74602a35666b2a32915",
+        ];
+
+        let mac = |k: &[u8], m: &[u8]| hmac_sha3_512(k, m).to_vec();
+        let cases = nist_sha3_cases([64, 72, 136]);
+        check(&cases, &expected, mac);
+    }
+}
@@ -0,0 +1,43 @@
+use std::fmt;
+use crate::aes::AesError;
+
+/// Errors of AES key wrap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyWrapError {
+    /// KW wraps a multiple of 8 bytes, at least 16; KWP wraps 1 up to
+    /// 2^32 - 1 bytes.
+    InvalidKeyDataLength { len: usize },
+    /// Wrapped keys are a multiple of 8 bytes, at least 24 for KW and 16
+    /// for KWP.
+    InvalidWrappedLength { len: usize },
+    /// The integrity check value (and for KWP the length and padding) does
+    /// not match, nothing is returned.
+    IntegrityCheckFailed,
+    /// Invalid AES key-encryption key.
+    Aes(AesError),
+}
+
+impl fmt::Display for KeyWrapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyWrapError::InvalidKeyDataLength { len } => {
+                write!(f, "key data of {len} bytes cannot be wrapped")
+            }
+            KeyWrapError::InvalidWrappedLength { len } => {
+                write!(f, "wrapped key of {len} bytes has an invalid length")
+            }
+            KeyWrapError::IntegrityCheckFailed => {
+                write!(f, "key unwrap integrity check failed")
+            }
+            KeyWrapError::Aes(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for KeyWrapError {}
+
+impl From<AesError> for KeyWrapError {
+    fn from(error: AesError) -> Self {
+        KeyWrapError::Aes(error)
+    }
+}
@@ -0,0 +1,382 @@
+use crate::aes::{ Aes, BLOCK_SIZE };
+use crate::key_wrap::KeyWrapError;
+use crate::utils::ct_eq;
+
+// Semiblock size in bytes, key data is handled in 64-bit halves.
+const SEMIBLOCK: usize = 8;
+
+// RFC 3394 default initial value.
+const KW_IV: [u8; SEMIBLOCK] = [0xa6; SEMIBLOCK];
+
+// RFC 5649 alternative initial value prefix, followed by the 32-bit MLI.
+const KWP_ICV: [u8; 4] = [0xa6, 0x59, 0x59, 0xa6];
+
+/// AES Key Wrap (KW): wrap `key_data` under the key-encryption key `kek`.
+///
+/// # Description
+/// - The key data is split into n >= 2 semiblocks R[1..n], A starts as
+///   A6A6A6A6A6A6A6A6.
+/// - Six passes over R: B = E(K, A || R[i]), A = MSB64(B) ^ t with
+///   t = n * j + i, R[i] = LSB64(B).
+/// - The output A || R[1..n] is 8 bytes longer than the key data.
+///
+/// # Reference
+/// [RFC 3394](https://www.rfc-editor.org/rfc/rfc3394), section 2.2.1.
+pub fn aes_kw_wrap(
+    kek: &[u8], 
+    key_data: &[u8]
+) -> Result<Vec<u8>, KeyWrapError> {
+    let len = key_data.len();
+    if len < 2 * SEMIBLOCK || !len.is_multiple_of(SEMIBLOCK) {
+        return Err(KeyWrapError::InvalidKeyDataLength { len });
+    }
+
+    let aes = Aes::new(kek)?;
+    Ok(wrap_blocks(&aes, KW_IV, key_data))
+}
+
+/// AES Key Wrap (KW): unwrap and check the integrity check value.
+///
+/// Fails closed: if A does not come out as A6A6A6A6A6A6A6A6 no key data is
+/// returned at all.
+pub fn aes_kw_unwrap(
+    kek: &[u8], 
+    wrapped: &[u8]
+) -> Result<Vec<u8>, KeyWrapError> {
+    let len = wrapped.len();
+    if len < 3 * SEMIBLOCK || !len.is_multiple_of(SEMIBLOCK) {
+        return Err(KeyWrapError::InvalidWrappedLength { len });
+    }
+
+    let aes = Aes::new(kek)?;
+    let (a, key_data) = unwrap_blocks(&aes, wrapped);
+    if !ct_eq(&a, &KW_IV) {
+        return Err(KeyWrapError::IntegrityCheckFailed);
+    }
+
+    Ok(key_data)
+}
+
+/// AES Key Wrap with Padding (KWP): wrap 1 up to 2^32 - 1 bytes of key
+/// data under `kek`.
+///
+/// # Description
+/// - A starts as A65959A6 || MLI, the key data length as a 32-bit
+///   big-endian integer.
+/// - The key data is padded with zeros to a multiple of 8 bytes.
+/// - 8 padded bytes are a single AES block encryption of A || P, anything
+///   longer is wrapped as in KW with the alternative initial value.
+///
+/// # Reference
+/// [RFC 5649](https://www.rfc-editor.org/rfc/rfc5649), section 4.1.
+pub fn aes_kwp_wrap(
+    kek: &[u8], 
+    key_data: &[u8]
+) -> Result<Vec<u8>, KeyWrapError> {
+    let len = key_data.len();
+    if len == 0 || len > u32::MAX as usize {
+        return Err(KeyWrapError::InvalidKeyDataLength { len });
+    }
+
+    let aes = Aes::new(kek)?;
+
+    let mut aiv = [0u8; SEMIBLOCK];
+    aiv[..4].copy_from_slice(&KWP_ICV);
+    aiv[4..].copy_from_slice(&(len as u32).to_be_bytes());
+
+    let mut padded = key_data.to_vec();
+    padded.resize(len.next_multiple_of(SEMIBLOCK), 0x00);
+
+    if padded.len() == SEMIBLOCK {
+        let mut block = [0u8; BLOCK_SIZE];
+        block[..SEMIBLOCK].copy_from_slice(&aiv);
+        block[SEMIBLOCK..].copy_from_slice(&padded);
+        return Ok(aes.encrypt_block(&block).to_vec());
+    }
+
+    Ok(wrap_blocks(&aes, aiv, &padded))
+}
+
+/// AES Key Wrap with Padding (KWP): unwrap and check the integrity check
+/// value, the message length indicator and the zero padding.
+///
+/// Fails closed: if any of the checks fails no key data is returned, and
+/// all of them report the same `IntegrityCheckFailed`.
+pub fn aes_kwp_unwrap(
+    kek: &[u8], 
+    wrapped: &[u8]
+) -> Result<Vec<u8>, KeyWrapError> {
+    let len = wrapped.len();
+    if len < 2 * SEMIBLOCK || !len.is_multiple_of(SEMIBLOCK) {
+        return Err(KeyWrapError::InvalidWrappedLength { len });
+    }
+
+    let aes = Aes::new(kek)?;
+
+    let (a, mut padded) = if len == BLOCK_SIZE {
+        let mut block = [0u8; BLOCK_SIZE];
+        block.copy_from_slice(wrapped);
+        let block = aes.decrypt_block(&block);
+
+        let mut a = [0u8; SEMIBLOCK];
+        a.copy_from_slice(&block[..SEMIBLOCK]);
+        (a, block[SEMIBLOCK..].to_vec())
+    } else {
+        unwrap_blocks(&aes, wrapped)
+    };
+
+    // 8 * (n - 1) < MLI <= 8 * n, with the MLI bytes followed by zeros.
+    let mli = u32::from_be_bytes([a[4], a[5], a[6], a[7]]) as usize;
+    let icv_ok = ct_eq(&a[..4], &KWP_ICV);
+    let mli_ok = mli > padded.len() - SEMIBLOCK && mli <= padded.len();
+    if !icv_ok || !mli_ok {
+        return Err(KeyWrapError::IntegrityCheckFailed);
+    }
+
+    let padding = padded.split_off(mli);
+    if !ct_eq(&padding, &vec![0x00; padding.len()]) {
+        return Err(KeyWrapError::IntegrityCheckFailed);
+    }
+
+    Ok(padded)
+}
+
+// W: six passes of the wrapping process, returns A || R[1..n].
+fn wrap_blocks(aes: &Aes, iv: [u8; SEMIBLOCK], data: &[u8]) -> Vec<u8> {
+    let n = data.len() / SEMIBLOCK;
+    let mut a = iv;
+    let mut r = data.to_vec();
+
+    let mut block = [0u8; BLOCK_SIZE];
+    for j in 0..6 {
+        for i in 0..n {
+            let semiblock = &mut r[SEMIBLOCK * i..SEMIBLOCK * (i + 1)];
+            block[..SEMIBLOCK].copy_from_slice(&a);
+            block[SEMIBLOCK..].copy_from_slice(semiblock);
+            let b = aes.encrypt_block(&block);
+
+            let t = (n * j + i + 1) as u64;
+            a.copy_from_slice(&b[..SEMIBLOCK]);
+            xor_counter(&mut a, t);
+            semiblock.copy_from_slice(&b[SEMIBLOCK..]);
+        }
+    }
+
+    let mut out = a.to_vec();
+    out.extend_from_slice(&r);
+    out
+}
+
+// W^-1: undo the six passes, returns A and R[1..n] for the caller to check.
+fn unwrap_blocks(aes: &Aes, wrapped: &[u8]) -> ([u8; SEMIBLOCK], Vec<u8>) {
+    let n = wrapped.len() / SEMIBLOCK - 1;
+    let mut a = [0u8; SEMIBLOCK];
+    a.copy_from_slice(&wrapped[..SEMIBLOCK]);
+    let mut r = wrapped[SEMIBLOCK..].to_vec();
+
+    let mut block = [0u8; BLOCK_SIZE];
+    for j in (0..6).rev() {
+        for i in (0..n).rev() {
+            let semiblock = &mut r[SEMIBLOCK * i..SEMIBLOCK * (i + 1)];
+            let t = (n * j + i + 1) as u64;
+            xor_counter(&mut a, t);
+            block[..SEMIBLOCK].copy_from_slice(&a);
+            block[SEMIBLOCK..].copy_from_slice(semiblock);
+            let b = aes.decrypt_block(&block);
+
+            a.copy_from_slice(&b[..SEMIBLOCK]);
+            semiblock.copy_from_slice(&b[SEMIBLOCK..]);
+        }
+    }
+
+    (a, r)
+}
+
+// A ^= t, with t as a 64-bit big-endian integer.
+fn xor_counter(a: &mut [u8; SEMIBLOCK], t: u64) {
+    for (x, y) in a.iter_mut().zip(t.to_be_bytes()) {
+        *x ^= y;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aes::AesError;
+    use crate::sha3::{ bytes_to_hex, hex_to_bytes };
+
+    const KEK: &[u8] = b"000102030405060708090a0b0c0d0e0f\
+                         101112131415161718191a1b1c1d1e1f";
+
+    const KEY_DATA: &[u8] = b"00112233445566778899aabbccddeeff\
+                              000102030405060708090a0b0c0d0e0f";
+
+    // RFC 3394 section 4, (KEK bytes, key data bytes, wrapped key).
+    #[test]
+    fn kw_rfc3394_section_4() {
+        let vectors: [(usize, usize, &[u8]); 6] = [
+            (16, 16, b"1fa68b0a8112b447aef34bd8fb5a7b82\
+                       9d3e862371d2cfe5"),
+            (24, 16, b"96778b25ae6ca435f92b5b97c050aed2\
+                       468ab8a17ad84e5d"),
+            (32, 16, b"64e8c3f9ce0f5ba263e9777905818a2a\
+                       93c8191e7d6e8ae7"),
+            (24, 24, b"031d33264e15d33268f24ec260743edc\
+                       e1c6c7ddee725a936ba814915c6762d2"),
+            (32, 24, b"a8f9bc1612c68b3ff6e6f4fbe30e71e4\
+                       769c8b80a32cb8958cd5d17d6b254da1"),
+            (32, 32, b"28c9f404c4b810f4cbccb35cfb87f826\
+                       3f5786e2d80ed326cbc7f0e71a99f43b\
+                       fb988b9b7a02dd21"),
+        ];
+        let kek = hex_to_bytes(KEK);
+        let key_data = hex_to_bytes(KEY_DATA);
+
+        for (kek_len, data_len, expected) in vectors {
+            let kek = &kek[..kek_len];
+            let data = &key_data[..data_len];
+
+            let wrapped = aes_kw_wrap(kek, data).unwrap();
+            assert_eq!((bytes_to_hex(&wrapped)), (expected.to_vec()));
+
+            let unwrapped = aes_kw_unwrap(kek, &wrapped).unwrap();
+            assert_eq!((unwrapped), (data.to_vec()));
+        }
+    }
+
+    #[test]
+    fn kw_tampering_fails_the_integrity_check() {
+        let kek = hex_to_bytes(KEK);
+        let wrapped = aes_kw_wrap(&kek[..16], &hex_to_bytes(KEY_DATA));
+        let wrapped = wrapped.unwrap();
+        let failed = Err(KeyWrapError::IntegrityCheckFailed);
+
+        for i in [0, 7, 8, wrapped.len() - 1] {
+            let mut bad = wrapped.clone();
+            bad[i] ^= 0x01;
+            assert_eq!((aes_kw_unwrap(&kek[..16], &bad)), (failed));
+        }
+
+        assert_eq!((aes_kw_unwrap(&kek[..24], &wrapped)), (failed));
+    }
+
+    #[test]
+    fn kw_invalid_lengths_are_errors() {
+        let kek = [0u8; 16];
+
+        let expected = Err(KeyWrapError::InvalidKeyDataLength { len: 8 });
+        assert_eq!((aes_kw_wrap(&kek, &[0u8; 8])), (expected));
+        let expected = Err(KeyWrapError::InvalidKeyDataLength { len: 17 });
+        assert_eq!((aes_kw_wrap(&kek, &[0u8; 17])), (expected));
+
+        let expected = Err(KeyWrapError::InvalidWrappedLength { len: 16 });
+        assert_eq!((aes_kw_unwrap(&kek, &[0u8; 16])), (expected));
+        let expected = Err(KeyWrapError::InvalidWrappedLength { len: 25 });
+        assert_eq!((aes_kw_unwrap(&kek, &[0u8; 25])), (expected));
+
+        let expected = Err(KeyWrapError::Aes(AesError::InvalidKeyLength {
+            len: 15,
+        }));
+        assert_eq!((aes_kw_wrap(&[0u8; 15], &[0u8; 16])), (expected));
+    }
+
+    const KWP_KEK: &[u8] = b"5840df6e29b02af1ab493b705bf16ea1\
+                             ae8338f4dcc176a8";
+
+    #[test]
+    fn kwp_rfc5649_section_6_20_octets() {
+        let kek = hex_to_bytes(KWP_KEK);
+        let key_data = hex_to_bytes(b"c37b7e6492584340bed1220780894115\
+                                      5068f738");
+
+        let wrapped = aes_kwp_wrap(&kek, &key_data).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&wrapped)),
+            (b"138bdeaa9b8fa7fc61f97742e72248ee\
+               5ae6ae5360d1ae6a5f54f373fa543b6a")
+        );
+
+        let unwrapped = aes_kwp_unwrap(&kek, &wrapped).unwrap();
+        assert_eq!((unwrapped), (key_data));
+    }
+
+    #[test]
+    fn kwp_rfc5649_section_6_7_octets() {
+        let kek = hex_to_bytes(KWP_KEK);
+        let key_data = hex_to_bytes(b"466f7250617369");
+
+        let wrapped = aes_kwp_wrap(&kek, &key_data).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&wrapped)),
+            (b"afbeb0f07dfbf5419200f2ccb50bb24f")
+        );
+
+        let unwrapped = aes_kwp_unwrap(&kek, &wrapped).unwrap();
+        assert_eq!((unwrapped), (key_data));
+    }
+
+    #[test]
+    fn kwp_round_trips_every_padding_length() {
+        let kek = hex_to_bytes(KWP_KEK);
+
+        for len in 1..=33 {
+            let key_data = vec![0x5a; len];
+            let wrapped = aes_kwp_wrap(&kek, &key_data).unwrap();
+            assert_eq!((wrapped.len()), (len.next_multiple_of(8) + 8));
+
+            let unwrapped = aes_kwp_unwrap(&kek, &wrapped).unwrap();
+            assert_eq!((unwrapped), (key_data));
+        }
+    }
+
+    #[test]
+    fn kwp_tampering_fails_the_integrity_check() {
+        let kek = hex_to_bytes(KWP_KEK);
+        let failed = Err(KeyWrapError::IntegrityCheckFailed);
+
+        for key_data in [&b"\x01"[..], b"0123456789abcdef0123"] {
+            let wrapped = aes_kwp_wrap(&kek, key_data).unwrap();
+            for i in [0, 8, wrapped.len() - 1] {
+                let mut bad = wrapped.clone();
+                bad[i] ^= 0x01;
+                assert_eq!((aes_kwp_unwrap(&kek, &bad)), (failed));
+            }
+        }
+
+        // A KW output never passes as KWP, the initial values differ.
+        let wrapped = aes_kw_wrap(&kek, &[0u8; 16]).unwrap();
+        assert_eq!((aes_kwp_unwrap(&kek, &wrapped)), (failed));
+    }
+
+    #[test]
+    fn kwp_wrong_length_indicator_fails() {
+        let kek = hex_to_bytes(KWP_KEK);
+        let aes = Aes::new(&kek).unwrap();
+        let failed = Err(KeyWrapError::IntegrityCheckFailed);
+
+        // MLI of 9 in a single 8-byte semiblock, and nonzero padding.
+        let mut block = [0u8; BLOCK_SIZE];
+        block[..4].copy_from_slice(&KWP_ICV);
+        block[7] = 9;
+        let wrapped = aes.encrypt_block(&block);
+        assert_eq!((aes_kwp_unwrap(&kek, &wrapped)), (failed));
+
+        block[7] = 7;
+        block[15] = 0x01;
+        let wrapped = aes.encrypt_block(&block);
+        assert_eq!((aes_kwp_unwrap(&kek, &wrapped)), (failed));
+    }
+
+    #[test]
+    fn kwp_invalid_lengths_are_errors() {
+        let kek = [0u8; 16];
+
+        let expected = Err(KeyWrapError::InvalidKeyDataLength { len: 0 });
+        assert_eq!((aes_kwp_wrap(&kek, b"")), (expected));
+
+        let expected = Err(KeyWrapError::InvalidWrappedLength { len: 8 });
+        assert_eq!((aes_kwp_unwrap(&kek, &[0u8; 8])), (expected));
+        let expected = Err(KeyWrapError::InvalidWrappedLength { len: 20 });
+        assert_eq!((aes_kwp_unwrap(&kek, &[0u8; 20])), (expected));
+    }
+}
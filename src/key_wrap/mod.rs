@@ -0,0 +1,6 @@
+pub mod error;
+#[allow(clippy::module_inception)]
+pub mod key_wrap;
+
+pub use error::KeyWrapError;
+pub use key_wrap::{ aes_kw_unwrap, aes_kw_wrap, aes_kwp_unwrap, aes_kwp_wrap };
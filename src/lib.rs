@@ -1,3 +1,4 @@
+pub mod digest;
 pub mod sha1;
 pub mod sha256;
 pub mod hmac;
@@ -8,4 +9,23 @@ pub mod padd_pars;
 pub mod md4;
 pub mod md5;
 pub mod sha512;
-pub mod sha3;
\ No newline at end of file
+pub mod sha3;
+pub mod hkdf;
+pub mod pbkdf2;
+pub mod scrypt;
+pub mod blake2;
+pub mod argon2;
+pub mod blake3;
+pub mod aes;
+pub mod block_cipher;
+pub mod aead;
+pub mod modes;
+pub mod gcm;
+pub mod poly1305;
+pub mod chacha20;
+pub mod salsa20;
+pub mod cmac;
+pub mod key_wrap;
+pub mod ccm;
+pub mod siv;
+pub mod gcm_siv;
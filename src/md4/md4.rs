@@ -1,192 +1,159 @@
-use crate::md4::{ f, g, h };
-use crate::utils::{ rotl, z };
+use crate::digest::Digest;
+use crate::padd_pars::{
+    little_endian_padd_tail, little_endian_pars_block, BlockBuffer
+};
 
-/// MD4: Message Digestion function.
+use crate::md4::{ compress_block, IV };
+
+/// MD4 streaming context.
+///
+/// Buffers at most one partial 64-byte block; every block that fills up is
+/// parsed and compressed straight away.
+#[derive(Clone)]
+pub struct Md4 {
+    state: [u32; 4],
+    buffer: BlockBuffer<64>,
+    msg_len: u64,
+}
+
+impl Md4 {
+    pub fn new() -> Self {
+        Md4 {
+            state: IV,
+            buffer: BlockBuffer::new(),
+            msg_len: 0,
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.msg_len = self.msg_len.wrapping_add(data.len() as u64);
+
+        let state = &mut self.state;
+        self.buffer.feed(data, |bytes| process_block(state, bytes));
+    }
+
+    /// Pad the buffered tail and return the 16-byte digest.
+    ///
+    /// Each state word is written out least significant byte first.
+    pub fn finalize(mut self) -> [u8; 16] {
+        let padded = little_endian_padd_tail(self.buffer.tail(), self.msg_len);
+
+        let mut j = 0;
+        while j < padded.len() {
+            process_block(&mut self.state, &padded[j..j + 64]);
+            j += 64;
+        }
+
+        let mut bytes = [0u8; 16];
+        for i in 0..4 {
+            let word = self.state[i];
+            let j = i * 4;
+
+            bytes[j] =     (word)       as u8;
+            bytes[j + 1] = (word >> 8)  as u8;
+            bytes[j + 2] = (word >> 16) as u8;
+            bytes[j + 3] = (word >> 24) as u8;
+        }
+
+        bytes
+    }
+}
+
+impl Default for Md4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Md4 {
+    const OUTPUT_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 64;
+    type Output = [u8; 16];
+
+    fn new() -> Self { Md4::new() }
+    fn update(&mut self, data: &[u8]) { Md4::update(self, data) }
+    fn finalize(self) -> [u8; 16] { Md4::finalize(self) }
+}
+
+/// Parse and compress one 64-byte block into the state.
+fn process_block(state: &mut [u32; 4], bytes: &[u8]) {
+    let block = little_endian_pars_block(bytes);
+    compress_block(state, &block);
+}
+
+/// MD4: Pads, parses, and compresses a message into a 128-bit hash.
 ///
-/// # Parameters
-/// `m`: A vector of 512-bit message blocks, where each block is represented
-///      as an array of sixteen 32-bit words in little-endian order.
+/// # Arguments
+/// - `msg`: Message as a byte slice (`&[u8]`). 
 ///
 /// # Description
-/// - The algorithm performs three rounds of nonlinear functions, message word
-///   reordering, bit rotations, and modular additions.  
-/// - Each 512-bit message block updates the internal state `(A, B, C, D)`
-///   to produce a 128-bit digest.
+/// - **Padding:** Little-endian padding to a multiple of 512 bits.
+/// - **Parsing:** Splits the padded message into 16 × 32-bit little-endian
+///   word blocks.
+/// - **Compression:** Updates the four word state across all blocks.
+///
+/// All steps run block by block through the `Md4` streaming context.
 ///
 /// # Returns
-/// A 128-bit digested hash key represented as an array of four 32-bit words.
+/// A 16-byte array representing the 128-bit hash.
 ///
 /// # Reference
-/// Based on the MD4 RFC-1320 specification:
 /// [RFC-1320](https://datatracker.ietf.org/doc/html/rfc1320)
-fn md4(m: &[[u32; 16]]) -> [u32; 4] {
-    // Four word buffer for message digestion.
-    // Order: A, B, C, D.
-    let mut four_word_bffr: [u32; 4] = [
-        0x67452301, // 0 = A
-        0xefcdab89, // 1 = B
-        0x98badcfe, // 2 = C
-        0x10325476, // 3 = D
-    ];
-
-    // Index message order for 3 round.
-    let message_order = [
-        [
-            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15
-        ],
-        [
-            0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15
-        ],
-        [
-            0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15
-        ],
-    ];
-    
-    // Rotl order for 3 rounds.
-    let round_rotation = [
-        [
-            3, 7, 11, 19, 3, 7, 11, 19, 3, 7, 11, 19, 3, 7, 11, 19 
-        ],
-        [
-            3, 5, 9, 13, 3, 5, 9, 13, 3, 5, 9, 13, 3, 5, 9, 13
-        ],
-        [
-            3, 9, 11, 15, 3, 9, 11, 15, 3, 9, 11, 15, 3, 9, 11, 15
-        ],    
-    ];
-
-    for x in m {
-        // Buffer: (A, B, C, D) safed as lower: (a, b, c, d) instead of:
-        // (AA, BB, CC, DD).
-        let mut a = four_word_bffr[0];
-        let mut b = four_word_bffr[1];
-        let mut c = four_word_bffr[2];
-        let mut d = four_word_bffr[3];
-
-        for round in 0..3 {
-            for j in 0..16 {
-                let (func, msg, cnst, shft) = match round {
-                    0 => (
-                        // Round 1: nonlinear function F and no constant.
-                        f(b, c, d), 
-                        x[message_order[round][j]], 
-                        0x00000000, 
-                        round_rotation[round][j]),
-                    1 => (
-                        // Round 2: nonlinear function G with 0x5A827999.
-                        g(b, c, d), x[message_order[round][j]], 
-                        0x5A827999, 
-                        round_rotation[round][j]),
-                    2 => (
-                        // Round 3: nonlinear function H with 0x6ED9EBA1.
-                        h(b, c, d), 
-                        x[message_order[round][j]], 
-                        0x6ED9EBA1, 
-                        round_rotation[round][j]),
-                    _ => unreachable!()
-
-                };
-
-                let temp = a;
-                a = d;
-                d = c;
-                c = b;
-                b = rotl(z(z(z(temp, func), msg), cnst), shft);
-            }
-
-        }
-
-        // Digest message.
-        four_word_bffr[0] = z(four_word_bffr[0], a);
-        four_word_bffr[1] = z(four_word_bffr[1], b);
-        four_word_bffr[2] = z(four_word_bffr[2], c);
-        four_word_bffr[3] = z(four_word_bffr[3], d);
-
-    }
-
-    // Digested state.
-    [
-        four_word_bffr[0].swap_bytes(), 
-        four_word_bffr[1].swap_bytes(), 
-        four_word_bffr[2].swap_bytes(), 
-        four_word_bffr[3].swap_bytes()
-    ]
+pub fn md4(msg: &[u8]) -> [u8; 16] {
+    let mut hasher = Md4::new();
+    hasher.update(msg);
+    hasher.finalize()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::padd_pars::{
-        little_endian_padd, little_endian_pars
-    };
 
     #[test]
-    fn md4_compute_empty_string() {
+    fn md4_digests_empty_string() {
         let msg = b"";
-        let padded = little_endian_padd(msg); 
-        let parsed = little_endian_pars(padded);
-        let result = md4(&parsed);
-
+        let result = md4(msg);
         let expected = [
-            0x31d6cfe0, 0xd16ae931, 0xb73c59d7, 0xe0c089c0
+            0x31, 0xd6, 0xcf, 0xe0, 0xd1, 0x6a, 0xe9, 0x31,
+            0xb7, 0x3c, 0x59, 0xd7, 0xe0, 0xc0, 0x89, 0xc0,
         ];
 
         assert_eq!((result), (expected));
     }
 
     #[test]
-    fn md4_compute_a() {
-        let msg = b"a";
-        let padded = little_endian_padd(msg);
-        let parsed = little_endian_pars(padded);
-        let result = md4(&parsed);
-
-        let expected = [
-            0xbde52cb3, 0x1de33e46, 0x245e05fb, 0xdbd6fb24
-        ];
-
-        assert_eq!((result), (expected));
-    }
-
-    #[test]
-    fn md4_compute_abc() {
+    fn md4_digests_abc() {
         let msg = b"abc";
-        let padded = little_endian_padd(msg); 
-        let parsed = little_endian_pars(padded);
-        let result = md4(&parsed);
-
+        let result = md4(msg);
         let expected = [
-            0xa448017a, 0xaf21d852, 0x5fc10ae8, 0x7aa6729d
+            0xa4, 0x48, 0x01, 0x7a, 0xaf, 0x21, 0xd8, 0x52,
+            0x5f, 0xc1, 0x0a, 0xe8, 0x7a, 0xa6, 0x72, 0x9d,
         ];
 
         assert_eq!((result), (expected));
     }
 
     #[test]
-    fn md4_compute_long_abcde_string() {
-        let msg = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcde\
-                    fghijklmnopqrstuvwxyz0123456789";
-        let padded = little_endian_padd(msg);
-        let parsed = little_endian_pars(padded);
-        let result = md4(&parsed);
-
+    fn md4_digests_message_digest() {
+        let msg = b"message digest";
+        let result = md4(msg);
         let expected = [
-            0x043f8582, 0xf241db35, 0x1ce627e1, 0x53e7f0e4
+            0xd9, 0x13, 0x0a, 0x81, 0x64, 0x54, 0x9f, 0xe8,
+            0x18, 0x87, 0x48, 0x06, 0xe1, 0xc7, 0x01, 0x4b,
         ];
 
         assert_eq!((result), (expected));
     }
 
     #[test]
-    fn md4_compute_long_numbers_string() {
+    fn md4_digests_long_numbers_string() {
         let msg = b"1234567890123456789012345678901234567890\
                     1234567890123456789012345678901234567890";
-        let padded = little_endian_padd(msg);
-        let parsed = little_endian_pars(padded);
-        let result = md4(&parsed);
-
+        let result = md4(msg);
         let expected = [
-            0xe33b4ddc, 0x9c38f219, 0x9c3e7b16, 0x4fcc0536
+            0xe3, 0x3b, 0x4d, 0xdc, 0x9c, 0x38, 0xf2, 0x19,
+            0x9c, 0x3e, 0x7b, 0x16, 0x4f, 0xcc, 0x05, 0x36,
         ];
 
         assert_eq!((result), (expected));
@@ -1,4 +1,8 @@
+pub mod compression;
 pub mod functions;
+#[allow(clippy::module_inception)]
 pub mod md4;
 
-pub use functions::{ f, g, h };
\ No newline at end of file
+pub use compression::{ compress, compress_block, IV };
+pub use functions::{ f, g, h };
+pub use md4::{ md4, Md4 };
\ No newline at end of file
@@ -1,244 +1,160 @@
-use crate::md5::functions::{ f, g, h, i };
-use crate::utils::{ rotl, z };
+use crate::digest::Digest;
+use crate::padd_pars::{
+    little_endian_padd_tail, little_endian_pars_block, BlockBuffer
+};
 
-/// MD5: Message Digestion function.
+use crate::md5::{ compress_block, IV };
+
+/// MD5 streaming context.
 ///
-/// # Parameters
-/// `m`: A vector of 512-bit message blocks, where each block is represented
-///      as an array of sixteen 32-bit words in little-endian order.
+/// Buffers at most one partial 64-byte block; every block that fills up is
+/// parsed and compressed straight away.
+#[derive(Clone)]
+pub struct Md5 {
+    state: [u32; 4],
+    buffer: BlockBuffer<64>,
+    msg_len: u64,
+}
+
+impl Md5 {
+    pub fn new() -> Self {
+        Md5 {
+            state: IV,
+            buffer: BlockBuffer::new(),
+            msg_len: 0,
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.msg_len = self.msg_len.wrapping_add(data.len() as u64);
+
+        let state = &mut self.state;
+        self.buffer.feed(data, |bytes| process_block(state, bytes));
+    }
+
+    /// Pad the buffered tail and return the 16-byte digest.
+    ///
+    /// Each state word is written out least significant byte first.
+    pub fn finalize(mut self) -> [u8; 16] {
+        let padded = little_endian_padd_tail(self.buffer.tail(), self.msg_len);
+
+        let mut j = 0;
+        while j < padded.len() {
+            process_block(&mut self.state, &padded[j..j + 64]);
+            j += 64;
+        }
+
+        let mut bytes = [0u8; 16];
+        for i in 0..4 {
+            let word = self.state[i];
+            let j = i * 4;
+
+            bytes[j] =     (word)       as u8;
+            bytes[j + 1] = (word >> 8)  as u8;
+            bytes[j + 2] = (word >> 16) as u8;
+            bytes[j + 3] = (word >> 24) as u8;
+        }
+
+        bytes
+    }
+}
+
+impl Default for Md5 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Md5 {
+    const OUTPUT_SIZE: usize = 16;
+    const BLOCK_SIZE: usize = 64;
+    type Output = [u8; 16];
+
+    fn new() -> Self { Md5::new() }
+    fn update(&mut self, data: &[u8]) { Md5::update(self, data) }
+    fn finalize(self) -> [u8; 16] { Md5::finalize(self) }
+}
+
+/// Parse and compress one 64-byte block into the state.
+fn process_block(state: &mut [u32; 4], bytes: &[u8]) {
+    let block = little_endian_pars_block(bytes);
+    compress_block(state, &block);
+}
+
+/// MD5: Pads, parses, and compresses a message into a 128-bit hash.
+///
+/// # Arguments
+/// - `msg`: Message as a byte slice (`&[u8]`). 
 ///
 /// # Description
-/// - The algorithm performs four rounds of nonlinear functions, message word
-///   reordering, bit rotations, and modular additions.  
-/// - Each 512-bit message block updates the internal state `(A, B, C, D)`
-///   to produce a 128-bit digest.
+/// - **Padding:** Little-endian padding to a multiple of 512 bits.
+/// - **Parsing:** Splits the padded message into 16 × 32-bit little-endian
+///   word blocks.
+/// - **Compression:** Updates the four word state across all blocks.
+///
+/// All steps run block by block through the `Md5` streaming context.
 ///
 /// # Returns
-/// A 128-bit digested hash key represented as an array of four 32-bit words.
+/// A 16-byte array representing the 128-bit hash.
 ///
 /// # Reference
-/// Based on the MD5 RFC-1321 specification:
 /// [RFC-1321](https://www.rfc-editor.org/rfc/pdfrfc/rfc1321.txt.pdf)
-fn md5(m: &[[u32; 16]]) -> [u32; 4] {
-    // Four word buffer for message digestion.
-    // Order: A, B, C, D.
-    let mut four_word_bffr: [u32; 4] = [
-        0x67452301, // 0 = A
-        0xefcdab89, // 1 = B
-        0x98badcfe, // 2 = C
-        0x10325476, // 3 = D
-    ];
-
-    let message_order = [
-        // Round 1
-        [
-            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15
-        ],
-        // Round 2
-        [
-            1, 6, 11, 0, 5, 10, 15, 4, 9, 14, 3, 8, 13, 2, 7, 12
-        ],
-        // Round 3
-        [
-            5, 8, 11, 14, 1, 4, 7, 10, 13, 0, 3, 6, 9, 12, 15, 2
-        ],
-        // Round 4
-        [
-            0, 7, 14, 5, 12, 3, 10, 1, 8, 15, 6, 13, 4, 11, 2, 9
-        ],
-    ];
-
-    let round_rotation = [
-        // Round 1
-        [
-            7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22
-        ],
-        // Round 2
-        [
-            5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20
-        ],
-        // Round 3
-        [
-            4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23 
-        ],
-        // Round 4
-        [
-            6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21
-        ]
-    ];
-
-    let i_th_element = [
-        // Round 1
-        [
-            0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee,
-            0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
-            0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be,
-            0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821
-        ],
-        // Round 2
-        [
-            0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa,
-            0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
-            0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
-            0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a
-        ],
-        // Round 3
-        [
-            0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c,
-            0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
-            0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05,
-            0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665
-        ],
-        // Round 4
-        [
-            0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039,
-            0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
-            0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1,
-            0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391
-        ]
-    ];
-
-    for x in m {
-        let mut a = four_word_bffr[0];
-        let mut b = four_word_bffr[1];
-        let mut c = four_word_bffr[2];
-        let mut d = four_word_bffr[3];
-        for round in 0..4 {
-            for j in 0..16 {
-                let (func, msg, i_th, shft) = match round {
-                    // Round (1, 2, 3, 4): nonlinear function (F, G, H, I) 
-                    // + message block (x) + the it-h element of the table 
-                    // + the defined round rotations.
-                    0 => (
-                        f(b, c, d), 
-                        x[message_order[round][j]], 
-                        i_th_element[round][j], 
-                        round_rotation[round][j]
-                    ),
-                    1 => (
-                        g(b, c, d), 
-                        x[message_order[round][j]], 
-                        i_th_element[round][j], 
-                        round_rotation[round][j]
-                    ),
-                    2 => (
-                        h(b, c, d), 
-                        x[message_order[round][j]], 
-                        i_th_element[round][j], 
-                        round_rotation[round][j]
-                    ),
-                    3 => (
-                        i(b, c, d), 
-                        x[message_order[round][j]], 
-                        i_th_element[round][j], 
-                        round_rotation[round][j]
-                    ),
-                    _ => unreachable!(),
-                };
-
-                let temp = d;
-                d = c;
-                c = b;
-                b = z(b, rotl(z(z(z(a, func), msg), i_th), shft));
-                a = temp;
-            }
-        }
-
-        // Message digestion
-        four_word_bffr[0] = z(four_word_bffr[0], a);
-        four_word_bffr[1] = z(four_word_bffr[1], b);
-        four_word_bffr[2] = z(four_word_bffr[2], c);
-        four_word_bffr[3] = z(four_word_bffr[3], d);
-
-    }
-
-    // Digested state.
-    [
-        four_word_bffr[0].swap_bytes(), 
-        four_word_bffr[1].swap_bytes(), 
-        four_word_bffr[2].swap_bytes(), 
-        four_word_bffr[3].swap_bytes()
-    ]
+pub fn md5(msg: &[u8]) -> [u8; 16] {
+    let mut hasher = Md5::new();
+    hasher.update(msg);
+    hasher.finalize()
 }
 
 #[cfg(test)]
-mod new {
+mod test {
     use super::*;
-    use crate::padd_pars::{
-        little_endian_padd, little_endian_pars
-    };
-
 
     #[test]
-    fn md5_compute_empty_string() {
+    fn md5_digests_empty_string() {
         let msg = b"";
-        let padded = little_endian_padd(msg);
-        let parsed = little_endian_pars(padded);
-        let result = md5(&parsed);
-
+        let result = md5(msg);
         let expected = [
-            0xd41d8cd9, 0x8f00b204, 0xe9800998, 0xecf8427e
+            0xd4, 0x1d, 0x8c, 0xd9, 0x8f, 0x00, 0xb2, 0x04,
+            0xe9, 0x80, 0x09, 0x98, 0xec, 0xf8, 0x42, 0x7e,
         ];
 
-        assert_eq!((expected), (result));
+        assert_eq!((result), (expected));
     }
 
     #[test]
-    fn md5_compute_one_a() {
-        let msg = b"a";
-        let padded = little_endian_padd(msg);
-        let parsed = little_endian_pars(padded);
-        let result = md5(&parsed);
-
-        let expected = [
-            0x0cc175b9, 0xc0f1b6a8, 0x31c399e2, 0x69772661
-        ];
-
-        assert_eq!((expected), (result));
-    }
-
-    #[test]
-    fn md5_compute_abc() {
+    fn md5_digests_abc() {
         let msg = b"abc";
-        let padded = little_endian_padd(msg);
-        let parsed = little_endian_pars(padded);
-        let result = md5(&parsed);
-
+        let result = md5(msg);
         let expected = [
-            0x90015098, 0x3cd24fb0, 0xd6963f7d, 0x28e17f72
+            0x90, 0x01, 0x50, 0x98, 0x3c, 0xd2, 0x4f, 0xb0,
+            0xd6, 0x96, 0x3f, 0x7d, 0x28, 0xe1, 0x7f, 0x72,
         ];
 
-        assert_eq!((expected), (result));
+        assert_eq!((result), (expected));
     }
 
     #[test]
-    fn md5_compute_expenditure_next_second_block() {
-        let msg = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcde\
-                    fghijklmnopqrstuvwxyz0123456789";
-        let padded = little_endian_padd(msg);
-        let parsed = little_endian_pars(padded);
-        let result = md5(&parsed);
-
+    fn md5_digests_brown_fox() {
+        let msg = b"The quick brown fox jumps over the lazy dog";
+        let result = md5(msg);
         let expected = [
-            0xd174ab98, 0xd277d9f5, 0xa5611c2c, 0x9f419d9f
+            0x9e, 0x10, 0x7d, 0x9d, 0x37, 0x2b, 0xb6, 0x82,
+            0x6b, 0xd8, 0x1d, 0x35, 0x42, 0xa4, 0x19, 0xd6,
         ];
 
-        assert_eq!((expected), (result));
+        assert_eq!((result), (expected));
     }
 
     #[test]
-    fn md5_compute_expenditure_into_next_block_with_all_numbers() {
-        let msg = b"123456789012345678901234567\
-                    890123456789012345678901234\
-                    56789012345678901234567890";
-        let padded = little_endian_padd(msg);
-        let parsed = little_endian_pars(padded);
-        let result = md5(&parsed);
-
+    fn md5_digests_one_million_a() {
+        let msg = b"a".repeat(1_000_000);
+        let result = md5(&msg);
         let expected = [
-            0x57edf4a2, 0x2be3c955, 0xac49da2e, 0x2107b67a
+            0x77, 0x07, 0xd6, 0xae, 0x4e, 0x02, 0x7c, 0x70,
+            0xee, 0xa2, 0xa9, 0x35, 0xc2, 0x29, 0x6f, 0x21,
         ];
 
-        assert_eq!((expected), (result));
+        assert_eq!((result), (expected));
     }
 }
\ No newline at end of file
@@ -1,4 +1,8 @@
+pub mod compression;
 pub mod functions;
+#[allow(clippy::module_inception)]
 pub mod md5;
 
-pub use functions::{ f, g, h, i };
\ No newline at end of file
+pub use compression::{ compress, compress_block, IV };
+pub use functions::{ f, g, h, i };
+pub use md5::{ md5, Md5 };
\ No newline at end of file
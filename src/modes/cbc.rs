@@ -0,0 +1,303 @@
+use crate::block_cipher::BlockCipher;
+use crate::modes::{ pkcs7_pad, pkcs7_unpad, ready_len, ModeError, Padding };
+
+/// CBC encryption context.
+///
+/// # Description
+/// - Every plaintext block is XORed with the previous ciphertext block (the
+///   IV for the first one) before it is encrypted.
+/// - `update` returns the ciphertext of every block completed so far and
+///   keeps the rest buffered.
+/// - `finalize` pads and encrypts the last block, or with `Padding::None`
+///   checks that nothing is left over.
+///
+/// # Reference
+/// [NIST SP 800-38A](https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38a.pdf),
+/// section 6.2.
+#[derive(Clone)]
+pub struct CbcEncryptor<C: BlockCipher> {
+    cipher: C,
+    padding: Padding,
+    chain: Vec<u8>,
+    buffer: Vec<u8>,
+}
+
+impl<C: BlockCipher> CbcEncryptor<C> {
+    pub fn new(
+        cipher: C, 
+        iv: &[u8], 
+        padding: Padding
+    ) -> Result<Self, ModeError> {
+        check_iv::<C>(iv)?;
+
+        Ok(CbcEncryptor {
+            cipher,
+            padding,
+            chain: iv.to_vec(),
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Encrypt the next chunk of the plaintext.
+    pub fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        self.buffer.extend_from_slice(data);
+        let ready = self.buffer.len() - self.buffer.len() % C::BLOCK_SIZE;
+
+        let mut out: Vec<u8> = self.buffer.drain(..ready).collect();
+        for block in out.chunks_mut(C::BLOCK_SIZE) {
+            self.encrypt_chained(block);
+        }
+        out
+    }
+
+    /// Encrypt the padded last block.
+    pub fn finalize(mut self) -> Result<Vec<u8>, ModeError> {
+        let mut last = match self.padding {
+            Padding::Pkcs7 => pkcs7_pad(&self.buffer, C::BLOCK_SIZE),
+            Padding::None if self.buffer.is_empty() => return Ok(Vec::new()),
+            Padding::None => {
+                let len = self.buffer.len();
+                return Err(ModeError::IncompleteBlock { len });
+            }
+        };
+
+        self.encrypt_chained(&mut last);
+        Ok(last)
+    }
+
+    fn encrypt_chained(&mut self, block: &mut [u8]) {
+        for (byte, c) in block.iter_mut().zip(&self.chain) {
+            *byte ^= c;
+        }
+        self.cipher.encrypt_block(block);
+        self.chain.copy_from_slice(block);
+    }
+}
+
+/// CBC decryption context.
+///
+/// With PKCS#7 the last complete block is held back until `finalize`, as
+/// only then is it known to carry the padding.
+#[derive(Clone)]
+pub struct CbcDecryptor<C: BlockCipher> {
+    cipher: C,
+    padding: Padding,
+    chain: Vec<u8>,
+    buffer: Vec<u8>,
+}
+
+impl<C: BlockCipher> CbcDecryptor<C> {
+    pub fn new(
+        cipher: C, 
+        iv: &[u8], 
+        padding: Padding
+    ) -> Result<Self, ModeError> {
+        check_iv::<C>(iv)?;
+
+        Ok(CbcDecryptor {
+            cipher,
+            padding,
+            chain: iv.to_vec(),
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Decrypt the next chunk of the ciphertext.
+    pub fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        self.buffer.extend_from_slice(data);
+        let ready = ready_len(self.buffer.len(), C::BLOCK_SIZE, self.padding);
+
+        let mut out: Vec<u8> = self.buffer.drain(..ready).collect();
+        for block in out.chunks_mut(C::BLOCK_SIZE) {
+            self.decrypt_chained(block);
+        }
+        out
+    }
+
+    /// Decrypt the last block and strip its padding.
+    pub fn finalize(mut self) -> Result<Vec<u8>, ModeError> {
+        match self.padding {
+            Padding::None if self.buffer.is_empty() => Ok(Vec::new()),
+            Padding::None => {
+                let len = self.buffer.len();
+                Err(ModeError::IncompleteBlock { len })
+            }
+            Padding::Pkcs7 => {
+                if self.buffer.len() != C::BLOCK_SIZE {
+                    let len = self.buffer.len();
+                    return Err(ModeError::IncompleteBlock { len });
+                }
+
+                let mut last = std::mem::take(&mut self.buffer);
+                self.decrypt_chained(&mut last);
+                Ok(pkcs7_unpad(&last, C::BLOCK_SIZE)?.to_vec())
+            }
+        }
+    }
+
+    fn decrypt_chained(&mut self, block: &mut [u8]) {
+        let ciphertext = block.to_vec();
+        self.cipher.decrypt_block(block);
+        for (byte, c) in block.iter_mut().zip(&self.chain) {
+            *byte ^= c;
+        }
+        self.chain = ciphertext;
+    }
+}
+
+/// CBC: encrypt a complete message.
+pub fn cbc_encrypt<C: BlockCipher>(
+    cipher: &C, 
+    iv: &[u8], 
+    padding: Padding, 
+    plaintext: &[u8]
+) -> Result<Vec<u8>, ModeError> {
+    let mut encryptor = CbcEncryptor::new(cipher.clone(), iv, padding)?;
+    let mut out = encryptor.update(plaintext);
+    out.extend(encryptor.finalize()?);
+    Ok(out)
+}
+
+/// CBC: decrypt a complete message.
+pub fn cbc_decrypt<C: BlockCipher>(
+    cipher: &C, 
+    iv: &[u8], 
+    padding: Padding, 
+    ciphertext: &[u8]
+) -> Result<Vec<u8>, ModeError> {
+    let mut decryptor = CbcDecryptor::new(cipher.clone(), iv, padding)?;
+    let mut out = decryptor.update(ciphertext);
+    out.extend(decryptor.finalize()?);
+    Ok(out)
+}
+
+/// The IV (or initial counter block) has to be exactly one block.
+pub(crate) fn check_iv<C: BlockCipher>(iv: &[u8]) -> Result<(), ModeError> {
+    if iv.len() != C::BLOCK_SIZE {
+        let len = iv.len();
+        return Err(ModeError::InvalidIvLength { len, expected: C::BLOCK_SIZE });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aes::Aes;
+    use crate::modes::sp800_38a::{ IV, KEY_128, KEY_256, PLAINTEXT };
+    use crate::sha3::bytes_to_hex;
+
+    #[test]
+    fn cbc_aes128_sp800_38a_f_2_1() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        let ciphertext = cbc_encrypt(&aes, &IV, Padding::None, &PLAINTEXT)
+            .unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"7649abac8119b246cee98e9b12e9197d\
+               5086cb9b507219ee95db113a917678b2\
+               73bed6b8e3c1743b7116e69e22229516\
+               3ff1caa1681fac09120eca307586e1a7".to_vec())
+        );
+
+        let plaintext = cbc_decrypt(&aes, &IV, Padding::None, &ciphertext)
+            .unwrap();
+        assert_eq!((plaintext), (PLAINTEXT.to_vec()));
+    }
+
+    #[test]
+    fn cbc_aes256_sp800_38a_f_2_5() {
+        let aes = Aes::new(&KEY_256).unwrap();
+        let ciphertext = cbc_encrypt(&aes, &IV, Padding::None, &PLAINTEXT)
+            .unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"f58c4c04d6e5f1ba779eabfb5f7bfbd6\
+               9cfc4e967edb808d679f777bc6702c7d\
+               39f23369a9d9bacfa530e26304231461\
+               b2eb05e2c39be9fcda6c19078c6a9d1b".to_vec())
+        );
+
+        let plaintext = cbc_decrypt(&aes, &IV, Padding::None, &ciphertext)
+            .unwrap();
+        assert_eq!((plaintext), (PLAINTEXT.to_vec()));
+    }
+
+    #[test]
+    fn cbc_pkcs7_adds_a_full_block_to_aligned_input() {
+        // Cross-checked with `openssl enc -aes-128-cbc`.
+        let aes = Aes::new(&KEY_128).unwrap();
+        let ciphertext = cbc_encrypt(&aes, &IV, Padding::Pkcs7, &PLAINTEXT)
+            .unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"7649abac8119b246cee98e9b12e9197d\
+               5086cb9b507219ee95db113a917678b2\
+               73bed6b8e3c1743b7116e69e22229516\
+               3ff1caa1681fac09120eca307586e1a7\
+               8cb82807230e1321d3fae00d18cc2012".to_vec())
+        );
+
+        let msg = &PLAINTEXT[..61];
+        let ciphertext = cbc_encrypt(&aes, &IV, Padding::Pkcs7, msg).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"7649abac8119b246cee98e9b12e9197d\
+               5086cb9b507219ee95db113a917678b2\
+               73bed6b8e3c1743b7116e69e22229516\
+               4da5b7c55a0cad5b5d4166590180c363".to_vec())
+        );
+
+        let plaintext = cbc_decrypt(&aes, &IV, Padding::Pkcs7, &ciphertext)
+            .unwrap();
+        assert_eq!((plaintext), (msg.to_vec()));
+    }
+
+    #[test]
+    fn cbc_streams_in_uneven_chunks() {
+        let aes = Aes::new(&KEY_256).unwrap();
+        let expected = cbc_encrypt(&aes, &IV, Padding::Pkcs7, &PLAINTEXT)
+            .unwrap();
+
+        let padding = Padding::Pkcs7;
+        for chunk_size in [1, 7, 16, 17, 64] {
+            let mut encryptor = CbcEncryptor::new(aes.clone(), &IV, padding)
+                .unwrap();
+            let mut ciphertext = Vec::new();
+            for chunk in PLAINTEXT.chunks(chunk_size) {
+                ciphertext.extend(encryptor.update(chunk));
+            }
+            ciphertext.extend(encryptor.finalize().unwrap());
+            assert_eq!((ciphertext), (expected));
+
+            let mut decryptor = CbcDecryptor::new(aes.clone(), &IV, padding)
+                .unwrap();
+            let mut plaintext = Vec::new();
+            for chunk in expected.chunks(chunk_size) {
+                plaintext.extend(decryptor.update(chunk));
+            }
+            plaintext.extend(decryptor.finalize().unwrap());
+            assert_eq!((plaintext), (PLAINTEXT.to_vec()));
+        }
+    }
+
+    #[test]
+    fn cbc_reports_bad_padding_and_bad_iv() {
+        let aes = Aes::new(&KEY_128).unwrap();
+
+        // The unpadded plaintext ends in 0x10, a pad length of 16 that the
+        // rest of the block does not repeat.
+        let ciphertext = cbc_encrypt(&aes, &IV, Padding::None, &PLAINTEXT)
+            .unwrap();
+        assert_eq!(
+            (cbc_decrypt(&aes, &IV, Padding::Pkcs7, &ciphertext)), 
+            (Err(ModeError::InvalidPadding))
+        );
+
+        assert_eq!(
+            (cbc_encrypt(&aes, &IV[..12], Padding::Pkcs7, &PLAINTEXT).err()), 
+            (Some(ModeError::InvalidIvLength { len: 12, expected: 16 }))
+        );
+    }
+}
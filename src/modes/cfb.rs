@@ -0,0 +1,171 @@
+use crate::block_cipher::BlockCipher;
+use crate::modes::{ check_iv, ModeError };
+
+// Shared state of CFB encryption and decryption: the feedback register
+// holds the previous ciphertext block, `keystream` its encryption.
+#[derive(Clone)]
+struct CfbState<C: BlockCipher> {
+    cipher: C,
+    register: Vec<u8>,
+    keystream: Vec<u8>,
+    pos: usize,
+}
+
+impl<C: BlockCipher> CfbState<C> {
+    fn new(cipher: C, iv: &[u8]) -> Result<Self, ModeError> {
+        check_iv::<C>(iv)?;
+
+        Ok(CfbState {
+            cipher,
+            register: iv.to_vec(),
+            keystream: vec![0u8; C::BLOCK_SIZE],
+            pos: C::BLOCK_SIZE,
+        })
+    }
+
+    // XOR `data` with the keystream. The ciphertext byte (the output when
+    // encrypting, the input when decrypting) is fed back into the register.
+    fn process(&mut self, data: &[u8], encrypt: bool) -> Vec<u8> {
+        let mut out = Vec::with_capacity(data.len());
+        for &byte in data {
+            if self.pos == C::BLOCK_SIZE {
+                self.keystream.copy_from_slice(&self.register);
+                self.cipher.encrypt_block(&mut self.keystream);
+                self.pos = 0;
+            }
+
+            let result = byte ^ self.keystream[self.pos];
+            self.register[self.pos] = if encrypt { result } else { byte };
+            out.push(result);
+            self.pos += 1;
+        }
+        out
+    }
+}
+
+/// CFB encryption context with full-block feedback (CFB128 for AES).
+///
+/// # Description
+/// - The keystream block is the encryption of the previous ciphertext
+///   block (the IV for the first one).
+/// - No padding: `update` encrypts every byte right away, a partial last
+///   block simply uses part of the keystream.
+///
+/// # Reference
+/// [NIST SP 800-38A](https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38a.pdf),
+/// section 6.3.
+#[derive(Clone)]
+pub struct CfbEncryptor<C: BlockCipher> {
+    state: CfbState<C>,
+}
+
+impl<C: BlockCipher> CfbEncryptor<C> {
+    pub fn new(cipher: C, iv: &[u8]) -> Result<Self, ModeError> {
+        Ok(CfbEncryptor {
+            state: CfbState::new(cipher, iv)?,
+        })
+    }
+
+    /// Encrypt the next chunk of the plaintext.
+    pub fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        self.state.process(data, true)
+    }
+}
+
+/// CFB decryption context, the counterpart of `CfbEncryptor`.
+#[derive(Clone)]
+pub struct CfbDecryptor<C: BlockCipher> {
+    state: CfbState<C>,
+}
+
+impl<C: BlockCipher> CfbDecryptor<C> {
+    pub fn new(cipher: C, iv: &[u8]) -> Result<Self, ModeError> {
+        Ok(CfbDecryptor {
+            state: CfbState::new(cipher, iv)?,
+        })
+    }
+
+    /// Decrypt the next chunk of the ciphertext.
+    pub fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        self.state.process(data, false)
+    }
+}
+
+/// CFB: encrypt a complete message.
+pub fn cfb_encrypt<C: BlockCipher>(
+    cipher: &C, 
+    iv: &[u8], 
+    plaintext: &[u8]
+) -> Result<Vec<u8>, ModeError> {
+    Ok(CfbEncryptor::new(cipher.clone(), iv)?.update(plaintext))
+}
+
+/// CFB: decrypt a complete message.
+pub fn cfb_decrypt<C: BlockCipher>(
+    cipher: &C, 
+    iv: &[u8], 
+    ciphertext: &[u8]
+) -> Result<Vec<u8>, ModeError> {
+    Ok(CfbDecryptor::new(cipher.clone(), iv)?.update(ciphertext))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aes::Aes;
+    use crate::modes::sp800_38a::{ IV, KEY_128, KEY_256, PLAINTEXT };
+    use crate::sha3::bytes_to_hex;
+
+    #[test]
+    fn cfb128_aes128_sp800_38a_f_3_13() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        let ciphertext = cfb_encrypt(&aes, &IV, &PLAINTEXT).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"3b3fd92eb72dad20333449f8e83cfb4a\
+               c8a64537a0b3a93fcde3cdad9f1ce58b\
+               26751f67a3cbb140b1808cf187a4f4df\
+               c04b05357c5d1c0eeac4c66f9ff7f2e6".to_vec())
+        );
+
+        let plaintext = cfb_decrypt(&aes, &IV, &ciphertext).unwrap();
+        assert_eq!((plaintext), (PLAINTEXT.to_vec()));
+    }
+
+    #[test]
+    fn cfb128_aes256_sp800_38a_f_3_17() {
+        let aes = Aes::new(&KEY_256).unwrap();
+        let ciphertext = cfb_encrypt(&aes, &IV, &PLAINTEXT).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"dc7e84bfda79164b7ecd8486985d3860\
+               39ffed143b28b1c832113c6331e5407b\
+               df10132415e54b92a13ed0a8267ae2f9\
+               75a385741ab9cef82031623d55b1e471".to_vec())
+        );
+
+        let plaintext = cfb_decrypt(&aes, &IV, &ciphertext).unwrap();
+        assert_eq!((plaintext), (PLAINTEXT.to_vec()));
+    }
+
+    #[test]
+    fn cfb_streams_in_uneven_chunks() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        let expected = cfb_encrypt(&aes, &IV, &PLAINTEXT[..61]).unwrap();
+
+        for chunk_size in [1, 7, 16, 17, 61] {
+            let mut encryptor = CfbEncryptor::new(aes.clone(), &IV).unwrap();
+            let mut decryptor = CfbDecryptor::new(aes.clone(), &IV).unwrap();
+            let mut ciphertext = Vec::new();
+            let mut plaintext = Vec::new();
+            for chunk in PLAINTEXT[..61].chunks(chunk_size) {
+                let encrypted = encryptor.update(chunk);
+                plaintext.extend(decryptor.update(&encrypted));
+                ciphertext.extend(encrypted);
+            }
+
+            assert_eq!((ciphertext), (expected));
+            assert_eq!((plaintext), (PLAINTEXT[..61].to_vec()));
+        }
+    }
+}
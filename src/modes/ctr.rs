@@ -0,0 +1,255 @@
+use crate::block_cipher::BlockCipher;
+use crate::modes::{ check_iv, ModeError };
+
+/// CTR context, the same for encryption and decryption.
+///
+/// # Description
+/// - The keystream is the encryption of successive counter blocks.
+/// - Only the last `counter_size` bytes of the block are a big-endian
+///   counter, they wrap around on their own without carrying into the
+///   nonce part in front (e.g. 4 for the 32-bit counter of GCM).
+/// - Once all 2^(8 * counter_size) counter values are used up, `update`
+///   returns `CounterOverflow` instead of repeating the keystream.
+/// - No padding: a partial last block simply uses part of the keystream.
+///
+/// # Reference
+/// [NIST SP 800-38A](https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38a.pdf),
+/// section 6.5 and appendix B.1.
+#[derive(Clone)]
+pub struct Ctr<C: BlockCipher> {
+    cipher: C,
+    counter_block: Vec<u8>,
+    counter_size: usize,
+    keystream: Vec<u8>,
+    pos: usize,
+    // Counter values left, `None` once the counter is too big to run out.
+    blocks_left: Option<u128>,
+}
+
+impl<C: BlockCipher> Ctr<C> {
+    /// Start at `initial_counter_block`, a full block whose last
+    /// `counter_size` bytes (1 up to `C::BLOCK_SIZE`) are the counter.
+    pub fn new(
+        cipher: C, 
+        initial_counter_block: &[u8], 
+        counter_size: usize
+    ) -> Result<Self, ModeError> {
+        check_iv::<C>(initial_counter_block)?;
+        if counter_size == 0 || counter_size > C::BLOCK_SIZE {
+            return Err(ModeError::InvalidCounterSize {
+                size: counter_size,
+                max: C::BLOCK_SIZE,
+            });
+        }
+
+        // 2^(8 * counter_size) only fits into a u128 below 16 bytes.
+        let blocks_left = u32::try_from(8 * counter_size)
+            .ok()
+            .and_then(|bits| 1u128.checked_shl(bits));
+
+        Ok(Ctr {
+            cipher,
+            counter_block: initial_counter_block.to_vec(),
+            counter_size,
+            keystream: vec![0u8; C::BLOCK_SIZE],
+            pos: C::BLOCK_SIZE,
+            blocks_left,
+        })
+    }
+
+    /// Encrypt or decrypt the next chunk.
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, ModeError> {
+        // Check the counter space up front, nothing is processed if the
+        // chunk does not fit.
+        if let Some(blocks_left) = self.blocks_left {
+            let buffered = (C::BLOCK_SIZE - self.pos) as u128;
+            let needed = (data.len() as u128).saturating_sub(buffered);
+            let blocks = needed.div_ceil(C::BLOCK_SIZE as u128);
+            if blocks > blocks_left {
+                return Err(ModeError::CounterOverflow);
+            }
+        }
+
+        let mut out = Vec::with_capacity(data.len());
+        for &byte in data {
+            if self.pos == C::BLOCK_SIZE {
+                self.next_keystream_block();
+            }
+
+            out.push(byte ^ self.keystream[self.pos]);
+            self.pos += 1;
+        }
+        Ok(out)
+    }
+
+    fn next_keystream_block(&mut self) {
+        self.keystream.copy_from_slice(&self.counter_block);
+        self.cipher.encrypt_block(&mut self.keystream);
+        self.pos = 0;
+
+        if let Some(blocks_left) = self.blocks_left.as_mut() {
+            *blocks_left -= 1;
+        }
+
+        // Increment the last `counter_size` bytes modulo 2^(8 * size).
+        let start = C::BLOCK_SIZE - self.counter_size;
+        for i in (start..C::BLOCK_SIZE).rev() {
+            self.counter_block[i] = self.counter_block[i].wrapping_add(1);
+            if self.counter_block[i] != 0 {
+                break;
+            }
+        }
+    }
+}
+
+/// CTR: encrypt or decrypt a complete message.
+pub fn ctr<C: BlockCipher>(
+    cipher: &C, 
+    initial_counter_block: &[u8], 
+    counter_size: usize, 
+    data: &[u8]
+) -> Result<Vec<u8>, ModeError> {
+    let icb = initial_counter_block;
+    let mut context = Ctr::new(cipher.clone(), icb, counter_size)?;
+    context.update(data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aes::Aes;
+    use crate::modes::sp800_38a::{ COUNTER, KEY_128, KEY_256, PLAINTEXT };
+    use crate::sha3::bytes_to_hex;
+
+    #[test]
+    fn ctr_aes128_sp800_38a_f_5_1() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        let ciphertext = ctr(&aes, &COUNTER, 16, &PLAINTEXT).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"874d6191b620e3261bef6864990db6ce\
+               9806f66b7970fdff8617187bb9fffdff\
+               5ae4df3edbd5d35e5b4f09020db03eab\
+               1e031dda2fbe03d1792170a0f3009cee".to_vec())
+        );
+
+        let plaintext = ctr(&aes, &COUNTER, 16, &ciphertext).unwrap();
+        assert_eq!((plaintext), (PLAINTEXT.to_vec()));
+    }
+
+    #[test]
+    fn ctr_aes256_sp800_38a_f_5_5() {
+        let aes = Aes::new(&KEY_256).unwrap();
+        let ciphertext = ctr(&aes, &COUNTER, 16, &PLAINTEXT).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"601ec313775789a5b7a7f504bbf3d228\
+               f443e3ca4d62b59aca84e990cacaf5c5\
+               2b0930daa23de94ce87017ba2d84988d\
+               dfc9c58db67aada613c2dd08457941a6".to_vec())
+        );
+    }
+
+    #[test]
+    fn ctr_counter_wraps_without_touching_the_nonce() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        let mut first = [0xaa; 16];
+        first[12..].fill(0xff);
+
+        // A 32-bit counter wraps to 00000000, a 128-bit one carries on
+        // into the nonce bytes.
+        let mut second_32 = first;
+        let mut second_128 = first;
+        for i in 12..16 {
+            second_32[i] = 0x00;
+            second_128[i] = 0x00;
+        }
+        second_128[11] = 0xab;
+
+        let keystream = ctr(&aes, &first, 4, &[0u8; 32]).unwrap();
+        assert_eq!((keystream[..16]), (aes.encrypt_block(&first)));
+        assert_eq!((keystream[16..]), (aes.encrypt_block(&second_32)));
+
+        let keystream = ctr(&aes, &first, 16, &[0u8; 32]).unwrap();
+        assert_eq!((keystream[16..]), (aes.encrypt_block(&second_128)));
+    }
+
+    #[test]
+    fn ctr_refuses_to_reuse_counter_values() {
+        // An 8-bit counter allows 256 blocks.
+        let aes = Aes::new(&KEY_128).unwrap();
+        let mut context = Ctr::new(aes.clone(), &COUNTER, 1).unwrap();
+        assert!(context.update(&[0u8; 256 * 16 - 1]).is_ok());
+        assert!(context.update(&[0u8; 1]).is_ok());
+        assert_eq!(
+            (context.update(&[0u8; 1])), 
+            (Err(ModeError::CounterOverflow))
+        );
+
+        assert_eq!(
+            (Ctr::new(aes.clone(), &COUNTER, 0).err()), 
+            (Some(ModeError::InvalidCounterSize { size: 0, max: 16 }))
+        );
+        assert_eq!(
+            (Ctr::new(aes, &COUNTER, 17).err()), 
+            (Some(ModeError::InvalidCounterSize { size: 17, max: 16 }))
+        );
+    }
+
+    // 32-byte block cipher to check the counter bound beyond 16 bytes.
+    #[derive(Clone)]
+    struct Xor256;
+
+    impl BlockCipher for Xor256 {
+        const BLOCK_SIZE: usize = 32;
+
+        fn encrypt_block(&self, block: &mut [u8]) {
+            for byte in block.iter_mut() {
+                *byte ^= 0x5c;
+            }
+        }
+
+        fn decrypt_block(&self, block: &mut [u8]) {
+            self.encrypt_block(block);
+        }
+    }
+
+    #[test]
+    fn ctr_counter_size_follows_the_block_size() {
+        let icb = [0u8; 32];
+        for counter_size in [1, 15, 16, 17, 32] {
+            assert!(Ctr::new(Xor256, &icb, counter_size).is_ok());
+        }
+        assert_eq!(
+            (Ctr::new(Xor256, &icb, 33).err()),
+            (Some(ModeError::InvalidCounterSize { size: 33, max: 32 }))
+        );
+
+        // Only an 8-bit counter can run out here: 256 blocks of 32 bytes.
+        let mut context = Ctr::new(Xor256, &icb, 1).unwrap();
+        assert!(context.update(&[0u8; 256 * 32]).is_ok());
+        assert_eq!(
+            (context.update(&[0u8; 1])),
+            (Err(ModeError::CounterOverflow))
+        );
+
+        let mut context = Ctr::new(Xor256, &icb, 17).unwrap();
+        assert!(context.update(&[0u8; 300 * 32]).is_ok());
+    }
+
+    #[test]
+    fn ctr_streams_in_uneven_chunks() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        let expected = ctr(&aes, &COUNTER, 4, &PLAINTEXT[..61]).unwrap();
+
+        for chunk_size in [1, 7, 16, 17, 61] {
+            let mut context = Ctr::new(aes.clone(), &COUNTER, 4).unwrap();
+            let mut ciphertext = Vec::new();
+            for chunk in PLAINTEXT[..61].chunks(chunk_size) {
+                ciphertext.extend(context.update(chunk).unwrap());
+            }
+
+            assert_eq!((ciphertext), (expected));
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use crate::block_cipher::BlockCipher;
+use crate::modes::{ pkcs7_pad, pkcs7_unpad, ModeError, Padding };
+
+/// ECB encryption context: every block is encrypted on its own.
+///
+/// # Description
+/// - `update` returns the ciphertext of every block completed so far and
+///   keeps the rest buffered.
+/// - `finalize` pads and encrypts the last block, or with `Padding::None`
+///   checks that nothing is left over.
+/// - Equal plaintext blocks give equal ciphertext blocks, ECB is only fit
+///   for single blocks or as a building block.
+///
+/// # Reference
+/// [NIST SP 800-38A](https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38a.pdf),
+/// section 6.1.
+#[derive(Clone)]
+pub struct EcbEncryptor<C: BlockCipher> {
+    cipher: C,
+    padding: Padding,
+    buffer: Vec<u8>,
+}
+
+impl<C: BlockCipher> EcbEncryptor<C> {
+    pub fn new(cipher: C, padding: Padding) -> Self {
+        EcbEncryptor {
+            cipher,
+            padding,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Encrypt the next chunk of the plaintext.
+    pub fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        self.buffer.extend_from_slice(data);
+        let ready = self.buffer.len() - self.buffer.len() % C::BLOCK_SIZE;
+
+        let mut out: Vec<u8> = self.buffer.drain(..ready).collect();
+        for block in out.chunks_mut(C::BLOCK_SIZE) {
+            self.cipher.encrypt_block(block);
+        }
+        out
+    }
+
+    /// Encrypt the padded last block.
+    pub fn finalize(self) -> Result<Vec<u8>, ModeError> {
+        let mut last = match self.padding {
+            Padding::Pkcs7 => pkcs7_pad(&self.buffer, C::BLOCK_SIZE),
+            Padding::None if self.buffer.is_empty() => return Ok(Vec::new()),
+            Padding::None => {
+                let len = self.buffer.len();
+                return Err(ModeError::IncompleteBlock { len });
+            }
+        };
+
+        self.cipher.encrypt_block(&mut last);
+        Ok(last)
+    }
+}
+
+/// ECB decryption context.
+///
+/// With PKCS#7 the last complete block is held back until `finalize`, as
+/// only then is it known to carry the padding.
+#[derive(Clone)]
+pub struct EcbDecryptor<C: BlockCipher> {
+    cipher: C,
+    padding: Padding,
+    buffer: Vec<u8>,
+}
+
+impl<C: BlockCipher> EcbDecryptor<C> {
+    pub fn new(cipher: C, padding: Padding) -> Self {
+        EcbDecryptor {
+            cipher,
+            padding,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Decrypt the next chunk of the ciphertext.
+    pub fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        self.buffer.extend_from_slice(data);
+        let ready = ready_len(self.buffer.len(), C::BLOCK_SIZE, self.padding);
+
+        let mut out: Vec<u8> = self.buffer.drain(..ready).collect();
+        for block in out.chunks_mut(C::BLOCK_SIZE) {
+            self.cipher.decrypt_block(block);
+        }
+        out
+    }
+
+    /// Decrypt the last block and strip its padding.
+    pub fn finalize(mut self) -> Result<Vec<u8>, ModeError> {
+        match self.padding {
+            Padding::None if self.buffer.is_empty() => Ok(Vec::new()),
+            Padding::None => {
+                let len = self.buffer.len();
+                Err(ModeError::IncompleteBlock { len })
+            }
+            Padding::Pkcs7 => {
+                if self.buffer.len() != C::BLOCK_SIZE {
+                    let len = self.buffer.len();
+                    return Err(ModeError::IncompleteBlock { len });
+                }
+
+                self.cipher.decrypt_block(&mut self.buffer);
+                Ok(pkcs7_unpad(&self.buffer, C::BLOCK_SIZE)?.to_vec())
+            }
+        }
+    }
+}
+
+/// ECB: encrypt a complete message.
+pub fn ecb_encrypt<C: BlockCipher>(
+    cipher: &C, 
+    padding: Padding, 
+    plaintext: &[u8]
+) -> Result<Vec<u8>, ModeError> {
+    let mut encryptor = EcbEncryptor::new(cipher.clone(), padding);
+    let mut out = encryptor.update(plaintext);
+    out.extend(encryptor.finalize()?);
+    Ok(out)
+}
+
+/// ECB: decrypt a complete message.
+pub fn ecb_decrypt<C: BlockCipher>(
+    cipher: &C, 
+    padding: Padding, 
+    ciphertext: &[u8]
+) -> Result<Vec<u8>, ModeError> {
+    let mut decryptor = EcbDecryptor::new(cipher.clone(), padding);
+    let mut out = decryptor.update(ciphertext);
+    out.extend(decryptor.finalize()?);
+    Ok(out)
+}
+
+/// Bytes of buffered ciphertext that can be decrypted right away: all
+/// complete blocks, except that PKCS#7 keeps the last one for `finalize`.
+pub(crate) fn ready_len(
+    buffered: usize, 
+    block_size: usize, 
+    padding: Padding
+) -> usize {
+    let complete = buffered - buffered % block_size;
+    if padding == Padding::Pkcs7 && complete == buffered && complete > 0 {
+        complete - block_size
+    } else {
+        complete
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aes::Aes;
+    use crate::modes::sp800_38a::{ KEY_128, KEY_256, PLAINTEXT };
+    use crate::sha3::bytes_to_hex;
+
+    #[test]
+    fn ecb_aes128_sp800_38a_f_1_1() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        let ciphertext = ecb_encrypt(&aes, Padding::None, &PLAINTEXT).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"3ad77bb40d7a3660a89ecaf32466ef97\
+               f5d3d58503b9699de785895a96fdbaaf\
+               43b1cd7f598ece23881b00e3ed030688\
+               7b0c785e27e8ad3f8223207104725dd4".to_vec())
+        );
+
+        let plaintext = ecb_decrypt(&aes, Padding::None, &ciphertext).unwrap();
+        assert_eq!((plaintext), (PLAINTEXT.to_vec()));
+    }
+
+    #[test]
+    fn ecb_aes256_sp800_38a_f_1_5() {
+        let aes = Aes::new(&KEY_256).unwrap();
+        let ciphertext = ecb_encrypt(&aes, Padding::None, &PLAINTEXT).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"f3eed1bdb5d2a03c064b5a7e3db181f8\
+               591ccb10d410ed26dc5ba74a31362870\
+               b6ed21b99ca6f4f9f153e7b1beafed1d\
+               23304b7a39f9f3ff067d8d8f9e24ecc7".to_vec())
+        );
+
+        let plaintext = ecb_decrypt(&aes, Padding::None, &ciphertext).unwrap();
+        assert_eq!((plaintext), (PLAINTEXT.to_vec()));
+    }
+
+    #[test]
+    fn ecb_pkcs7_pads_the_last_block() {
+        // Cross-checked with `openssl enc -aes-128-ecb`.
+        let aes = Aes::new(&KEY_128).unwrap();
+        let ciphertext = ecb_encrypt(&aes, Padding::Pkcs7, &PLAINTEXT[..61])
+            .unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"3ad77bb40d7a3660a89ecaf32466ef97\
+               f5d3d58503b9699de785895a96fdbaaf\
+               43b1cd7f598ece23881b00e3ed030688\
+               59ed056dea98a52f52dfac14a67a6e8d".to_vec())
+        );
+
+        let plaintext = ecb_decrypt(&aes, Padding::Pkcs7, &ciphertext).unwrap();
+        assert_eq!((plaintext), (PLAINTEXT[..61].to_vec()));
+    }
+
+    #[test]
+    fn ecb_streams_in_uneven_chunks() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        let expected = ecb_encrypt(&aes, Padding::Pkcs7, &PLAINTEXT).unwrap();
+
+        for chunk_size in [1, 7, 16, 17, 64] {
+            let mut encryptor = EcbEncryptor::new(aes.clone(), Padding::Pkcs7);
+            let mut ciphertext = Vec::new();
+            for chunk in PLAINTEXT.chunks(chunk_size) {
+                ciphertext.extend(encryptor.update(chunk));
+            }
+            ciphertext.extend(encryptor.finalize().unwrap());
+            assert_eq!((ciphertext), (expected));
+
+            let mut decryptor = EcbDecryptor::new(aes.clone(), Padding::Pkcs7);
+            let mut plaintext = Vec::new();
+            for chunk in expected.chunks(chunk_size) {
+                plaintext.extend(decryptor.update(chunk));
+            }
+            plaintext.extend(decryptor.finalize().unwrap());
+            assert_eq!((plaintext), (PLAINTEXT.to_vec()));
+        }
+    }
+
+    #[test]
+    fn ecb_rejects_incomplete_blocks() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        assert_eq!(
+            (ecb_encrypt(&aes, Padding::None, &PLAINTEXT[..20])), 
+            (Err(ModeError::IncompleteBlock { len: 4 }))
+        );
+        assert_eq!(
+            (ecb_decrypt(&aes, Padding::Pkcs7, &[])), 
+            (Err(ModeError::IncompleteBlock { len: 0 }))
+        );
+    }
+}
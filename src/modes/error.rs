@@ -0,0 +1,40 @@
+use std::fmt;
+
+/// Errors of the block cipher modes of operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeError {
+    /// The IV or initial counter block must be one block long.
+    InvalidIvLength { len: usize, expected: usize },
+    /// Unpadded input left an incomplete last block of `len` bytes.
+    IncompleteBlock { len: usize },
+    /// The last block does not end in valid PKCS#7 padding.
+    InvalidPadding,
+    /// The CTR counter must be 1 byte up to the block size.
+    InvalidCounterSize { size: usize, max: usize },
+    /// The counter would wrap around and repeat a keystream block.
+    CounterOverflow,
+}
+
+impl fmt::Display for ModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModeError::InvalidIvLength { len, expected } => {
+                write!(f, "IV length {len} is not the block size {expected}")
+            }
+            ModeError::IncompleteBlock { len } => {
+                write!(f, "input ends in an incomplete block of {len} bytes")
+            }
+            ModeError::InvalidPadding => {
+                write!(f, "invalid PKCS#7 padding")
+            }
+            ModeError::InvalidCounterSize { size, max } => {
+                write!(f, "CTR counter size {size} is outside 1 to {max}")
+            }
+            ModeError::CounterOverflow => {
+                write!(f, "CTR counter space is exhausted")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ModeError {}
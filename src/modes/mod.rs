@@ -0,0 +1,19 @@
+pub mod error;
+pub mod padding;
+pub mod ecb;
+pub mod cbc;
+pub mod cfb;
+pub mod ofb;
+pub mod ctr;
+#[cfg(test)]
+pub(crate) mod sp800_38a;
+
+pub use error::ModeError;
+pub use padding::{ pkcs7_pad, pkcs7_unpad, Padding };
+pub use ecb::{ ecb_decrypt, ecb_encrypt, EcbDecryptor, EcbEncryptor };
+pub(crate) use ecb::ready_len;
+pub use cbc::{ cbc_decrypt, cbc_encrypt, CbcDecryptor, CbcEncryptor };
+pub(crate) use cbc::check_iv;
+pub use cfb::{ cfb_decrypt, cfb_encrypt, CfbDecryptor, CfbEncryptor };
+pub use ofb::{ ofb, Ofb };
+pub use ctr::{ ctr, Ctr };
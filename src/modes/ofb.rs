@@ -0,0 +1,109 @@
+use crate::block_cipher::BlockCipher;
+use crate::modes::{ check_iv, ModeError };
+
+/// OFB context, the same for encryption and decryption.
+///
+/// # Description
+/// - The keystream is the IV encrypted over and over, independent of the
+///   message.
+/// - No padding: `update` XORs every byte right away, a partial last block
+///   simply uses part of the keystream.
+///
+/// # Reference
+/// [NIST SP 800-38A](https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38a.pdf),
+/// section 6.4.
+#[derive(Clone)]
+pub struct Ofb<C: BlockCipher> {
+    cipher: C,
+    keystream: Vec<u8>,
+    pos: usize,
+}
+
+impl<C: BlockCipher> Ofb<C> {
+    pub fn new(cipher: C, iv: &[u8]) -> Result<Self, ModeError> {
+        check_iv::<C>(iv)?;
+
+        Ok(Ofb {
+            cipher,
+            keystream: iv.to_vec(),
+            pos: C::BLOCK_SIZE,
+        })
+    }
+
+    /// Encrypt or decrypt the next chunk.
+    pub fn update(&mut self, data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(data.len());
+        for &byte in data {
+            if self.pos == C::BLOCK_SIZE {
+                self.cipher.encrypt_block(&mut self.keystream);
+                self.pos = 0;
+            }
+
+            out.push(byte ^ self.keystream[self.pos]);
+            self.pos += 1;
+        }
+        out
+    }
+}
+
+/// OFB: encrypt or decrypt a complete message.
+pub fn ofb<C: BlockCipher>(
+    cipher: &C, 
+    iv: &[u8], 
+    data: &[u8]
+) -> Result<Vec<u8>, ModeError> {
+    Ok(Ofb::new(cipher.clone(), iv)?.update(data))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aes::Aes;
+    use crate::modes::sp800_38a::{ IV, KEY_128, KEY_256, PLAINTEXT };
+    use crate::sha3::bytes_to_hex;
+
+    #[test]
+    fn ofb_aes128_sp800_38a_f_4_1() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        let ciphertext = ofb(&aes, &IV, &PLAINTEXT).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"3b3fd92eb72dad20333449f8e83cfb4a\
+               7789508d16918f03f53c52dac54ed825\
+               9740051e9c5fecf64344f7a82260edcc\
+               304c6528f659c77866a510d9c1d6ae5e".to_vec())
+        );
+
+        let plaintext = ofb(&aes, &IV, &ciphertext).unwrap();
+        assert_eq!((plaintext), (PLAINTEXT.to_vec()));
+    }
+
+    #[test]
+    fn ofb_aes256_sp800_38a_f_4_5() {
+        let aes = Aes::new(&KEY_256).unwrap();
+        let ciphertext = ofb(&aes, &IV, &PLAINTEXT).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&ciphertext)),
+            (b"dc7e84bfda79164b7ecd8486985d3860\
+               4febdc6740d20b3ac88f6ad82a4fb08d\
+               71ab47a086e86eedf39d1c5bba97c408\
+               0126141d67f37be8538f5a8be740e484".to_vec())
+        );
+    }
+
+    #[test]
+    fn ofb_streams_in_uneven_chunks() {
+        let aes = Aes::new(&KEY_128).unwrap();
+        let expected = ofb(&aes, &IV, &PLAINTEXT[..61]).unwrap();
+
+        for chunk_size in [1, 7, 16, 17, 61] {
+            let mut context = Ofb::new(aes.clone(), &IV).unwrap();
+            let mut ciphertext = Vec::new();
+            for chunk in PLAINTEXT[..61].chunks(chunk_size) {
+                ciphertext.extend(context.update(chunk));
+            }
+
+            assert_eq!((ciphertext), (expected));
+        }
+    }
+}
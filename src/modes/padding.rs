@@ -0,0 +1,74 @@
+use crate::modes::ModeError;
+
+/// Padding of the last block for ECB and CBC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// PKCS#7 (RFC 5652 section 6.3): always 1 to `block_size` bytes, each
+    /// holding the padding length.
+    Pkcs7,
+    /// No padding, the input has to be a multiple of the block size.
+    None,
+}
+
+/// PKCS#7: pad an incomplete (possibly empty) last block to a full one.
+pub fn pkcs7_pad(tail: &[u8], block_size: usize) -> Vec<u8> {
+    let pad_len = block_size - tail.len() % block_size;
+
+    let mut padded = tail.to_vec();
+    for _ in 0..pad_len {
+        padded.push(pad_len as u8);
+    }
+    padded
+}
+
+/// PKCS#7: strip the padding off the decrypted last block.
+///
+/// Every byte of the block is inspected whatever the padding length, so
+/// the time taken does not tell which check failed.
+pub fn pkcs7_unpad(
+    block: &[u8], 
+    block_size: usize
+) -> Result<&[u8], ModeError> {
+    if block.len() != block_size {
+        return Err(ModeError::IncompleteBlock { len: block.len() });
+    }
+
+    let pad_len = block[block_size - 1] as usize;
+    let mut bad = (pad_len == 0) as u8 | (pad_len > block_size) as u8;
+    for (i, &byte) in block.iter().enumerate() {
+        // Only the last `pad_len` bytes have to equal `pad_len`.
+        let in_padding = (block_size - i <= pad_len) as u8;
+        bad |= in_padding & (byte != pad_len as u8) as u8;
+    }
+
+    if bad != 0 {
+        return Err(ModeError::InvalidPadding);
+    }
+
+    Ok(&block[..block_size - pad_len])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pkcs7_pads_a_full_block_onto_aligned_input() {
+        assert_eq!((pkcs7_pad(b"", 4)), (vec![4, 4, 4, 4]));
+        assert_eq!((pkcs7_pad(b"abc", 4)), (b"abc\x01".to_vec()));
+        assert_eq!((pkcs7_unpad(b"abc\x01", 4)), (Ok(&b"abc"[..])));
+        assert_eq!((pkcs7_unpad(&[4, 4, 4, 4], 4)), (Ok(&b""[..])));
+    }
+
+    #[test]
+    fn pkcs7_rejects_bad_padding() {
+        let bad = Err(ModeError::InvalidPadding);
+        assert_eq!((pkcs7_unpad(b"abc\x00", 4)), (bad));
+        assert_eq!((pkcs7_unpad(b"abc\x05", 4)), (bad));
+        assert_eq!((pkcs7_unpad(b"ab\x01\x02", 4)), (bad));
+        assert_eq!(
+            (pkcs7_unpad(b"abc", 4)), 
+            (Err(ModeError::IncompleteBlock { len: 3 }))
+        );
+    }
+}
@@ -12,7 +12,24 @@
 /// # Returns
 /// A `Vec<u8>` containing the padded message in bytes.
 pub fn big_endian_padd(msg: &[u8]) -> Vec<u8> {
-    let mut bytes: Vec<u8> = msg.to_vec();
+    big_endian_padd_tail(msg, msg.len() as u64)
+}
+
+/// Pad the unprocessed tail of a message that was fed in chunks.
+///
+/// # Arguments
+/// - `tail`: The trailing bytes that did not fill a whole 64-byte block.
+/// - `msg_len`: Length of the complete message in bytes.
+///
+/// # Description
+/// Same padding as `big_endian_padd`, but the encoded length is taken from
+/// `msg_len` instead of `tail`, since the earlier blocks were already
+/// compressed by a streaming hasher.
+///
+/// # Returns
+/// A `Vec<u8>` of one or two 64-byte blocks.
+pub fn big_endian_padd_tail(tail: &[u8], msg_len: u64) -> Vec<u8> {
+    let mut bytes: Vec<u8> = tail.to_vec();
 
     // Append 1 + k.
     bytes.push(0x80);
@@ -20,7 +37,7 @@ pub fn big_endian_padd(msg: &[u8]) -> Vec<u8> {
         bytes.push(0x00);
     }
 
-    let msg_as_bits = (msg.len() as u64) * 8;
+    let msg_as_bits = msg_len.wrapping_mul(8);
     let mut i = 0;
     // Append MSG length, in big endian order.
     while i < 8 {
@@ -53,4 +70,19 @@ mod test {
         
         assert_eq!((result), (expected));
     }
+
+    #[test]
+    fn pads_tail_with_length_of_whole_streamed_message() {
+        // "abc" is the tail of a 67-byte message (536 bits = 0x0218).
+        let result = big_endian_padd_tail(b"abc", 67);
+        let mut expected = vec![0u8; 64];
+        expected[0] = 97;
+        expected[1] = 98;
+        expected[2] = 99;
+        expected[3] = 128;
+        expected[62] = 0x02;
+        expected[63] = 0x18;
+
+        assert_eq!((result), (expected));
+    }
 }
\ No newline at end of file
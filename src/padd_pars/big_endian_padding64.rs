@@ -1,5 +1,17 @@
+/// Pad a message to a 1024-bit block (SHA-512 padding).
+///
+/// Same as `big_endian_padd`, but the message is padded to 112 (mod 128)
+/// bytes and the length is appended as a 128-bit big-endian integer.
 pub fn big_endian_padd64(msg: &[u8]) -> Vec<u8> {
-    let mut bytes: Vec<u8> = msg.to_vec();
+    big_endian_padd64_tail(msg, msg.len() as u128)
+}
+
+/// Pad the unprocessed tail of a message fed in chunks (SHA-512 padding).
+///
+/// The encoded length is `msg_len` (bytes of the whole message), not the
+/// length of `tail`, since earlier blocks were already compressed.
+pub fn big_endian_padd64_tail(tail: &[u8], msg_len: u128) -> Vec<u8> {
+    let mut bytes: Vec<u8> = tail.to_vec();
     // Append 1.
     bytes.push(0x80);
     // Append all 0s (k) till the message is l + 1 + k -= 896 MOD 1024.
@@ -7,7 +19,7 @@ pub fn big_endian_padd64(msg: &[u8]) -> Vec<u8> {
         bytes.push(0x00);
     }
     // Append message when l + 1 + k -= 896 MOD 1024.
-    let msg_in_bits = (msg.len() as u128) * 8;
+    let msg_in_bits = msg_len.wrapping_mul(8);
     let mut i = 0;
     while i < 16 {
         let decremental_shift = (15 - i) * 8;
@@ -16,4 +28,24 @@ pub fn big_endian_padd64(msg: &[u8]) -> Vec<u8> {
         i += 1;
     }
     bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pads_tail_with_length_of_whole_streamed_message() {
+        // "abc" is the tail of a 131-byte message (1048 bits = 0x0418).
+        let result = big_endian_padd64_tail(b"abc", 131);
+        let mut expected = vec![0u8; 128];
+        expected[0] = 97;
+        expected[1] = 98;
+        expected[2] = 99;
+        expected[3] = 128;
+        expected[126] = 0x04;
+        expected[127] = 0x18;
+
+        assert_eq!((result), (expected));
+    }
 }
\ No newline at end of file
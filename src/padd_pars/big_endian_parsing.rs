@@ -45,6 +45,27 @@ pub fn big_endian_pars(bytes: Vec<u8>) -> Vec<[u32; 16]> {
     blocks
 }
 
+/// Parse exactly one 64-byte block into 16 × 32-bit big-endian words.
+///
+/// Used by the streaming hashers, which compress a block as soon as it is
+/// filled instead of parsing the whole padded message at once.
+pub fn big_endian_pars_block(bytes: &[u8]) -> [u32; 16] {
+    let mut block = [0u32; 16];
+    let mut l = 0;
+    while l < 16 {
+        let j = l * 4;
+        let b0 = bytes[j] as u32;
+        let b1 = bytes[j + 1] as u32;
+        let b2 = bytes[j + 2] as u32;
+        let b3 = bytes[j + 3] as u32;
+
+        block[l] = (b0 << 24) | (b1 << 16) | (b2 << 8) | (b3);
+        l += 1;
+    }
+
+    block
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -105,4 +126,14 @@ mod test {
 
         assert_eq!((result), (expected));
     }
-}
+
+    #[test]
+    fn pars_block_matches_second_block_of_full_parsing() {
+        let bytes = big_endian_padd(b"AAAAA_AAAAA_AAAAA_AAAAA_AAAAA_AAAAA_\
+        AAAAA_AAAAA_AAAAA_AAAAA_AAAAA");
+        let result = big_endian_pars_block(&bytes[64..128]);
+        let expected = big_endian_pars(bytes)[1];
+
+        assert_eq!((result), (expected));
+    }
+}
\ No newline at end of file
@@ -32,4 +32,38 @@ pub fn big_endian_pars64(msg_in_bytes: Vec<u8>) -> Vec<[u64; 16]> {
         k += 16;
     }
     blocks
+}
+
+/// Parse exactly one 128-byte block into 16 × 64-bit big-endian words.
+pub fn big_endian_pars64_block(bytes: &[u8]) -> [u64; 16] {
+    let mut block = [0u64; 16];
+    let mut l = 0;
+    while l < 16 {
+        let j = l * 8;
+        let mut word = 0u64;
+        let mut k = 0;
+        while k < 8 {
+            word = (word << 8) | bytes[j + k] as u64;
+            k += 1;
+        }
+        block[l] = word;
+        l += 1;
+    }
+    block
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::padd_pars::big_endian_padd64;
+
+    #[test]
+    fn pars_block_matches_full_parsing() {
+        let bytes = big_endian_padd64(b"abc");
+        let result = big_endian_pars64_block(&bytes);
+        let expected = big_endian_pars64(bytes)[0];
+
+        assert_eq!((result[0]), (0x6162638000000000));
+        assert_eq!((result), (expected));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,85 @@
+/// Buffer for the partial block of a message fed in chunks.
+///
+/// # Description
+/// - `feed` first tops up a partially filled buffer, then hands every whole
+///   `N`-byte block to the caller, straight from the input where possible.
+/// - Whatever does not fill a block stays buffered until the next `feed`.
+/// - `tail` returns the buffered bytes, ready to be padded on finalize.
+#[derive(Clone)]
+pub struct BlockBuffer<const N: usize> {
+    buffer: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> BlockBuffer<N> {
+    pub fn new() -> Self {
+        BlockBuffer {
+            buffer: [0u8; N],
+            len: 0,
+        }
+    }
+
+    /// Buffer `data` and call `process` on every block that fills up.
+    pub fn feed(&mut self, data: &[u8], mut process: impl FnMut(&[u8])) {
+        let mut i = 0;
+
+        // Top up a partially filled buffer first.
+        if self.len > 0 {
+            while self.len < N && i < data.len() {
+                self.buffer[self.len] = data[i];
+                self.len += 1;
+                i += 1;
+            }
+
+            if self.len < N {
+                return;
+            }
+
+            process(&self.buffer);
+            self.len = 0;
+        }
+
+        // Process whole blocks directly from the input.
+        while data.len() - i >= N {
+            process(&data[i..i + N]);
+            i += N;
+        }
+
+        // Keep the remainder for the next call.
+        while i < data.len() {
+            self.buffer[self.len] = data[i];
+            self.len += 1;
+            i += 1;
+        }
+    }
+
+    /// Bytes buffered so far that do not fill a whole block.
+    pub fn tail(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+}
+
+impl<const N: usize> Default for BlockBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hands_out_whole_blocks_and_keeps_the_rest() {
+        let mut buffer: BlockBuffer<4> = BlockBuffer::new();
+        let mut blocks: Vec<Vec<u8>> = Vec::new();
+
+        buffer.feed(b"ab", |block| blocks.push(block.to_vec()));
+        buffer.feed(b"cdefghij", |block| blocks.push(block.to_vec()));
+
+        let expected: Vec<Vec<u8>> = vec![b"abcd".to_vec(), b"efgh".to_vec()];
+
+        assert_eq!((blocks), (expected));
+        assert_eq!((buffer.tail()), (b"ij"));
+    }
+}
\ No newline at end of file
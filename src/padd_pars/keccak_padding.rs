@@ -1,17 +1,50 @@
 // Keccak padding: to produce output string of decired length.
-pub fn keccak_padd(mut v: Vec<u8>, rate: usize) -> Vec<u8> {
+//
+// `domain` is the domain separation suffix with the first padding bit
+// already appended: 0x06 for SHA-3, 0x1f for SHAKE, 0x01 for the original
+// Keccak submission.
+pub fn keccak_padd(mut v: Vec<u8>, rate: usize, domain: u8) -> Vec<u8> {
     let rate_bytes = rate / 8;
 
     // Domain sepperator.
-    v.push(0x06);
+    v.push(domain);
 
     // Pad till end of block with all 0s.
-    while (v.len() % rate_bytes) != rate_bytes - 1 {
+    while !v.len().is_multiple_of(rate_bytes) {
         v.push(0x00);
     }
 
-    // Final byte, end of message.
-    v.push(0x80);
+    // Final bit, end of message. Shares a byte with the domain separator
+    // when the message ends one byte short of a full block.
+    let last = v.len() - 1;
+    v[last] |= 0x80;
 
     v
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pads_to_one_block_when_one_byte_is_left() {
+        let result = keccak_padd(vec![0x61; 135], 1088, 0x06);
+        let mut expected = vec![0x61; 136];
+        expected[135] = 0x86;
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn appends_given_domain_suffix() {
+        let result = keccak_padd(b"abc".to_vec(), 1344, 0x1f);
+        let mut expected = vec![0x00; 168];
+        expected[0] = 0x61;
+        expected[1] = 0x62;
+        expected[2] = 0x63;
+        expected[3] = 0x1f;
+        expected[167] = 0x80;
+
+        assert_eq!((result), (expected));
+    }
 }
\ No newline at end of file
@@ -1,6 +1,15 @@
 /// Padding message in little endian order.
 pub fn little_endian_padd(msg: &[u8]) -> Vec<u8> {
-    let mut bytes: Vec<u8> = msg.to_vec();
+    little_endian_padd_tail(msg, msg.len() as u64)
+}
+
+/// Padding the unprocessed tail of a message fed in chunks, in little endian
+/// order.
+///
+/// The encoded length is `msg_len` (bytes of the whole message), not the
+/// length of `tail`, since earlier blocks were already compressed.
+pub fn little_endian_padd_tail(tail: &[u8], msg_len: u64) -> Vec<u8> {
+    let mut bytes: Vec<u8> = tail.to_vec();
 
     // Append 1 for end of message.
     // And all 0s to fill the block size.
@@ -10,7 +19,7 @@ pub fn little_endian_padd(msg: &[u8]) -> Vec<u8> {
     }
 
     // Work with byte range.
-    let msg_len_bits = (msg.len() as u64) * 8;
+    let msg_len_bits = msg_len.wrapping_mul(8);
     for i in 0..8 {
         bytes.push((msg_len_bits >> (i * 8)) as u8); // little-endian
     }
@@ -18,4 +27,35 @@ pub fn little_endian_padd(msg: &[u8]) -> Vec<u8> {
     bytes
 }
 
-//TODO: Add tests.
\ No newline at end of file
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn appends_1_k_and_msg_length_least_significant_byte_first() {
+        let result = little_endian_padd(b"abc");
+        let mut expected = vec![0u8; 64];
+        expected[0] = 97;
+        expected[1] = 98;
+        expected[2] = 99;
+        expected[3] = 128;
+        expected[56] = 24;
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn pads_tail_with_length_of_whole_streamed_message() {
+        // "abc" is the tail of a 67-byte message (536 bits = 0x0218).
+        let result = little_endian_padd_tail(b"abc", 67);
+        let mut expected = vec![0u8; 64];
+        expected[0] = 97;
+        expected[1] = 98;
+        expected[2] = 99;
+        expected[3] = 128;
+        expected[56] = 0x18;
+        expected[57] = 0x02;
+
+        assert_eq!((result), (expected));
+    }
+}
\ No newline at end of file
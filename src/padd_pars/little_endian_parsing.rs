@@ -34,4 +34,39 @@ pub fn little_endian_pars(bytes: Vec<u8>) -> Vec<[u32; 16]> {
     blocks
 }
 
-//TODO: Add tests.
\ No newline at end of file
+/// Little-endian parsing of exactly one 64-byte block into 16 words.
+///
+/// Used by the streaming hashers, which compress a block as soon as it is
+/// filled instead of parsing the whole padded message at once.
+pub fn little_endian_pars_block(bytes: &[u8]) -> [u32; 16] {
+    let mut block = [0u32; 16];
+    let mut l = 0;
+    while l < 16 {
+        let j = l * 4;
+        let b0 = bytes[j] as u32;
+        let b1 = bytes[j + 1] as u32;
+        let b2 = bytes[j + 2] as u32;
+        let b3 = bytes[j + 3] as u32;
+
+        block[l] = (b0) | (b1 << 8) | (b2 << 16) | (b3 << 24);
+        l += 1;
+    }
+
+    block
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::padd_pars::little_endian_padd;
+
+    #[test]
+    fn pars_block_matches_full_parsing() {
+        let bytes = little_endian_padd(b"abc");
+        let result = little_endian_pars_block(&bytes);
+        let expected = little_endian_pars(bytes)[0];
+
+        assert_eq!((result[0]), (0x80636261));
+        assert_eq!((result), (expected));
+    }
+}
\ No newline at end of file
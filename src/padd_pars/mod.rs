@@ -5,11 +5,13 @@ pub mod big_endian_parsing64;
 pub mod little_endian_padding;
 pub mod little_endian_parsing;
 pub mod keccak_padding;
+pub mod block_buffer;
 
-pub use big_endian_padding::big_endian_padd;
-pub use big_endian_parsing::big_endian_pars;
-pub use big_endian_padding64::big_endian_padd64;
-pub use big_endian_parsing64::big_endian_pars64;
-pub use little_endian_padding::little_endian_padd;
-pub use little_endian_parsing::little_endian_pars;
-pub use keccak_padding::keccak_padd;
\ No newline at end of file
+pub use big_endian_padding::{ big_endian_padd, big_endian_padd_tail };
+pub use big_endian_parsing::{ big_endian_pars, big_endian_pars_block };
+pub use big_endian_padding64::{ big_endian_padd64, big_endian_padd64_tail };
+pub use big_endian_parsing64::{ big_endian_pars64, big_endian_pars64_block };
+pub use little_endian_padding::{ little_endian_padd, little_endian_padd_tail };
+pub use little_endian_parsing::{ little_endian_pars, little_endian_pars_block };
+pub use keccak_padding::keccak_padd;
+pub use block_buffer::BlockBuffer;
\ No newline at end of file
@@ -0,0 +1,26 @@
+use std::fmt;
+
+/// Errors of PBKDF2 parameter validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pbkdf2Error {
+    /// The iteration count must be at least 1.
+    ZeroIterations,
+    /// More output was requested than (2^32 - 1) PRF blocks can provide.
+    OutputTooLong { len: usize, max: usize },
+}
+
+impl fmt::Display for Pbkdf2Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pbkdf2Error::ZeroIterations => {
+                write!(f, "PBKDF2 iteration count must be at least 1")
+            }
+            Pbkdf2Error::OutputTooLong { len, max } => write!(
+                f, 
+                "PBKDF2 output of {len} bytes exceeds the maximum of {max}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Pbkdf2Error {}
@@ -0,0 +1,6 @@
+pub mod error;
+#[allow(clippy::module_inception)]
+pub mod pbkdf2;
+
+pub use error::Pbkdf2Error;
+pub use pbkdf2::{ pbkdf2, pbkdf2_with, Prf };
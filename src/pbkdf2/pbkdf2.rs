@@ -0,0 +1,255 @@
+use crate::digest::Digest;
+use crate::hmac::Hmac;
+use crate::pbkdf2::Pbkdf2Error;
+use crate::sha1::Sha1;
+use crate::sha256::{ Sha224, Sha256 };
+use crate::sha512::{ Sha384, Sha512 };
+
+/// Pseudorandom function used by `pbkdf2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prf {
+    HmacSha1,
+    HmacSha224,
+    HmacSha256,
+    HmacSha384,
+    HmacSha512,
+}
+
+/// PBKDF2 (Password-Based Key Derivation Function 2).
+///
+/// # Arguments
+/// - `prf`: HMAC variant used as the pseudorandom function.
+/// - `password`, `salt`: As byte slices (`&[u8]`).
+/// - `iterations`: Iteration count `c`, at least 1.
+/// - `out_len`: Derived key length `dkLen` in bytes.
+///
+/// # Returns
+/// The `out_len`-byte derived key, or a `Pbkdf2Error` for a zero iteration
+/// count or an output longer than (2^32 - 1) PRF blocks.
+///
+/// # Reference
+/// [RFC 8018](https://www.rfc-editor.org/rfc/rfc8018), section 5.2.
+pub fn pbkdf2(
+    prf: Prf, 
+    password: &[u8], 
+    salt: &[u8], 
+    iterations: u32, 
+    out_len: usize
+) -> Result<Vec<u8>, Pbkdf2Error> {
+    match prf {
+        Prf::HmacSha1 => {
+            pbkdf2_with::<Sha1>(password, salt, iterations, out_len)
+        }
+        Prf::HmacSha224 => {
+            pbkdf2_with::<Sha224>(password, salt, iterations, out_len)
+        }
+        Prf::HmacSha256 => {
+            pbkdf2_with::<Sha256>(password, salt, iterations, out_len)
+        }
+        Prf::HmacSha384 => {
+            pbkdf2_with::<Sha384>(password, salt, iterations, out_len)
+        }
+        Prf::HmacSha512 => {
+            pbkdf2_with::<Sha512>(password, salt, iterations, out_len)
+        }
+    }
+}
+
+/// PBKDF2 with HMAC over any hash `D` as the PRF.
+///
+/// # Description
+/// - The password is the HMAC key, so `Hmac::<D>::new(password)` is built
+///   once: the `K0 ^ ipad` and `K0 ^ opad` states are cloned for every PRF
+///   call instead of re-hashing the key each iteration.
+/// - Block `i` is `T_i = U_1 ^ U_2 ^ ... ^ U_c`, with
+///   `U_1 = PRF(P, S || INT(i))` and `U_j = PRF(P, U_{j-1})`.
+/// - The derived key is the first `out_len` bytes of `T_1 || T_2 || ...`.
+pub fn pbkdf2_with<D: Digest>(
+    password: &[u8], 
+    salt: &[u8], 
+    iterations: u32, 
+    out_len: usize
+) -> Result<Vec<u8>, Pbkdf2Error> {
+    if iterations == 0 {
+        return Err(Pbkdf2Error::ZeroIterations);
+    }
+
+    let max = (u32::MAX as u64 * D::OUTPUT_SIZE as u64)
+        .min(usize::MAX as u64) as usize;
+    if out_len > max {
+        return Err(Pbkdf2Error::OutputTooLong { len: out_len, max });
+    }
+
+    // Keyed once per password.
+    let keyed = Hmac::<D>::new(password);
+
+    let mut derived: Vec<u8> = Vec::with_capacity(out_len);
+    let mut block_index: u32 = 1;
+    while derived.len() < out_len {
+        // U_1 = PRF(P, S || INT(i)).
+        let mut mac = keyed.clone();
+        mac.update(salt);
+        mac.update(&block_index.to_be_bytes());
+        let mut u = mac.finalize();
+        let mut t = u.as_ref().to_vec();
+
+        // U_j = PRF(P, U_{j-1}), T_i ^= U_j.
+        for _ in 1..iterations {
+            let mut mac = keyed.clone();
+            mac.update(u.as_ref());
+            u = mac.finalize();
+
+            let u_bytes = u.as_ref();
+            for k in 0..t.len() {
+                t[k] ^= u_bytes[k];
+            }
+        }
+
+        let take = (out_len - derived.len()).min(t.len());
+        derived.extend_from_slice(&t[..take]);
+        block_index = block_index.wrapping_add(1);
+    }
+
+    Ok(derived)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc6070_one_iteration() {
+        let result = pbkdf2(Prf::HmacSha1, b"password", b"salt", 1, 20);
+        let expected = vec![
+            0x0c, 0x60, 0xc8, 0x0f, 0x96, 0x1f, 0x0e, 0x71,
+            0xf3, 0xa9, 0xb5, 0x24, 0xaf, 0x60, 0x12, 0x06,
+            0x2f, 0xe0, 0x37, 0xa6,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn rfc6070_two_iterations() {
+        let result = pbkdf2(Prf::HmacSha1, b"password", b"salt", 2, 20);
+        let expected = vec![
+            0xea, 0x6c, 0x01, 0x4d, 0xc7, 0x2d, 0x6f, 0x8c,
+            0xcd, 0x1e, 0xd9, 0x2a, 0xce, 0x1d, 0x41, 0xf0,
+            0xd8, 0xde, 0x89, 0x57,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn rfc6070_4096_iterations() {
+        let result = pbkdf2(Prf::HmacSha1, b"password", b"salt", 4096, 20);
+        let expected = vec![
+            0x4b, 0x00, 0x79, 0x01, 0xb7, 0x65, 0x48, 0x9a,
+            0xbe, 0xad, 0x49, 0xd9, 0x26, 0xf7, 0x21, 0xd0,
+            0x65, 0xa4, 0x29, 0xc1,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn rfc6070_multi_block_output() {
+        let password = b"passwordPASSWORDpassword";
+        let salt = b"saltSALTsaltSALTsaltSALTsaltSALTsalt";
+        let result = pbkdf2(Prf::HmacSha1, password, salt, 4096, 25);
+        let expected = vec![
+            0x3d, 0x2e, 0xec, 0x4f, 0xe4, 0x1c, 0x84, 0x9b,
+            0x80, 0xc8, 0xd8, 0x36, 0x62, 0xc0, 0xe4, 0x4a,
+            0x8b, 0x29, 0x1a, 0x96, 0x4c, 0xf2, 0xf0, 0x70,
+            0x38,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn rfc6070_embedded_nul_bytes() {
+        let result = pbkdf2(Prf::HmacSha1, b"pass\0word", b"sa\0lt", 4096, 16);
+        let expected = vec![
+            0x56, 0xfa, 0x6a, 0xa7, 0x55, 0x48, 0x09, 0x9d,
+            0xcc, 0x37, 0xd7, 0xf0, 0x34, 0x25, 0xe0, 0xc3,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn rfc7914_passwd_salt() {
+        let result = pbkdf2(Prf::HmacSha256, b"passwd", b"salt", 1, 64);
+        let expected = vec![
+            0x55, 0xac, 0x04, 0x6e, 0x56, 0xe3, 0x08, 0x9f,
+            0xec, 0x16, 0x91, 0xc2, 0x25, 0x44, 0xb6, 0x05,
+            0xf9, 0x41, 0x85, 0x21, 0x6d, 0xde, 0x04, 0x65,
+            0xe6, 0x8b, 0x9d, 0x57, 0xc2, 0x0d, 0xac, 0xbc,
+            0x49, 0xca, 0x9c, 0xcc, 0xf1, 0x79, 0xb6, 0x45,
+            0x99, 0x16, 0x64, 0xb3, 0x9d, 0x77, 0xef, 0x31,
+            0x7c, 0x71, 0xb8, 0x45, 0xb1, 0xe3, 0x0b, 0xd5,
+            0x09, 0x11, 0x20, 0x41, 0xd3, 0xa1, 0x97, 0x83,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn rfc7914_password_nacl() {
+        let result = pbkdf2(Prf::HmacSha256, b"Password", b"NaCl", 80000, 64);
+        let expected = vec![
+            0x4d, 0xdc, 0xd8, 0xf6, 0x0b, 0x98, 0xbe, 0x21,
+            0x83, 0x0c, 0xee, 0x5e, 0xf2, 0x27, 0x01, 0xf9,
+            0x64, 0x1a, 0x44, 0x18, 0xd0, 0x4c, 0x04, 0x14,
+            0xae, 0xff, 0x08, 0x87, 0x6b, 0x34, 0xab, 0x56,
+            0xa1, 0xd4, 0x25, 0xa1, 0x22, 0x58, 0x33, 0x54,
+            0x9a, 0xdb, 0x84, 0x1b, 0x51, 0xc9, 0xb3, 0x17,
+            0x6a, 0x27, 0x2b, 0xde, 0xbb, 0xa1, 0xd0, 0x78,
+            0x47, 0x8f, 0x62, 0xb3, 0x97, 0xf3, 0x3c, 0x8d,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn sha512_password_salt() {
+        let result = pbkdf2(Prf::HmacSha512, b"password", b"salt", 1000, 64);
+        let expected = vec![
+            0xaf, 0xe6, 0xc5, 0x53, 0x07, 0x85, 0xb6, 0xcc,
+            0x6b, 0x1c, 0x64, 0x53, 0x38, 0x47, 0x31, 0xbd,
+            0x5e, 0xe4, 0x32, 0xee, 0x54, 0x9f, 0xd4, 0x2f,
+            0xb6, 0x69, 0x57, 0x79, 0xad, 0x8a, 0x1c, 0x5b,
+            0xf5, 0x9d, 0xe6, 0x9c, 0x48, 0xf7, 0x74, 0xef,
+            0xc4, 0x00, 0x7d, 0x52, 0x98, 0xf9, 0x03, 0x3c,
+            0x02, 0x41, 0xd5, 0xab, 0x69, 0x30, 0x5e, 0x7b,
+            0x64, 0xec, 0xee, 0xb8, 0xd8, 0x34, 0xcf, 0xec,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn generic_matches_prf_dispatch() {
+        let result = pbkdf2_with::<Sha384>(b"password", b"salt", 3, 100);
+        let expected = pbkdf2(Prf::HmacSha384, b"password", b"salt", 3, 100);
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn rejects_zero_iterations() {
+        let result = pbkdf2(Prf::HmacSha256, b"password", b"salt", 0, 32);
+        assert_eq!((result), (Err(Pbkdf2Error::ZeroIterations)));
+    }
+
+    #[test]
+    fn rejects_output_beyond_block_counter() {
+        let max = u32::MAX as usize * 20;
+        let result = pbkdf2(Prf::HmacSha1, b"password", b"salt", 1, max + 1);
+        let error = Pbkdf2Error::OutputTooLong { len: max + 1, max };
+
+        assert_eq!((result), (Err(error)));
+    }
+}
@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod poly1305;
+
+pub use poly1305::{ poly1305, Poly1305, KEY_SIZE, TAG_SIZE };
@@ -0,0 +1,336 @@
+use crate::padd_pars::BlockBuffer;
+
+/// Poly1305 one-time key size in bytes, r || s.
+pub const KEY_SIZE: usize = 32;
+
+/// Poly1305 tag size in bytes.
+pub const TAG_SIZE: usize = 16;
+
+// 26-bit limb mask.
+const MASK: u32 = 0x3ffffff;
+
+/// Poly1305 one-time authenticator.
+///
+/// # Description
+/// - The key is split into r (clamped) and s, 16 bytes each.
+/// - Every 16-byte block, with a 0x01 byte appended, is added to the
+///   accumulator, which is then multiplied by r modulo p = 2^130 - 5.
+/// - The 130-bit accumulator and r are kept in five 26-bit limbs, so every
+///   limb product fits a u64 and the reduction is a carry chain with the
+///   top carry folded back in times 5 (2^130 = 5 mod p).
+/// - `finalize` fully reduces the accumulator in constant time and returns
+///   (acc + s) mod 2^128.
+///
+/// A key must never authenticate more than one message.
+///
+/// # Reference
+/// [RFC 8439](https://www.rfc-editor.org/rfc/rfc8439), section 2.5.
+#[derive(Clone)]
+pub struct Poly1305 {
+    r: [u32; 5],
+    s: [u32; 4],
+    h: [u32; 5],
+    buffer: BlockBuffer<16>,
+}
+
+impl Poly1305 {
+    /// Start a new MAC with a 32-byte one-time key.
+    pub fn new(key: &[u8; KEY_SIZE]) -> Self {
+        // Clamp: r &= 0x0ffffffc0ffffffc0ffffffc0fffffff, split into limbs.
+        let r = [
+            le32(key, 0) & 0x3ffffff,
+            (le32(key, 3) >> 2) & 0x3ffff03,
+            (le32(key, 6) >> 4) & 0x3ffc0ff,
+            (le32(key, 9) >> 6) & 0x3f03fff,
+            (le32(key, 12) >> 8) & 0x00fffff,
+        ];
+        let s = [
+            le32(key, 16),
+            le32(key, 20),
+            le32(key, 24),
+            le32(key, 28)
+        ];
+
+        Poly1305 {
+            r,
+            s,
+            h: [0u32; 5],
+            buffer: BlockBuffer::new(),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        let h = &mut self.h;
+        let r = &self.r;
+        self.buffer.feed(data, |block| process_block(h, r, block, 1 << 24));
+    }
+
+    /// Absorb `data` followed by zeros up to a 16-byte boundary, as the
+    /// AEAD constructions do for associated data and ciphertext.
+    pub fn update_padded(&mut self, data: &[u8]) {
+        self.update(data);
+
+        let rem = data.len() % 16;
+        if rem != 0 {
+            self.update(&[0u8; 16][rem..]);
+        }
+    }
+
+    /// Process the buffered tail and return the 16-byte tag.
+    pub fn finalize(mut self) -> [u8; TAG_SIZE] {
+        let tail = self.buffer.tail();
+        if !tail.is_empty() {
+            // A short last block gets its 0x01 byte in place, not at 2^128.
+            let mut block = [0u8; 16];
+            block[..tail.len()].copy_from_slice(tail);
+            block[tail.len()] = 0x01;
+            process_block(&mut self.h, &self.r, &block, 0);
+        }
+
+        let mut h = self.h;
+
+        // Full carry, h < 2^130 + small.
+        let mut c = h[1] >> 26;
+        h[1] &= MASK;
+        for limb in h[2..].iter_mut() {
+            *limb += c;
+            c = *limb >> 26;
+            *limb &= MASK;
+        }
+        h[0] += c * 5;
+        c = h[0] >> 26;
+        h[0] &= MASK;
+        h[1] += c;
+
+        // g = h + 5 - 2^130, i.e. h - p.
+        let mut g = [0u32; 5];
+        g[0] = h[0].wrapping_add(5);
+        c = g[0] >> 26;
+        g[0] &= MASK;
+        for i in 1..5 {
+            g[i] = h[i].wrapping_add(c);
+            c = g[i] >> 26;
+            g[i] &= MASK;
+        }
+        g[4] = g[4].wrapping_add(c << 26).wrapping_sub(1 << 26);
+
+        // Select g when h >= p (no borrow out of the top limb), without
+        // branching on the secret accumulator.
+        let select_g = (g[4] >> 31).wrapping_sub(1);
+        for i in 0..5 {
+            h[i] = (h[i] & !select_g) | (g[i] & select_g);
+        }
+
+        // Pack the limbs into four 32-bit words, mod 2^128.
+        let words = [
+            h[0] | (h[1] << 26),
+            (h[1] >> 6) | (h[2] << 20),
+            (h[2] >> 12) | (h[3] << 14),
+            (h[3] >> 18) | (h[4] << 8),
+        ];
+
+        // tag = (h + s) mod 2^128.
+        let mut tag = [0u8; TAG_SIZE];
+        let mut carry = 0u64;
+        for i in 0..4 {
+            let f = words[i] as u64 + self.s[i] as u64 + carry;
+            tag[4 * i..4 * i + 4].copy_from_slice(&(f as u32).to_le_bytes());
+            carry = f >> 32;
+        }
+
+        tag
+    }
+}
+
+/// Poly1305: one-shot MAC of a complete message.
+pub fn poly1305(key: &[u8; KEY_SIZE], msg: &[u8]) -> [u8; TAG_SIZE] {
+    let mut mac = Poly1305::new(key);
+    mac.update(msg);
+    mac.finalize()
+}
+
+// h = (h + block + hibit * 2^128) * r mod 2^130 - 5, partially reduced.
+fn process_block(h: &mut [u32; 5], r: &[u32; 5], block: &[u8], hibit: u32) {
+    h[0] += le32(block, 0) & MASK;
+    h[1] += (le32(block, 3) >> 2) & MASK;
+    h[2] += (le32(block, 6) >> 4) & MASK;
+    h[3] += (le32(block, 9) >> 6) & MASK;
+    h[4] += (le32(block, 12) >> 8) | hibit;
+
+    // Limbs past 2^130 wrap around times 5.
+    let [r0, r1, r2, r3, r4] = r.map(|x| x as u64);
+    let [s1, s2, s3, s4] = [r1 * 5, r2 * 5, r3 * 5, r4 * 5];
+    let [h0, h1, h2, h3, h4] = h.map(|x| x as u64);
+
+    let d = [
+        h0 * r0 + h1 * s4 + h2 * s3 + h3 * s2 + h4 * s1,
+        h0 * r1 + h1 * r0 + h2 * s4 + h3 * s3 + h4 * s2,
+        h0 * r2 + h1 * r1 + h2 * r0 + h3 * s4 + h4 * s3,
+        h0 * r3 + h1 * r2 + h2 * r1 + h3 * r0 + h4 * s4,
+        h0 * r4 + h1 * r3 + h2 * r2 + h3 * r1 + h4 * r0,
+    ];
+
+    // Carry chain back down to 26-bit limbs.
+    let mut c = 0u64;
+    for i in 0..5 {
+        let t = d[i] + c;
+        h[i] = (t as u32) & MASK;
+        c = t >> 26;
+    }
+    h[0] += (c as u32) * 5;
+    let c = h[0] >> 26;
+    h[0] &= MASK;
+    h[1] += c;
+}
+
+// Little-endian u32 at byte offset i.
+fn le32(bytes: &[u8], i: usize) -> u32 {
+    u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::{ bytes_to_hex, hex_to_bytes };
+
+    fn key(hex: &[u8]) -> [u8; KEY_SIZE] {
+        hex_to_bytes(hex).try_into().unwrap()
+    }
+
+    #[test]
+    fn rfc8439_section_2_5_2() {
+        let key = key(b"85d6be7857556d337f4452fe42d506a8\
+                        0103808afb0db2fd4abff6af4149f51b");
+
+        let result = poly1305(&key, b"Cryptographic Forum Research Group");
+
+        assert_eq!(
+            (bytes_to_hex(&result)),
+            (b"a8061dc1305136c6c22b8baf0c0127a9".to_vec())
+        );
+    }
+
+    #[test]
+    fn rfc8439_appendix_a3_zero_key() {
+        let msg = [0u8; 64];
+
+        let result = poly1305(&[0u8; KEY_SIZE], &msg);
+
+        assert_eq!((result), ([0u8; TAG_SIZE]));
+    }
+
+    #[test]
+    fn rfc8439_appendix_a3_r_zero() {
+        // Test vector #2: r = 0, so the tag is s.
+        let key = key(b"00000000000000000000000000000000\
+                        36e5f6b5c5e06070f0efca96227a863e");
+        let msg = b"Any submission to the IETF intended by the Contributor \
+                    for publication as all or part of an IETF Internet-Draft \
+                    or RFC and any statement made within the context of an \
+                    IETF activity is considered an \"IETF Contribution\". \
+                    Such statements include oral statements in IETF \
+                    sessions, as well as written and electronic \
+                    communications made at any time or place, which are \
+                    addressed to";
+
+        let result = poly1305(&key, msg);
+
+        assert_eq!(
+            (bytes_to_hex(&result)),
+            (b"36e5f6b5c5e06070f0efca96227a863e".to_vec())
+        );
+    }
+
+    // Edge cases of the final reduction, Appendix A.3 vectors #5 to #11.
+    #[test]
+    fn rfc8439_appendix_a3_reduction_edge_cases() {
+        let cases: [(&[u8], &[u8], &[u8]); 7] = [
+            (
+                b"02000000000000000000000000000000\
+                  00000000000000000000000000000000",
+                b"ffffffffffffffffffffffffffffffff",
+                b"03000000000000000000000000000000",
+            ),
+            (
+                b"02000000000000000000000000000000\
+                  ffffffffffffffffffffffffffffffff",
+                b"02000000000000000000000000000000",
+                b"03000000000000000000000000000000",
+            ),
+            (
+                b"01000000000000000000000000000000\
+                  00000000000000000000000000000000",
+                b"ffffffffffffffffffffffffffffffff\
+                  f0ffffffffffffffffffffffffffffff\
+                  11000000000000000000000000000000",
+                b"05000000000000000000000000000000",
+            ),
+            (
+                b"01000000000000000000000000000000\
+                  00000000000000000000000000000000",
+                b"ffffffffffffffffffffffffffffffff\
+                  fbfefefefefefefefefefefefefefefe\
+                  01010101010101010101010101010101",
+                b"00000000000000000000000000000000",
+            ),
+            (
+                b"02000000000000000000000000000000\
+                  00000000000000000000000000000000",
+                b"fdffffffffffffffffffffffffffffff",
+                b"faffffffffffffffffffffffffffffff",
+            ),
+            (
+                b"01000000000000000400000000000000\
+                  00000000000000000000000000000000",
+                b"e33594d7505e43b90000000000000000\
+                  3394d7505e4379cd0100000000000000\
+                  00000000000000000000000000000000\
+                  01000000000000000000000000000000",
+                b"14000000000000005500000000000000",
+            ),
+            (
+                b"01000000000000000400000000000000\
+                  00000000000000000000000000000000",
+                b"e33594d7505e43b90000000000000000\
+                  3394d7505e4379cd0100000000000000\
+                  00000000000000000000000000000000",
+                b"13000000000000000000000000000000",
+            ),
+        ];
+
+        for (k, msg, tag) in cases {
+            let result = poly1305(&key(k), &hex_to_bytes(msg));
+
+            assert_eq!((bytes_to_hex(&result)), (tag.to_vec()));
+        }
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        let key = key(b"85d6be7857556d337f4452fe42d506a8\
+                        0103808afb0db2fd4abff6af4149f51b");
+        let msg = b"Cryptographic Forum Research Group".repeat(7);
+
+        let mut mac = Poly1305::new(&key);
+        for chunk in msg.chunks(11) {
+            mac.update(chunk);
+        }
+
+        assert_eq!((mac.finalize()), (poly1305(&key, &msg)));
+    }
+
+    #[test]
+    fn update_padded_pads_to_a_block_boundary() {
+        let key = key(b"85d6be7857556d337f4452fe42d506a8\
+                        0103808afb0db2fd4abff6af4149f51b");
+        let mut padded = b"Cryptographic".to_vec();
+        padded.resize(16, 0);
+
+        let mut mac = Poly1305::new(&key);
+        mac.update_padded(b"Cryptographic");
+        mac.update_padded(b"");
+
+        assert_eq!((mac.finalize()), (poly1305(&key, &padded)));
+    }
+}
@@ -1,6 +1,6 @@
-/// #Reference
-/// All round constants, shifts, and message ordering are gained from:
-/// [KULeuven Report AB-9601](https://homes.esat.kuleuven.be/~bosselae/ripemd160/pdf/AB-9601/AB-9601.pdf)
+//! #Reference
+//! All round constants, shifts, and message ordering are gained from:
+//! [KULeuven Report AB-9601](https://homes.esat.kuleuven.be/~bosselae/ripemd160/pdf/AB-9601/AB-9601.pdf)
 
 /// Left round constants.
 ///
@@ -1,3 +1,4 @@
+pub mod compression;
 pub mod constants;
 pub mod functions;
 pub mod ripemd160;
@@ -5,4 +6,6 @@ pub mod ripemd160;
 pub use constants::{
     l_round_constants, r_round_constants
 };
-pub use functions::{f_left, f_right};
\ No newline at end of file
+pub use compression::{ compress, compress_block, IV };
+pub use functions::{f_left, f_right};
+pub use ripemd160::{ ripemd160, Ripemd160 };
\ No newline at end of file
@@ -0,0 +1,30 @@
+use std::fmt;
+
+/// Errors of Salsa20 and secretbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Salsa20Error {
+    /// The 64-bit block counter would wrap around and repeat keystream.
+    CounterOverflow,
+    /// The box is shorter than the 16-byte tag.
+    CiphertextTooShort { len: usize },
+    /// The tag does not match, nothing is decrypted.
+    AuthenticationFailed,
+}
+
+impl fmt::Display for Salsa20Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Salsa20Error::CounterOverflow => {
+                write!(f, "Salsa20 block counter overflow")
+            }
+            Salsa20Error::CiphertextTooShort { len } => {
+                write!(f, "secretbox of {len} bytes has no full tag")
+            }
+            Salsa20Error::AuthenticationFailed => {
+                write!(f, "secretbox tag mismatch")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Salsa20Error {}
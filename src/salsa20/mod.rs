@@ -0,0 +1,13 @@
+pub mod error;
+#[allow(clippy::module_inception)]
+pub mod salsa20;
+pub mod xsalsa20;
+pub mod secretbox;
+
+pub use error::Salsa20Error;
+pub use salsa20::{
+    salsa20, salsa20_core, salsa20_rounds, Salsa20, BLOCK_SIZE, KEY_SIZE,
+    NONCE_SIZE
+};
+pub use xsalsa20::{ hsalsa20, xsalsa20, XNONCE_SIZE };
+pub use secretbox::{ secretbox_open, secretbox_seal, TAG_SIZE };
@@ -0,0 +1,267 @@
+use crate::padd_pars::little_endian_pars_block;
+use crate::salsa20::Salsa20Error;
+use crate::utils::{ rotl, z };
+
+/// Salsa20 key size in bytes.
+pub const KEY_SIZE: usize = 32;
+
+/// Salsa20 nonce size in bytes.
+pub const NONCE_SIZE: usize = 8;
+
+/// Salsa20 keystream block size in bytes.
+pub const BLOCK_SIZE: usize = 64;
+
+// "expand 32-byte k" as four little-endian words.
+const SIGMA: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+/// Salsa20/20 stream cipher context, the same for encryption and
+/// decryption.
+///
+/// # Description
+/// - The state holds the constant on the diagonal, the 256-bit key, the
+///   64-bit nonce and a 64-bit block counter, all as little-endian words.
+/// - Every keystream block is the Salsa20 core (20 rounds plus the
+///   feed-forward addition) of the state, after which the counter goes up
+///   by one.
+/// - Once the counter has used up all 2^64 values, `update` returns
+///   `CounterOverflow` instead of repeating the keystream.
+///
+/// # Reference
+/// [Salsa20 specification](https://cr.yp.to/snuffle/spec.pdf)
+#[derive(Clone)]
+pub struct Salsa20 {
+    state: [u32; 16],
+    keystream: [u8; BLOCK_SIZE],
+    pos: usize,
+    blocks_left: u128,
+}
+
+impl Salsa20 {
+    /// Start the keystream at block `counter`.
+    pub fn new(
+        key: &[u8; KEY_SIZE], 
+        nonce: &[u8; NONCE_SIZE], 
+        counter: u64
+    ) -> Self {
+        let mut input = [0u8; 16];
+        input[..8].copy_from_slice(nonce);
+        input[8..].copy_from_slice(&counter.to_le_bytes());
+
+        Salsa20 {
+            state: key_setup(key, &input),
+            keystream: [0u8; BLOCK_SIZE],
+            pos: BLOCK_SIZE,
+            blocks_left: (1u128 << 64) - counter as u128,
+        }
+    }
+
+    /// Encrypt or decrypt the next chunk.
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>, Salsa20Error> {
+        // Check the counter space up front, nothing is processed if the
+        // chunk does not fit.
+        let buffered = (BLOCK_SIZE - self.pos) as u128;
+        let needed = (data.len() as u128).saturating_sub(buffered);
+        if needed.div_ceil(BLOCK_SIZE as u128) > self.blocks_left {
+            return Err(Salsa20Error::CounterOverflow);
+        }
+
+        let mut out = Vec::with_capacity(data.len());
+        for &byte in data {
+            if self.pos == BLOCK_SIZE {
+                self.next_keystream_block();
+            }
+
+            out.push(byte ^ self.keystream[self.pos]);
+            self.pos += 1;
+        }
+        Ok(out)
+    }
+
+    fn next_keystream_block(&mut self) {
+        let block = salsa20_core(&self.state, 20);
+        for (chunk, word) in self.keystream.chunks_mut(4).zip(&block) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        self.pos = 0;
+        self.blocks_left -= 1;
+
+        // 64-bit counter in words 8 (low) and 9 (high).
+        self.state[8] = self.state[8].wrapping_add(1);
+        if self.state[8] == 0 {
+            self.state[9] = self.state[9].wrapping_add(1);
+        }
+    }
+}
+
+/// Salsa20/20: encrypt or decrypt a complete message, starting at block
+/// `counter`.
+pub fn salsa20(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; NONCE_SIZE], 
+    counter: u64, 
+    data: &[u8]
+) -> Result<Vec<u8>, Salsa20Error> {
+    Salsa20::new(key, nonce, counter).update(data)
+}
+
+/// Salsa20 core: `rounds` rounds of the permutation followed by the
+/// feed-forward addition of the input words, e.g. 20 for Salsa20/20 and 8
+/// for the Salsa20/8 of scrypt.
+pub fn salsa20_core(input: &[u32; 16], rounds: usize) -> [u32; 16] {
+    let x = salsa20_rounds(input, rounds);
+
+    let mut out = [0u32; 16];
+    for i in 0..16 {
+        out[i] = z(x[i], input[i]);
+    }
+
+    out
+}
+
+/// `rounds` rounds (alternating column and row rounds) of the Salsa20
+/// permutation, without the feed-forward addition. `rounds` must be even.
+pub fn salsa20_rounds(input: &[u32; 16], rounds: usize) -> [u32; 16] {
+    let mut x = *input;
+
+    for _ in 0..rounds / 2 {
+        // Column round.
+        quarter_round(&mut x, 0, 4, 8, 12);
+        quarter_round(&mut x, 5, 9, 13, 1);
+        quarter_round(&mut x, 10, 14, 2, 6);
+        quarter_round(&mut x, 15, 3, 7, 11);
+
+        // Row round.
+        quarter_round(&mut x, 0, 1, 2, 3);
+        quarter_round(&mut x, 5, 6, 7, 4);
+        quarter_round(&mut x, 10, 11, 8, 9);
+        quarter_round(&mut x, 15, 12, 13, 14);
+    }
+
+    x
+}
+
+// Constants on the diagonal, the key in words 1..5 and 11..15, and the
+// 16-byte input (nonce || counter, or the HSalsa20 nonce) in words 6..10.
+pub(crate) fn key_setup(key: &[u8; KEY_SIZE], input: &[u8; 16]) -> [u32; 16] {
+    let mut bytes = [0u8; 64];
+    bytes[4..20].copy_from_slice(&key[..16]);
+    bytes[24..40].copy_from_slice(input);
+    bytes[44..60].copy_from_slice(&key[16..]);
+
+    let mut state = little_endian_pars_block(&bytes);
+    state[0] = SIGMA[0];
+    state[5] = SIGMA[1];
+    state[10] = SIGMA[2];
+    state[15] = SIGMA[3];
+
+    state
+}
+
+// Salsa20 quarter round on the words at positions (a, b, c, d).
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[b] ^= rotl(z(x[a], x[d]), 7);
+    x[c] ^= rotl(z(x[b], x[a]), 9);
+    x[d] ^= rotl(z(x[c], x[b]), 13);
+    x[a] ^= rotl(z(x[d], x[c]), 18);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    // eSTREAM Salsa20/20 256-bit key vectors, first keystream block.
+    #[test]
+    fn salsa20_estream_set_1_vector_0() {
+        let mut key = [0u8; KEY_SIZE];
+        key[0] = 0x80;
+
+        let result = salsa20(&key, &[0u8; NONCE_SIZE], 0, &[0u8; 64]);
+
+        assert_eq!(
+            (bytes_to_hex(&result.unwrap())),
+            (b"e3be8fdd8beca2e3ea8ef9475b29a6e7\
+               003951e1097a5c38d23b7a5fad9f6844\
+               b22c97559e2723c7cbbd3fe4fc8d9a07\
+               44652a83e72a9c461876af4d7ef1a117".to_vec())
+        );
+    }
+
+    #[test]
+    fn salsa20_zero_key_nonce_msb() {
+        let mut nonce = [0u8; NONCE_SIZE];
+        nonce[0] = 0x80;
+
+        let result = salsa20(&[0u8; KEY_SIZE], &nonce, 0, &[0u8; 64]);
+
+        assert_eq!(
+            (bytes_to_hex(&result.unwrap())),
+            (b"2aba3dc45b4947007b14c851cd694456\
+               b303ad59a465662803006705673d6c3e\
+               29f1d3510dfc0405463c03414e0e07e3\
+               59f1f1816c68b2434a19d3eee0464873".to_vec())
+        );
+    }
+
+    #[test]
+    fn salsa20_four_blocks_streamed() {
+        let key = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+            0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10,
+            0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18,
+            0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20,
+        ];
+        let nonce = [0x03, 0x01, 0x04, 0x01, 0x05, 0x09, 0x02, 0x06];
+
+        let mut context = Salsa20::new(&key, &nonce, 0);
+        let mut result = Vec::new();
+        for chunk in [0u8; 256].chunks(37) {
+            result.extend(context.update(chunk).unwrap());
+        }
+
+        assert_eq!(
+            (bytes_to_hex(&result)),
+            (b"6ebcbdbf76fccc64ab05542bee8a67cb\
+               c28fa2e141fbefbb3a2f9b221909c8d7\
+               d4295258cb539770dd24d7ac3443769f\
+               fa27a50e60644264dc8b6b612683372e\
+               085d0a12bf240b189ce2b78289862b56\
+               fdc9fcffc33bef9325a2e81b98fb3fb9\
+               aa04cf434615ceffeb985c1cb08d8440\
+               e90b1d56ddeaea16d9e15affff1f698c\
+               483c7a466af1fe062574adfd2b06a62b\
+               4d98440719ea776385c470349a7ed696\
+               9583463ed5d26b8fefccb205da0f5bfa\
+               98c77812fe756b09eacc282aa42f4baf\
+               a79633189046e2b20f35b3e0e54aa3b9\
+               29e23c0f47dc7bcd4f928b2a9764be7d\
+               4b8a50f980a50b35ad8087375e0c556e\
+               cbe6a7161e8653ce9391e1e6710ed4f1".to_vec())
+        );
+    }
+
+    #[test]
+    fn counter_carries_into_the_high_word() {
+        let key = [0x42u8; KEY_SIZE];
+        let nonce = [0x24u8; NONCE_SIZE];
+
+        let mut context = Salsa20::new(&key, &nonce, 0xffff_ffff);
+        context.update(&[0u8; 64]).unwrap();
+        let result = context.update(&[0u8; 64]).unwrap();
+
+        let expected = salsa20(&key, &nonce, 1 << 32, &[0u8; 64]).unwrap();
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn counter_overflow_is_an_error() {
+        let key = [0u8; KEY_SIZE];
+        let nonce = [0u8; NONCE_SIZE];
+
+        let mut context = Salsa20::new(&key, &nonce, u64::MAX);
+        let overflow = Err(Salsa20Error::CounterOverflow);
+        assert_eq!((context.update(&[0u8; 65])), (overflow));
+        assert!(context.update(&[0u8; 64]).is_ok());
+        assert_eq!((context.update(&[0u8])), (overflow));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::poly1305::{ poly1305, Poly1305 };
+use crate::salsa20::{ Salsa20, Salsa20Error, KEY_SIZE, XNONCE_SIZE };
+use crate::utils::ct_eq;
+
+/// secretbox tag size in bytes.
+pub const TAG_SIZE: usize = 16;
+
+/// secretbox (XSalsa20-Poly1305): encrypt and authenticate, returns
+/// tag || ciphertext.
+///
+/// # Description
+/// - The first 32 bytes of the XSalsa20 keystream are the one-time
+///   Poly1305 key, the plaintext is encrypted with the keystream from
+///   byte 32 on (the rest of block 0, then block 1, ...).
+/// - The tag is Poly1305 of the ciphertext alone, there is no associated
+///   data.
+/// - The layout tag || ciphertext is that of libsodium's
+///   `crypto_secretbox_easy`, byte for byte.
+///
+/// # Reference
+/// [Cryptography in NaCl](https://cr.yp.to/highspeed/naclcrypto-20090310.pdf),
+/// section 9.
+pub fn secretbox_seal(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; XNONCE_SIZE], 
+    plaintext: &[u8]
+) -> Vec<u8> {
+    let mut cipher = Salsa20::new_xsalsa(key, nonce, 0);
+    let mac_key = one_time_key(&mut cipher);
+    let ciphertext = cipher.update(plaintext)
+        .expect("a 64-bit counter from 0 does not run out");
+
+    let mut sealed = poly1305(&mac_key, &ciphertext).to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    sealed
+}
+
+/// secretbox (XSalsa20-Poly1305): verify and decrypt tag || ciphertext.
+///
+/// Fails closed: on a tag mismatch no plaintext is produced at all.
+pub fn secretbox_open(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; XNONCE_SIZE], 
+    sealed: &[u8]
+) -> Result<Vec<u8>, Salsa20Error> {
+    if sealed.len() < TAG_SIZE {
+        let len = sealed.len();
+        return Err(Salsa20Error::CiphertextTooShort { len });
+    }
+
+    let (tag, ciphertext) = sealed.split_at(TAG_SIZE);
+    let mut cipher = Salsa20::new_xsalsa(key, nonce, 0);
+
+    let mut mac = Poly1305::new(&one_time_key(&mut cipher));
+    mac.update(ciphertext);
+    if !ct_eq(&mac.finalize(), tag) {
+        return Err(Salsa20Error::AuthenticationFailed);
+    }
+
+    cipher.update(ciphertext)
+}
+
+// Poly1305 key: the first 32 keystream bytes.
+fn one_time_key(cipher: &mut Salsa20) -> [u8; 32] {
+    let keystream = cipher.update(&[0u8; 32])
+        .expect("a 64-bit counter from 0 does not run out");
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&keystream);
+    key
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::{ bytes_to_hex, hex_to_bytes };
+
+    // NaCl tests/secretbox.c, with the 16-byte tag in front as in
+    // crypto_secretbox_easy.
+    const KEY: [u8; KEY_SIZE] = [
+        0x1b, 0x27, 0x55, 0x64, 0x73, 0xe9, 0x85, 0xd4,
+        0x62, 0xcd, 0x51, 0x19, 0x7a, 0x9a, 0x46, 0xc7,
+        0x60, 0x09, 0x54, 0x9e, 0xac, 0x64, 0x74, 0xf2,
+        0x06, 0xc4, 0xee, 0x08, 0x44, 0xf6, 0x83, 0x89,
+    ];
+
+    const NONCE: [u8; XNONCE_SIZE] = [
+        0x69, 0x69, 0x6e, 0xe9, 0x55, 0xb6, 0x2b, 0x73,
+        0xcd, 0x62, 0xbd, 0xa8, 0x75, 0xfc, 0x73, 0xd6,
+        0x82, 0x19, 0xe0, 0x03, 0x6b, 0x7a, 0x0b, 0x37,
+    ];
+
+    const PLAINTEXT: &[u8] = b"be075fc53c81f2d5cf141316ebeb0c7b\
+                               5228c52a4c62cbd44b66849b64244ffc\
+                               e5ecbaaf33bd751a1ac728d45e6c6129\
+                               6cdc3c01233561f41db66cce314adb31\
+                               0e3be8250c46f06dceea3a7fa1348057\
+                               e2f6556ad6b1318a024a838f21af1fde\
+                               048977eb48f59ffd4924ca1c60902e52\
+                               f0a089bc76897040e082f93776384864\
+                               5e0705";
+
+    #[test]
+    fn secretbox_nacl_vector() {
+        let plaintext = hex_to_bytes(PLAINTEXT);
+
+        let sealed = secretbox_seal(&KEY, &NONCE, &plaintext);
+        assert_eq!(
+            (bytes_to_hex(&sealed)),
+            (b"f3ffc7703f9400e52a7dfb4b3d3305d9\
+               8e993b9f48681273c29650ba32fc76ce\
+               48332ea7164d96a4476fb8c531a1186a\
+               c0dfc17c98dce87b4da7f011ec48c972\
+               71d2c20f9b928fe2270d6fb863d51738\
+               b48eeee314a7cc8ab932164548e526ae\
+               90224368517acfeabd6bb3732bc0e9da\
+               99832b61ca01b6de56244a9e88d5f9b3\
+               7973f622a43d14a6599b1f654cb45a74\
+               e355a5".to_vec())
+        );
+
+        let opened = secretbox_open(&KEY, &NONCE, &sealed).unwrap();
+        assert_eq!((opened), (plaintext));
+    }
+
+    #[test]
+    fn empty_message_is_only_a_tag() {
+        let sealed = secretbox_seal(&KEY, &NONCE, b"");
+        assert_eq!((sealed.len()), (TAG_SIZE));
+
+        let opened = secretbox_open(&KEY, &NONCE, &sealed).unwrap();
+        assert_eq!((opened), (Vec::<u8>::new()));
+    }
+
+    #[test]
+    fn tampering_fails_authentication() {
+        let sealed = secretbox_seal(&KEY, &NONCE, &hex_to_bytes(PLAINTEXT));
+        let failed = Err(Salsa20Error::AuthenticationFailed);
+
+        for i in [0, 15, 16, sealed.len() - 1] {
+            let mut bad = sealed.clone();
+            bad[i] ^= 0x01;
+            assert_eq!((secretbox_open(&KEY, &NONCE, &bad)), (failed));
+        }
+
+        let mut nonce = NONCE;
+        nonce[23] ^= 0x01;
+        assert_eq!((secretbox_open(&KEY, &nonce, &sealed)), (failed));
+    }
+
+    #[test]
+    fn short_box_is_an_error() {
+        let result = secretbox_open(&KEY, &NONCE, &[0u8; 15]);
+
+        let expected = Err(Salsa20Error::CiphertextTooShort { len: 15 });
+        assert_eq!((result), (expected));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::salsa20::salsa20::key_setup;
+use crate::salsa20::{
+    salsa20_rounds, Salsa20, Salsa20Error, KEY_SIZE, NONCE_SIZE
+};
+
+/// XSalsa20 nonce size in bytes.
+pub const XNONCE_SIZE: usize = 24;
+
+/// HSalsa20: derive a 256-bit subkey from a key and a 128-bit nonce.
+///
+/// # Description
+/// - The state is set up like Salsa20, with the 16-byte nonce in place of
+///   the nonce and block counter.
+/// - After the 20 rounds, without the feed-forward addition, the diagonal
+///   (words 0, 5, 10, 15) followed by words 6..10 are the subkey.
+///
+/// # Reference
+/// [Extending the Salsa20 nonce](https://cr.yp.to/snuffle/xsalsa-20110204.pdf),
+/// section 2.
+pub fn hsalsa20(key: &[u8; KEY_SIZE], nonce: &[u8; 16]) -> [u8; KEY_SIZE] {
+    let x = salsa20_rounds(&key_setup(key, nonce), 20);
+    let words = [x[0], x[5], x[10], x[15], x[6], x[7], x[8], x[9]];
+
+    let mut out = [0u8; KEY_SIZE];
+    for i in 0..8 {
+        out[4 * i..4 * i + 4].copy_from_slice(&words[i].to_le_bytes());
+    }
+
+    out
+}
+
+impl Salsa20 {
+    /// XSalsa20: Salsa20 under the HSalsa20 subkey of the first 16 nonce
+    /// bytes, with the last 8 nonce bytes as the 64-bit nonce.
+    pub fn new_xsalsa(
+        key: &[u8; KEY_SIZE], 
+        nonce: &[u8; XNONCE_SIZE], 
+        counter: u64
+    ) -> Self {
+        let (subkey, salsa_nonce) = xsalsa20_setup(key, nonce);
+        Salsa20::new(&subkey, &salsa_nonce, counter)
+    }
+}
+
+/// XSalsa20: encrypt or decrypt a complete message, starting at block
+/// `counter`.
+pub fn xsalsa20(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; XNONCE_SIZE], 
+    counter: u64, 
+    data: &[u8]
+) -> Result<Vec<u8>, Salsa20Error> {
+    Salsa20::new_xsalsa(key, nonce, counter).update(data)
+}
+
+// Subkey and 64-bit nonce of the inner Salsa20.
+fn xsalsa20_setup(
+    key: &[u8; KEY_SIZE], 
+    nonce: &[u8; XNONCE_SIZE]
+) -> ([u8; KEY_SIZE], [u8; NONCE_SIZE]) {
+    let mut hnonce = [0u8; 16];
+    hnonce.copy_from_slice(&nonce[..16]);
+
+    let mut salsa_nonce = [0u8; NONCE_SIZE];
+    salsa_nonce.copy_from_slice(&nonce[16..]);
+
+    (hsalsa20(key, &hnonce), salsa_nonce)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    const SHARED: [u8; KEY_SIZE] = [
+        0x4a, 0x5d, 0x9d, 0x5b, 0xa4, 0xce, 0x2d, 0xe1,
+        0x72, 0x8e, 0x3b, 0xf4, 0x80, 0x35, 0x0f, 0x25,
+        0xe0, 0x7e, 0x21, 0xc9, 0x47, 0xd1, 0x9e, 0x33,
+        0x76, 0xf0, 0x9b, 0x3c, 0x1e, 0x16, 0x17, 0x42,
+    ];
+
+    // NaCl tests/core1.c: the first key of crypto_box from a shared secret.
+    #[test]
+    fn hsalsa20_nacl_core1() {
+        let result = hsalsa20(&SHARED, &[0u8; 16]);
+
+        assert_eq!(
+            (bytes_to_hex(&result)),
+            (b"1b27556473e985d462cd51197a9a46c7\
+               6009549eac6474f206c4ee0844f68389")
+        );
+    }
+
+    // NaCl tests/core2.c: the second key, from the first 16 nonce bytes.
+    #[test]
+    fn hsalsa20_nacl_core2() {
+        let first_key = hsalsa20(&SHARED, &[0u8; 16]);
+        let nonce = [
+            0x69, 0x69, 0x6e, 0xe9, 0x55, 0xb6, 0x2b, 0x73,
+            0xcd, 0x62, 0xbd, 0xa8, 0x75, 0xfc, 0x73, 0xd6,
+        ];
+
+        let result = hsalsa20(&first_key, &nonce);
+
+        assert_eq!(
+            (bytes_to_hex(&result)),
+            (b"dc908dda0b9344a953629b7338207788\
+               80f3ceb421bb61b91cbd4c3e66256ce4")
+        );
+    }
+
+    #[test]
+    fn xsalsa20_zeros() {
+        let key = b"this is 32-byte key for xsalsa20";
+        let nonce = b"24-byte nonce for xsalsa";
+
+        let result = xsalsa20(key, nonce, 0, &[0u8; 64]).unwrap();
+
+        assert_eq!(
+            (bytes_to_hex(&result)),
+            (b"4848297feb1fb52fb66d81609bd547fa\
+               bcbe7026edc8b5e5e449d088bfa69c08\
+               8f5d8da1d791267c2c195a7f8cae9c4b\
+               4050d08ce6d3a151ec265f3a58e47648".to_vec())
+        );
+    }
+
+    #[test]
+    fn xsalsa20_hello_world() {
+        let key = b"this is 32-byte key for xsalsa20";
+        let nonce = b"24-byte nonce for xsalsa";
+
+        let result = xsalsa20(key, nonce, 0, b"Hello world!").unwrap();
+
+        assert_eq!((bytes_to_hex(&result)), (b"002d4513843fc240c401e541"));
+    }
+}
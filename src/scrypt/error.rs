@@ -0,0 +1,55 @@
+use std::fmt;
+use crate::pbkdf2::Pbkdf2Error;
+
+/// Errors of scrypt parameter validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScryptError {
+    /// The cost parameter N must be a power of two greater than 1.
+    InvalidCost { n: u64 },
+    /// N must be less than 2^(16 * r).
+    CostTooLarge { n: u64, r: u32 },
+    /// The block size parameter r must be at least 1.
+    ZeroBlockSize,
+    /// The parallelization parameter p must be at least 1.
+    ZeroParallelism,
+    /// r * p must be less than 2^30.
+    ParallelismTooLarge { r: u32, p: u32 },
+    /// 128 * r * N (or 128 * r * p) bytes do not fit in memory.
+    MemoryTooLarge,
+    /// The surrounding PBKDF2-HMAC-SHA256 call rejected the output length.
+    Pbkdf2(Pbkdf2Error),
+}
+
+impl fmt::Display for ScryptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScryptError::InvalidCost { n } => {
+                write!(f, "scrypt N = {n} is not a power of two greater than 1")
+            }
+            ScryptError::CostTooLarge { n, r } => {
+                write!(f, "scrypt N = {n} must be less than 2^(16 * {r})")
+            }
+            ScryptError::ZeroBlockSize => {
+                write!(f, "scrypt block size r must be at least 1")
+            }
+            ScryptError::ZeroParallelism => {
+                write!(f, "scrypt parallelization p must be at least 1")
+            }
+            ScryptError::ParallelismTooLarge { r, p } => {
+                write!(f, "scrypt r * p = {r} * {p} must be less than 2^30")
+            }
+            ScryptError::MemoryTooLarge => {
+                write!(f, "scrypt parameters need more memory than addressable")
+            }
+            ScryptError::Pbkdf2(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ScryptError {}
+
+impl From<Pbkdf2Error> for ScryptError {
+    fn from(error: Pbkdf2Error) -> Self {
+        ScryptError::Pbkdf2(error)
+    }
+}
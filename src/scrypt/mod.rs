@@ -0,0 +1,12 @@
+pub mod error;
+pub mod salsa;
+pub mod block_mix;
+pub mod ro_mix;
+#[allow(clippy::module_inception)]
+pub mod scrypt;
+
+pub use error::ScryptError;
+pub use salsa::salsa20_8;
+pub use block_mix::block_mix;
+pub use ro_mix::ro_mix;
+pub use scrypt::scrypt;
@@ -0,0 +1,201 @@
+use crate::padd_pars::little_endian_pars_block;
+use crate::pbkdf2::pbkdf2_with;
+use crate::scrypt::{ ro_mix, ScryptError };
+use crate::sha256::Sha256;
+
+/// scrypt password-based key derivation.
+///
+/// # Arguments
+/// - `password`, `salt`: As byte slices (`&[u8]`).
+/// - `n`: CPU/memory cost, a power of two greater than 1.
+/// - `r`: Block size; every ROMix block is `128 * r` bytes.
+/// - `p`: Parallelization, the number of independent ROMix runs.
+/// - `out_len`: Derived key length in bytes.
+///
+/// # Description
+/// - `B = PBKDF2-HMAC-SHA256(password, salt, 1, p * 128 * r)`.
+/// - Each of the `p` chunks of `B` goes through `ro_mix`.
+/// - `DK = PBKDF2-HMAC-SHA256(password, B, 1, out_len)`.
+///
+/// # Returns
+/// The derived key, or a `ScryptError` naming the rejected parameter.
+///
+/// # Reference
+/// [RFC 7914](https://www.rfc-editor.org/rfc/rfc7914), section 6.
+pub fn scrypt(
+    password: &[u8], 
+    salt: &[u8], 
+    n: u64, 
+    r: u32, 
+    p: u32, 
+    out_len: usize
+) -> Result<Vec<u8>, ScryptError> {
+    check_params(n, r, p)?;
+
+    let chunk_len = 128 * r as usize;
+    let b_len = p as usize * chunk_len;
+    let mut b = pbkdf2_with::<Sha256>(password, salt, 1, b_len)?;
+
+    for chunk in b.chunks_mut(chunk_len) {
+        let blocks: Vec<[u32; 16]> = chunk
+            .chunks(64)
+            .map(little_endian_pars_block)
+            .collect();
+
+        let mixed = ro_mix(&blocks, n as usize);
+
+        for (i, block) in mixed.iter().enumerate() {
+            for k in 0..16 {
+                let bytes = block[k].to_le_bytes();
+                for l in 0..4 {
+                    chunk[i * 64 + k * 4 + l] = bytes[l];
+                }
+            }
+        }
+    }
+
+    Ok(pbkdf2_with::<Sha256>(password, &b, 1, out_len)?)
+}
+
+// Parameter limits of RFC 7914, plus what the platform can allocate.
+fn check_params(n: u64, r: u32, p: u32) -> Result<(), ScryptError> {
+    if r == 0 {
+        return Err(ScryptError::ZeroBlockSize);
+    }
+
+    if p == 0 {
+        return Err(ScryptError::ZeroParallelism);
+    }
+
+    if n < 2 || n & (n - 1) != 0 {
+        return Err(ScryptError::InvalidCost { n });
+    }
+
+    if r < 4 && n >= 1u64 << (16 * r) {
+        return Err(ScryptError::CostTooLarge { n, r });
+    }
+
+    if r as u64 * p as u64 >= 1 << 30 {
+        return Err(ScryptError::ParallelismTooLarge { r, p });
+    }
+
+    // V holds n blocks of 128 * r bytes, B holds p of them.
+    let block_len = 128usize.checked_mul(r as usize);
+    let v_len = usize::try_from(n)
+        .ok()
+        .and_then(|n| block_len.and_then(|len| len.checked_mul(n)));
+    let b_len = block_len.and_then(|len| len.checked_mul(p as usize));
+    if v_len.is_none() || b_len.is_none() {
+        return Err(ScryptError::MemoryTooLarge);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pbkdf2::Pbkdf2Error;
+
+    #[test]
+    fn rfc7914_empty_password() {
+        let result = scrypt(b"", b"", 16, 1, 1, 64);
+        let expected = vec![
+            0x77, 0xd6, 0x57, 0x62, 0x38, 0x65, 0x7b, 0x20,
+            0x3b, 0x19, 0xca, 0x42, 0xc1, 0x8a, 0x04, 0x97,
+            0xf1, 0x6b, 0x48, 0x44, 0xe3, 0x07, 0x4a, 0xe8,
+            0xdf, 0xdf, 0xfa, 0x3f, 0xed, 0xe2, 0x14, 0x42,
+            0xfc, 0xd0, 0x06, 0x9d, 0xed, 0x09, 0x48, 0xf8,
+            0x32, 0x6a, 0x75, 0x3a, 0x0f, 0xc8, 0x1f, 0x17,
+            0xe8, 0xd3, 0xe0, 0xfb, 0x2e, 0x0d, 0x36, 0x28,
+            0xcf, 0x35, 0xe2, 0x0c, 0x38, 0xd1, 0x89, 0x06,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn rfc7914_password_nacl() {
+        let result = scrypt(b"password", b"NaCl", 1024, 8, 16, 64);
+        let expected = vec![
+            0xfd, 0xba, 0xbe, 0x1c, 0x9d, 0x34, 0x72, 0x00,
+            0x78, 0x56, 0xe7, 0x19, 0x0d, 0x01, 0xe9, 0xfe,
+            0x7c, 0x6a, 0xd7, 0xcb, 0xc8, 0x23, 0x78, 0x30,
+            0xe7, 0x73, 0x76, 0x63, 0x4b, 0x37, 0x31, 0x62,
+            0x2e, 0xaf, 0x30, 0xd9, 0x2e, 0x22, 0xa3, 0x88,
+            0x6f, 0xf1, 0x09, 0x27, 0x9d, 0x98, 0x30, 0xda,
+            0xc7, 0x27, 0xaf, 0xb9, 0x4a, 0x83, 0xee, 0x6d,
+            0x83, 0x60, 0xcb, 0xdf, 0xa2, 0xcc, 0x06, 0x40,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn rfc7914_pleaseletmein() {
+        let result = scrypt(
+            b"pleaseletmein", b"SodiumChloride", 16384, 8, 1, 64
+        );
+        let expected = vec![
+            0x70, 0x23, 0xbd, 0xcb, 0x3a, 0xfd, 0x73, 0x48,
+            0x46, 0x1c, 0x06, 0xcd, 0x81, 0xfd, 0x38, 0xeb,
+            0xfd, 0xa8, 0xfb, 0xba, 0x90, 0x4f, 0x8e, 0x3e,
+            0xa9, 0xb5, 0x43, 0xf6, 0x54, 0x5d, 0xa1, 0xf2,
+            0xd5, 0x43, 0x29, 0x55, 0x61, 0x3f, 0x0f, 0xcf,
+            0x62, 0xd4, 0x97, 0x05, 0x24, 0x2a, 0x9a, 0xf9,
+            0xe6, 0x1e, 0x85, 0xdc, 0x0d, 0x65, 0x1e, 0x40,
+            0xdf, 0xcf, 0x01, 0x7b, 0x45, 0x57, 0x58, 0x87,
+        ];
+
+        assert_eq!((result), (Ok(expected)));
+    }
+
+    #[test]
+    fn rejects_cost_that_is_not_a_power_of_two() {
+        let result = scrypt(b"password", b"salt", 1000, 8, 1, 32);
+        assert_eq!((result), (Err(ScryptError::InvalidCost { n: 1000 })));
+
+        let result = scrypt(b"password", b"salt", 1, 8, 1, 32);
+        assert_eq!((result), (Err(ScryptError::InvalidCost { n: 1 })));
+    }
+
+    #[test]
+    fn rejects_cost_too_large_for_block_size() {
+        let result = scrypt(b"password", b"salt", 1 << 16, 1, 1, 32);
+        let error = ScryptError::CostTooLarge { n: 1 << 16, r: 1 };
+
+        assert_eq!((result), (Err(error)));
+    }
+
+    #[test]
+    fn rejects_zero_block_size_and_parallelism() {
+        let result = scrypt(b"password", b"salt", 16, 0, 1, 32);
+        assert_eq!((result), (Err(ScryptError::ZeroBlockSize)));
+
+        let result = scrypt(b"password", b"salt", 16, 1, 0, 32);
+        assert_eq!((result), (Err(ScryptError::ZeroParallelism)));
+    }
+
+    #[test]
+    fn rejects_too_much_parallelism() {
+        let result = scrypt(b"password", b"salt", 16, 1 << 15, 1 << 15, 32);
+        let error = ScryptError::ParallelismTooLarge { r: 1 << 15, p: 1 << 15 };
+
+        assert_eq!((result), (Err(error)));
+    }
+
+    #[test]
+    fn rejects_unaddressable_memory() {
+        let result = scrypt(b"password", b"salt", 1 << 62, 8, 1, 32);
+        assert_eq!((result), (Err(ScryptError::MemoryTooLarge)));
+    }
+
+    #[test]
+    fn reports_pbkdf2_output_errors() {
+        let max = u32::MAX as usize * 32;
+        let result = scrypt(b"password", b"salt", 16, 1, 1, max + 1);
+        let error = Pbkdf2Error::OutputTooLong { len: max + 1, max };
+
+        assert_eq!((result), (Err(ScryptError::Pbkdf2(error))));
+    }
+}
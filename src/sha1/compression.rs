@@ -0,0 +1,200 @@
+use crate::utils::{ rotl, z };
+use crate::sha1::{ k, f };
+
+/// SHA1 initial hash value H(0).
+pub const IV: [u32; 5] = [
+    0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0,
+];
+
+/// SHA1 Message Digestion Algorithm.
+///
+/// # Argument
+/// Takes scheduled message as Vec<[u32; 80]>, then compression starts.
+///
+/// # Description
+/// - Initialize (h0, h1, h2, h3, h4) with the hash value H(0).
+/// - Run `compress_block` on every scheduled block.
+///
+/// # Returns
+/// Final 8-word digest as [u32; 5].
+///
+/// # Reference
+/// Based on the FIPS PUB 180-4 specification:
+/// [FIPS PUB 180-4](https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.180-4.pdf)
+pub fn compress(msg: &[[u32; 80]]) -> [u32; 5] {
+    let mut h = IV;
+
+    for m in msg {
+        compress_block(&mut h, m);
+    }
+
+    // Digested state.
+    h
+}
+
+/// SHA1 compression of a single scheduled block.
+///
+/// # Description
+/// - Initialize (a, b, c, d, e), with the 5 working variables; 
+///   (h0, h1, h2, h3, h4).
+/// - Implement all round operations (Ch, Maj, Parity, ROTL).
+/// - Compute the i-th intermediate hash value H(i) in place.
+pub fn compress_block(state: &mut [u32; 5], m: &[u32; 80]) {
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut e = state[4];
+
+    // Implement round operations.
+    for t in 0..80 {
+
+        let temp = z(
+            z(
+                z(
+                    z(
+                        rotl(a, 5), 
+                        f(t, b, c, d)), 
+                    e), 
+                k(t)), 
+            m[t as usize]
+        );
+
+        e = d;
+        d = c;
+        c = rotl(b, 30);
+        b = a;
+        a = temp;
+
+    };
+
+    // Compute the i-th intermediate hash value.
+    state[0] = z(a, state[0]);
+    state[1] = z(b, state[1]);
+    state[2] = z(c, state[2]);
+    state[3] = z(d, state[3]);
+    state[4] = z(e, state[4]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::padd_pars::{
+        big_endian_padd, big_endian_pars
+    };
+    use crate::sha1::schedule;
+
+    #[test]
+    fn sha1_computes_empty_string() {
+        let msg = b"";
+        let padded = big_endian_padd(msg);
+        let parsed = big_endian_pars(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+
+        let expected = [
+            0xda39a3ee, 0x5e6b4b0d, 0x3255bfef, 0x95601890, 0xafd80709
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha1_computes_abc() {
+        let msg = b"abc";
+        let padded = big_endian_padd(msg);
+        let parsed = big_endian_pars(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+
+        let expected = [
+            0xa9993e36, 0x4706816a, 0xba3e2571, 0x7850c26c, 0x9cd0d89d
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha1_computes_448bits() {
+        let msg = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        let padded = big_endian_padd(msg);
+        let parsed = big_endian_pars(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+
+        let expected = [
+            0x84983e44, 0x1c3bd26e, 0xbaae4aa1, 0xf95129e5, 0xe54670f1
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha1_computes_8_000_000bits() {
+        let msg = b"a".repeat(1_000_000);
+        let padded = big_endian_padd(&msg);
+        let parsed = big_endian_pars(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+
+        let expected = [
+            0x34aa973c, 0xd4c4daa4, 0xf61eeb2b, 0xdbad2731, 0x6534016f
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha1_computes_896bits() {
+        let msg = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                    hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"
+        ;
+        let padded = big_endian_padd(msg);
+        let parsed = big_endian_pars(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+
+        let expected = [
+            0xa49b2446, 0xa02c645b, 0xf419f995, 0xb6709125, 0x3a04a259
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha1_computes_brown_fox_over_dog() {
+        let msg = b"The quick brown fox jumps over the lazy dog";
+        let padded = big_endian_padd(msg);
+        let parsed = big_endian_pars(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+
+        let expected = [
+            0x2fd4e1c6, 0x7a2d28fc, 0xed849ee1, 0xbb76e739, 0x1b93eb12
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha1_computes_brown_fox_over_cog() {
+        let msg = b"The quick brown fox jumps over the lazy cog";
+        let padded = big_endian_padd(msg);
+        let parsed = big_endian_pars(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+
+        let expected = [
+            0xde9f2c7f, 0xd25e1b3a, 0xfad3e85a, 0x0bd17d9b, 0x100db4b3
+        ];
+
+        assert_eq!((result), (expected));
+    }
+}
\ No newline at end of file
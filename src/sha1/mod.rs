@@ -1,8 +1,12 @@
+pub mod compression;
 pub mod constants;
 pub mod functions;
 pub mod schedule;
+#[allow(clippy::module_inception)]
 pub mod sha1;
 
 pub use constants::{ k };
 pub use functions::{ f };
-pub use schedule::schedule;
\ No newline at end of file
+pub use compression::{ compress, compress_block, IV };
+pub use schedule::{ schedule, schedule_block };
+pub use sha1::{ sha1, Sha1 };
\ No newline at end of file
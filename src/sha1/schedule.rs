@@ -17,18 +17,21 @@ pub fn schedule(parsed: &[[u32; 16]]) -> Vec<[u32; 80]> {
     let mut scheduled: Vec<[u32; 80]> = Vec::new();
 
     for block in parsed {
-        let mut m = [0u32; 80];
+        scheduled.push(schedule_block(block));
+    }
+    scheduled
 
-        for i in 0..16 {
-            m[i] = block[i];
-        };
+}
 
-        for t in 16..80 {
-            m[t] = rotl(m[t-3] ^ m[t-8] ^ m[t-14] ^ m[t-16], 1);
-        }
+/// Expand a single 512-bit block into its 80-word message schedule.
+pub fn schedule_block(block: &[u32; 16]) -> [u32; 80] {
+    let mut m = [0u32; 80];
 
-        scheduled.push(m);
+    m[..16].copy_from_slice(block);
+
+    for t in 16..80 {
+        m[t] = rotl(m[t-3] ^ m[t-8] ^ m[t-14] ^ m[t-16], 1);
     }
-    scheduled
 
+    m
 }
\ No newline at end of file
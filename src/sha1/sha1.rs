@@ -1,197 +1,166 @@
-use crate::utils::{ rotl, z };
-use crate::sha1::{ k, f };
+use crate::digest::Digest;
+use crate::padd_pars::{
+    big_endian_padd_tail, big_endian_pars_block, BlockBuffer
+};
 
-/// SHA1 Message Digestion Algorithm.
+use crate::sha1::{
+    schedule_block, compress_block, IV
+};
+
+/// SHA-1 streaming context.
+///
+/// Buffers at most one partial 64-byte block; every block that fills up is
+/// parsed, scheduled and compressed straight away.
+#[derive(Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    buffer: BlockBuffer<64>,
+    msg_len: u64,
+}
+
+impl Sha1 {
+    pub fn new() -> Self {
+        Sha1 {
+            state: IV,
+            buffer: BlockBuffer::new(),
+            msg_len: 0,
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.msg_len = self.msg_len.wrapping_add(data.len() as u64);
+
+        let state = &mut self.state;
+        self.buffer.feed(data, |bytes| process_block(state, bytes));
+    }
+
+    /// Pad the buffered tail and return the 20-byte digest.
+    ///
+    /// Each state word is written out most significant byte first.
+    pub fn finalize(mut self) -> [u8; 20] {
+        let padded = big_endian_padd_tail(self.buffer.tail(), self.msg_len);
+
+        let mut j = 0;
+        while j < padded.len() {
+            process_block(&mut self.state, &padded[j..j + 64]);
+            j += 64;
+        }
+
+        let mut bytes = [0u8; 20];
+        for i in 0..5 {
+            let word = self.state[i];
+            let j = i * 4;
+
+            bytes[j] =     (word >> 24) as u8;
+            bytes[j + 1] = (word >> 16) as u8;
+            bytes[j + 2] = (word >> 8)  as u8;
+            bytes[j + 3] = (word)       as u8;
+        }
+
+        bytes
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha1 {
+    const OUTPUT_SIZE: usize = 20;
+    const BLOCK_SIZE: usize = 64;
+    type Output = [u8; 20];
+
+    fn new() -> Self { Sha1::new() }
+    fn update(&mut self, data: &[u8]) { Sha1::update(self, data) }
+    fn finalize(self) -> [u8; 20] { Sha1::finalize(self) }
+}
+
+/// Parse, schedule, and compress one 64-byte block into the state.
+fn process_block(state: &mut [u32; 5], bytes: &[u8]) {
+    let block = big_endian_pars_block(bytes);
+    let m = schedule_block(&block);
+    compress_block(state, &m);
+}
+
+/// SHA-1: Pads, parses, schedules, and compresses a message into a 160-bit 
+/// hash.
 ///
-/// # Argument
-/// Takes scheduled message as Vec<[u32; 80]>, then compression starts.
+/// # Arguments
+/// - `msg`: Message as a byte slice (`&[u8]`). 
 ///
 /// # Description
-/// - Initialize (a, b, c, d, e), with the 5 working variables; 
-///   (h0, h1, h2, h3, h4).
-/// - Implement all round operations (Ch, Maj, Parity, ROTL).
-/// - Compute the i-th intermediate hash value H(i)
+/// - **Padding:** Big-endian padding to a multiple of 512 bits.
+/// - **Parsing:** Splits the padded message into 16 × 32-bit word blocks.
+/// - **Scheduling:** Expands each block into 80 words using ROTL.
+/// - **Compression:** Updates the five word state across all blocks.
+///
+/// All steps run block by block through the `Sha1` streaming context.
 ///
 /// # Returns
-/// Final 8-word digest as [u32; 5].
+/// A 20-byte array representing the 160-bit hash.
 ///
 /// # Reference
-/// Based on the FIPS PUB 180-4 specification:
 /// [FIPS PUB 180-4](https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.180-4.pdf)
-fn sha1(msg: &[[u32; 80]]) -> [u32; 5] {
-    // Initialize working variables.
-    let mut h0: u32 = 0x67452301;
-    let mut h1: u32 = 0xefcdab89;
-    let mut h2: u32 = 0x98badcfe;
-    let mut h3: u32 = 0x10325476;
-    let mut h4: u32 = 0xc3d2e1f0;
-
-    for m in msg {
-        let mut a = h0;
-        let mut b = h1;
-        let mut c = h2;
-        let mut d = h3;
-        let mut e = h4;
-
-        // Implement round operations.
-        for t in 0..80 {
-
-            let temp = z(
-                z(
-                    z(
-                        z(
-                            rotl(a, 5), 
-                            f(t, b, c, d)), 
-                        e), 
-                    k(t)), 
-                m[t as usize]
-            );
-
-            e = d;
-            d = c;
-            c = rotl(b, 30);
-            b = a;
-            a = temp;
-
-        };
-
-        // Compute the i-th intermediate hash value.
-        h0 = z(a, h0);
-        h1 = z(b, h1);
-        h2 = z(c, h2);
-        h3 = z(d, h3);
-        h4 = z(e, h4);
-
-    }
-
-    // Digested state.
-    [
-        h0, 
-        h1, 
-        h2, 
-        h3, 
-        h4
-    ]
-
+pub fn sha1(msg: &[u8]) -> [u8; 20] {
+    let mut hasher = Sha1::new();
+    hasher.update(msg);
+    hasher.finalize()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::padd_pars::{
-        big_endian_padd, big_endian_pars
-    };
-    use crate::sha1::schedule;
 
     #[test]
-    fn sha1_computes_empty_string() {
+    fn sha1_digests_empty_string() {
         let msg = b"";
-        let padded = big_endian_padd(msg);
-        let parsed = big_endian_pars(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha1(&scheduled);
-
+        let result = sha1(msg);
         let expected = [
-            0xda39a3ee, 0x5e6b4b0d, 0x3255bfef, 0x95601890, 0xafd80709
+            0xda, 0x39, 0xa3, 0xee, 0x5e, 0x6b, 0x4b, 0x0d,
+            0x32, 0x55, 0xbf, 0xef, 0x95, 0x60, 0x18, 0x90,
+            0xaf, 0xd8, 0x07, 0x09,
         ];
 
         assert_eq!((result), (expected));
     }
 
     #[test]
-    fn sha1_computes_abc() {
+    fn sha1_digests_abc() {
         let msg = b"abc";
-        let padded = big_endian_padd(msg);
-        let parsed = big_endian_pars(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha1(&scheduled);
-
+        let result = sha1(msg);
         let expected = [
-            0xa9993e36, 0x4706816a, 0xba3e2571, 0x7850c26c, 0x9cd0d89d
+            0xa9, 0x99, 0x3e, 0x36, 0x47, 0x06, 0x81, 0x6a,
+            0xba, 0x3e, 0x25, 0x71, 0x78, 0x50, 0xc2, 0x6c,
+            0x9c, 0xd0, 0xd8, 0x9d,
         ];
 
         assert_eq!((result), (expected));
     }
 
     #[test]
-    fn sha1_computes_448bits() {
-        let msg = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
-        let padded = big_endian_padd(msg);
-        let parsed = big_endian_pars(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha1(&scheduled);
-
-        let expected = [
-            0x84983e44, 0x1c3bd26e, 0xbaae4aa1, 0xf95129e5, 0xe54670f1
-        ];
-
-        assert_eq!((result), (expected));
-    }
-
-    #[test]
-    fn sha1_computes_8_000_000bits() {
-        let msg = b"a".repeat(1_000_000);
-        let padded = big_endian_padd(&msg);
-        let parsed = big_endian_pars(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha1(&scheduled);
-
-        let expected = [
-            0x34aa973c, 0xd4c4daa4, 0xf61eeb2b, 0xdbad2731, 0x6534016f
-        ];
-
-        assert_eq!((result), (expected));
-    }
-
-    #[test]
-    fn sha1_computes_896bits() {
-        let msg = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
-                    hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"
-        ;
-        let padded = big_endian_padd(msg);
-        let parsed = big_endian_pars(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha1(&scheduled);
-
-        let expected = [
-            0xa49b2446, 0xa02c645b, 0xf419f995, 0xb6709125, 0x3a04a259
-        ];
-
-        assert_eq!((result), (expected));
-    }
-
-    #[test]
-    fn sha1_computes_brown_fox_over_dog() {
+    fn sha1_digests_brown_fox() {
         let msg = b"The quick brown fox jumps over the lazy dog";
-        let padded = big_endian_padd(msg);
-        let parsed = big_endian_pars(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha1(&scheduled);
-
+        let result = sha1(msg);
         let expected = [
-            0x2fd4e1c6, 0x7a2d28fc, 0xed849ee1, 0xbb76e739, 0x1b93eb12
+            0x2f, 0xd4, 0xe1, 0xc6, 0x7a, 0x2d, 0x28, 0xfc,
+            0xed, 0x84, 0x9e, 0xe1, 0xbb, 0x76, 0xe7, 0x39,
+            0x1b, 0x93, 0xeb, 0x12,
         ];
 
         assert_eq!((result), (expected));
     }
 
     #[test]
-    fn sha1_computes_brown_fox_over_cog() {
-        let msg = b"The quick brown fox jumps over the lazy cog";
-        let padded = big_endian_padd(msg);
-        let parsed = big_endian_pars(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha1(&scheduled);
-
+    fn sha1_digests_one_million_a() {
+        let msg = b"a".repeat(1_000_000);
+        let result = sha1(&msg);
         let expected = [
-            0xde9f2c7f, 0xd25e1b3a, 0xfad3e85a, 0x0bd17d9b, 0x100db4b3
+            0x34, 0xaa, 0x97, 0x3c, 0xd4, 0xc4, 0xda, 0xa4,
+            0xf6, 0x1e, 0xeb, 0x2b, 0xdb, 0xad, 0x27, 0x31,
+            0x65, 0x34, 0x01, 0x6f,
         ];
 
         assert_eq!((result), (expected));
@@ -1,9 +1,14 @@
 pub mod compression;
 pub mod schedule;
+pub mod sha224;
+#[allow(clippy::module_inception)]
 pub mod sha256;
 pub mod to_bytes;
 
-pub use compression::compress;
-pub use schedule::schedule;
+pub use compression::{
+    compress, compress_with_iv, compress_block, H224, H256
+};
+pub use schedule::{ schedule, schedule_block };
 pub use to_bytes::to_bytes;
-pub use sha256::sha256;
\ No newline at end of file
+pub use sha256::{ sha256, Sha256 };
+pub use sha224::{ sha224, Sha224 };
\ No newline at end of file
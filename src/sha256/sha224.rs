@@ -0,0 +1,135 @@
+use crate::digest::Digest;
+use crate::sha256::{ Sha256, H224 };
+use crate::utils::truncate;
+
+/// SHA-224 streaming context.
+///
+/// A `Sha256` context started from `H224`, whose digest is cut down to the
+/// leftmost 28 bytes.
+#[derive(Clone)]
+pub struct Sha224 {
+    inner: Sha256,
+}
+
+impl Sha224 {
+    pub fn new() -> Self {
+        Sha224 {
+            inner: Sha256::with_iv(H224),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad the buffered tail and return the 28-byte digest.
+    pub fn finalize(self) -> [u8; 28] {
+        truncate(&self.inner.finalize())
+    }
+}
+
+impl Default for Sha224 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha224 {
+    const OUTPUT_SIZE: usize = 28;
+    const BLOCK_SIZE: usize = 64;
+    type Output = [u8; 28];
+
+    fn new() -> Self { Sha224::new() }
+    fn update(&mut self, data: &[u8]) { Sha224::update(self, data) }
+    fn finalize(self) -> [u8; 28] { Sha224::finalize(self) }
+}
+
+/// SHA-224: SHA-256 with a different initial hash value, truncated to 224 
+/// bits.
+///
+/// # Arguments
+/// - `msg`: Message as a byte slice (`&[u8]`). 
+///
+/// # Description
+/// - **Padding, Parsing, Scheduling:** Identical to SHA-256.
+/// - **Compression:** Same rounds as SHA-256, started from `H224` instead of
+///   `H256`.
+/// - **Truncation:** Only the leftmost seven state words (28 bytes) are kept.
+///
+/// All steps run block by block through the `Sha224` streaming context.
+///
+/// # Returns
+/// A 28-byte array representing the 224-bit hash.
+///
+/// # Reference
+/// [FIPS PUB 180-4](https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.180-4.pdf)
+pub fn sha224(msg: &[u8]) -> [u8; 28] {
+    let mut hasher = Sha224::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sha224_digests_empty_string() {
+        let msg = b"";
+        let result = sha224(msg);
+        let expected = [
+            0xd1, 0x4a, 0x02, 0x8c, 0x2a, 0x3a, 0x2b, 0xc9,
+            0x47, 0x61, 0x02, 0xbb, 0x28, 0x82, 0x34, 0xc4,
+            0x15, 0xa2, 0xb0, 0x1f, 0x82, 0x8e, 0xa6, 0x2a,
+            0xc5, 0xb3, 0xe4, 0x2f,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha224_digests_abc() {
+        let msg = b"abc";
+        let result = sha224(msg);
+        let expected = [
+            0x23, 0x09, 0x7d, 0x22, 0x34, 0x05, 0xd8, 0x22,
+            0x86, 0x42, 0xa4, 0x77, 0xbd, 0xa2, 0x55, 0xb3,
+            0x2a, 0xad, 0xbc, 0xe4, 0xbd, 0xa0, 0xb3, 0xf7,
+            0xe3, 0x6c, 0x9d, 0xa7,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha224_digests_two_blocks() {
+        let msg = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        let result = sha224(msg);
+        let expected = [
+            0x75, 0x38, 0x8b, 0x16, 0x51, 0x27, 0x76, 0xcc,
+            0x5d, 0xba, 0x5d, 0xa1, 0xfd, 0x89, 0x01, 0x50,
+            0xb0, 0xc6, 0x45, 0x5c, 0xb4, 0xf5, 0x8b, 0x19,
+            0x52, 0x52, 0x25, 0x25,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha224_streams_one_million_a() {
+        let msg = b"a".repeat(1_000_000);
+        let mut hasher = Sha224::new();
+        for chunk in msg.chunks(4_099) {
+            hasher.update(chunk);
+        }
+        let expected = [
+            0x20, 0x79, 0x46, 0x55, 0x98, 0x0c, 0x91, 0xd8,
+            0xbb, 0xb4, 0xc1, 0xea, 0x97, 0x61, 0x8a, 0x4b,
+            0xf0, 0x3f, 0x42, 0x58, 0x19, 0x48, 0xb2, 0xee,
+            0x4e, 0xe7, 0xad, 0x67,
+        ];
+
+        assert_eq!((hasher.finalize()), (expected));
+    }
+}
\ No newline at end of file
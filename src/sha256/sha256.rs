@@ -1,11 +1,89 @@
+use crate::digest::Digest;
 use crate::padd_pars::{
-    big_endian_padd, big_endian_pars
+    big_endian_padd_tail, big_endian_pars_block, BlockBuffer
 };
 
 use crate::sha256::{
-    schedule, compress, to_bytes
+    schedule_block, compress_block, to_bytes, H256
 };
 
+/// SHA-256 streaming context.
+///
+/// # Description
+/// - **update:** Buffers at most one partial 64-byte block; every block that
+///   fills up is parsed, scheduled and compressed straight away.
+/// - **finalize:** Pads the buffered tail with the length of everything fed
+///   through `update` and compresses the last one or two blocks.
+///
+/// Memory use stays constant no matter how long the message is, so large
+/// files and streams can be hashed chunk by chunk.
+#[derive(Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    buffer: BlockBuffer<64>,
+    msg_len: u64,
+}
+
+impl Sha256 {
+    /// Start a new hash with the SHA-256 initial hash value H(0).
+    pub fn new() -> Self {
+        Self::with_iv(H256)
+    }
+
+    /// Start a new hash from a custom initial hash value.
+    pub(crate) fn with_iv(iv: [u32; 8]) -> Self {
+        Sha256 {
+            state: iv,
+            buffer: BlockBuffer::new(),
+            msg_len: 0,
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.msg_len = self.msg_len.wrapping_add(data.len() as u64);
+
+        let state = &mut self.state;
+        self.buffer.feed(data, |bytes| process_block(state, bytes));
+    }
+
+    /// Pad the buffered tail and return the 32-byte digest.
+    pub fn finalize(mut self) -> [u8; 32] {
+        let padded = big_endian_padd_tail(self.buffer.tail(), self.msg_len);
+
+        let mut j = 0;
+        while j < padded.len() {
+            process_block(&mut self.state, &padded[j..j + 64]);
+            j += 64;
+        }
+
+        to_bytes(self.state)
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha256 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 64;
+    type Output = [u8; 32];
+
+    fn new() -> Self { Sha256::new() }
+    fn update(&mut self, data: &[u8]) { Sha256::update(self, data) }
+    fn finalize(self) -> [u8; 32] { Sha256::finalize(self) }
+}
+
+/// Parse, schedule, and compress one 64-byte block into the state.
+fn process_block(state: &mut [u32; 8], bytes: &[u8]) {
+    let block = big_endian_pars_block(bytes);
+    let m = schedule_block(&block);
+    compress_block(state, &m);
+}
+
 /// SHA-256: Pads, parses, schedules, and compresses a message into a 256-bit 
 /// hash.
 ///
@@ -21,6 +99,8 @@ use crate::sha256::{
 /// - **Compression:** Iteratively updates the hash state across all blocks
 ///   to compute the final digest.
 ///
+/// All steps run block by block through the `Sha256` streaming context.
+///
 /// # Returns
 /// A 32-byte array representing the 256-bit hash.
 ///
@@ -30,18 +110,16 @@ use crate::sha256::{
 /// # Reference
 /// [FIPS PUB 180-4](https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.180-4.pdf)
 pub fn sha256(msg: &[u8]) -> [u8; 32] {
-    let padding = big_endian_padd(msg);
-    let parsing = big_endian_pars(padding);
-    let scheduled = schedule(parsing);
-    let digest = compress(scheduled);
-    let bytes = to_bytes(digest);
-
-    bytes
+    let mut hasher = Sha256::new();
+    hasher.update(msg);
+    hasher.finalize()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::padd_pars::{ big_endian_padd, big_endian_pars };
+    use crate::sha256::{ schedule, compress };
 
     #[test]
     fn test_empty_string_and_digest() {
@@ -98,4 +176,63 @@ mod test {
 
         assert_eq!((result), (expected));
     }
+
+    #[test]
+    fn streaming_matches_one_shot_for_every_chunk_size() {
+        let msgs: [&[u8]; 3] = [
+            b"",
+            b"abc",
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+        ];
+        let long = b"a".repeat(1_000);
+
+        for msg in msgs.iter().copied().chain([&long[..]]) {
+            // Whole-message pipeline: pad, parse, schedule, compress.
+            let padding = big_endian_padd(msg);
+            let parsing = big_endian_pars(padding);
+            let expected = to_bytes(compress(schedule(parsing)));
+
+            for chunk_size in 1..=130 {
+                let mut hasher = Sha256::new();
+                for chunk in msg.chunks(chunk_size) {
+                    hasher.update(chunk);
+                }
+
+                assert_eq!((hasher.finalize()), (expected));
+            }
+        }
+    }
+
+    #[test]
+    fn streaming_one_million_a_in_odd_chunks() {
+        let msg = b"a".repeat(1_000_000);
+        let mut hasher = Sha256::new();
+        for chunk in msg.chunks(997) {
+            hasher.update(chunk);
+        }
+        let expected = [
+            0xcd, 0xc7, 0x6e, 0x5c, 0x99, 0x14, 0xfb, 0x92, 
+            0x81, 0xa1, 0xc7, 0xe2, 0x84, 0xd7, 0x3e, 0x67, 
+            0xf1, 0x80, 0x9a, 0x48, 0xa4, 0x97, 0x20, 0x0e, 
+            0x04, 0x6d, 0x39, 0xcc, 0xc7, 0x11, 0x2c, 0xd0,
+        ];
+
+        assert_eq!((hasher.finalize()), (expected));
+    }
+
+    #[test]
+    fn streaming_two_block_vector_split_across_update_calls() {
+        let mut hasher = Sha256::new();
+        hasher.update(b"abcdbcdecdefdefgefghfghighijhijk");
+        hasher.update(b"");
+        hasher.update(b"ijkljklmklmnlmnomnopnopq");
+        let expected = [
+            0x24, 0x8d, 0x6a, 0x61, 0xd2, 0x06, 0x38, 0xb8, 
+            0xe5, 0xc0, 0x26, 0x93, 0x0c, 0x3e, 0x60, 0x39, 
+            0xa3, 0x3c, 0xe4, 0x59, 0x64, 0xff, 0x21, 0x67, 
+            0xf6, 0xec, 0xed, 0xd4, 0x19, 0xdb, 0x06, 0xc1,
+        ];
+
+        assert_eq!((hasher.finalize()), (expected));
+    }
 }
\ No newline at end of file
@@ -19,4 +19,32 @@ pub fn bytes_to_hex(bytes: &[u8]) -> Vec<u8> {
     }
 
     out
-}
\ No newline at end of file
+}
+
+// Hex to bytes: the inverse of `bytes_to_hex`, for test vectors written as
+// hex strings. Expects an even number of lowercase or uppercase digits.
+pub fn hex_to_bytes(hex: &[u8]) -> Vec<u8> {
+    assert!(hex.len().is_multiple_of(2), "hex string must have an even length");
+    let mut out = Vec::with_capacity(hex.len() / 2);
+
+    let mut i = 0;
+    while i < hex.len() {
+        // High nibble, then low nibble.
+        let hi = hex_digit(hex[i]);
+        let lo = hex_digit(hex[i + 1]);
+        out.push((hi << 4) | lo);
+
+        i += 2;
+    }
+
+    out
+}
+
+fn hex_digit(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => panic!("invalid hex digit {:?}", c as char),
+    }
+}
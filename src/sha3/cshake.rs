@@ -0,0 +1,180 @@
+use crate::sha3::{ bytepad, encode_string, Keccak, XofReader };
+
+// cSHAKE customizable SHAKE (NIST SP 800-185, section 3).
+//
+// - `function_name` is reserved for functions defined by NIST (KMAC,
+//   TupleHash, ...), `customization` is free for the caller.
+// - With both strings empty cSHAKE is plain SHAKE, otherwise their encoding
+//   is absorbed first as a full rate block, and the domain suffix is 0x04.
+
+// Absorbing engine shared by cSHAKE and the functions built on top of it.
+pub(crate) fn cshake_engine(
+    rate: usize, 
+    function_name: &[u8], 
+    customization: &[u8]
+) -> Keccak {
+    if function_name.is_empty() && customization.is_empty() {
+        return Keccak::init(rate, 200 - rate, 0x1f, 0);
+    }
+
+    let mut prefix = encode_string(function_name);
+    prefix.extend_from_slice(&encode_string(customization));
+
+    let mut keccak = Keccak::init(rate, 200 - rate, 0x04, 0);
+    keccak.update(&bytepad(&prefix, rate));
+    keccak
+}
+
+/// cSHAKE128 absorbing context: rate 168 bytes, capacity 32 bytes.
+#[derive(Clone)]
+pub struct CShake128 {
+    inner: Keccak,
+}
+
+impl CShake128 {
+    pub fn new(function_name: &[u8], customization: &[u8]) -> Self {
+        CShake128 {
+            inner: cshake_engine(168, function_name, customization),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad and absorb the buffered tail, then return the output reader.
+    pub fn finalize_xof(self) -> XofReader {
+        self.inner.finalize_xof()
+    }
+}
+
+/// cSHAKE128: first `out_len` bytes of output for `msg`.
+pub fn cshake128(
+    msg: &[u8], 
+    out_len: usize, 
+    function_name: &[u8], 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut hasher = CShake128::new(function_name, customization);
+    hasher.update(msg);
+
+    let mut out = vec![0u8; out_len];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+/// cSHAKE256 absorbing context: rate 136 bytes, capacity 64 bytes.
+#[derive(Clone)]
+pub struct CShake256 {
+    inner: Keccak,
+}
+
+impl CShake256 {
+    pub fn new(function_name: &[u8], customization: &[u8]) -> Self {
+        CShake256 {
+            inner: cshake_engine(136, function_name, customization),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad and absorb the buffered tail, then return the output reader.
+    pub fn finalize_xof(self) -> XofReader {
+        self.inner.finalize_xof()
+    }
+}
+
+/// cSHAKE256: first `out_len` bytes of output for `msg`.
+pub fn cshake256(
+    msg: &[u8], 
+    out_len: usize, 
+    function_name: &[u8], 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut hasher = CShake256::new(function_name, customization);
+    hasher.update(msg);
+
+    let mut out = vec![0u8; out_len];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::{ bytes_to_hex, shake128, shake256 };
+
+    // Message byte strings 00 01 02 ... from the NIST samples.
+    fn counting(len: usize) -> Vec<u8> {
+        (0..len).map(|i| i as u8).collect()
+    }
+
+    #[test]
+    fn cshake128_nist_sample_1() {
+        let out = cshake128(&counting(4), 32, b"", b"Email Signature");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"c1c36925b6409a04f1b504fcbca9d82b\
+              4017277cb5ed2b2065fc1d3814d5aaf5"
+        );
+    }
+
+    #[test]
+    fn cshake128_nist_sample_2() {
+        let out = cshake128(&counting(200), 32, b"", b"Email Signature");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"c5221d50e4f822d96a2e8881a961420f\
+              294b7b24fe3d2094baed2c6524cc166b"
+        );
+    }
+
+    #[test]
+    fn cshake256_nist_sample_3() {
+        let out = cshake256(&counting(4), 64, b"", b"Email Signature");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"d008828e2b80ac9d2218ffee1d070c48\
+              b8e4c87bff32c9699d5b6896eee0edd1\
+              64020e2be0560858d9c00c037e34a969\
+              37c561a74c412bb4c746469527281c8c"
+        );
+    }
+
+    #[test]
+    fn cshake256_nist_sample_4() {
+        let out = cshake256(&counting(200), 64, b"", b"Email Signature");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"07dc27b11e51fbac75bc7b3c1d983e8b\
+              4b85fb1defaf218912ac864302730917\
+              27f42b17ed1df63e8ec118f04b23633c\
+              1dfb1574c8fb55cb45da8e25afb092bb"
+        );
+    }
+
+    #[test]
+    fn empty_strings_fall_back_to_shake() {
+        assert_eq!((cshake128(b"abc", 64, b"", b"")), (shake128(b"abc", 64)));
+        assert_eq!((cshake256(b"abc", 64, b"", b"")), (shake256(b"abc", 64)));
+    }
+
+    #[test]
+    fn function_name_alone_changes_output() {
+        let out = cshake128(b"abc", 32, b"Foo", b"");
+        assert_ne!((out), (shake128(b"abc", 32)));
+        assert_eq!(
+            bytes_to_hex(&out),
+            b"7ff00c872c07ca9956afd87420dd3230\
+            78622b194c8f8a1f3424f44b55499358"
+        );
+    }
+}
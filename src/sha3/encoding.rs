@@ -0,0 +1,90 @@
+// Encoding helpers from NIST SP 800-185, section 2.3. Lengths are given in
+// bits, as the standard does, and always fit in a u64 here.
+
+/// Encode `x` as its minimal big-endian byte string, prefixed by the number
+/// of bytes used. `left_encode(0)` is `[1, 0]`.
+pub fn left_encode(x: u64) -> Vec<u8> {
+    let mut encoded = minimal_be_bytes(x);
+    encoded.insert(0, encoded.len() as u8);
+    encoded
+}
+
+/// Encode `x` as its minimal big-endian byte string, followed by the number
+/// of bytes used. `right_encode(0)` is `[0, 1]`.
+pub fn right_encode(x: u64) -> Vec<u8> {
+    let mut encoded = minimal_be_bytes(x);
+    encoded.push(encoded.len() as u8);
+    encoded
+}
+
+/// Prefix `s` with its bit length, so concatenated strings stay
+/// unambiguous.
+pub fn encode_string(s: &[u8]) -> Vec<u8> {
+    let mut encoded = left_encode(s.len() as u64 * 8);
+    encoded.extend_from_slice(s);
+    encoded
+}
+
+/// Prefix `x` with `left_encode(w)` and zero-pad the result to a multiple
+/// of `w` bytes.
+pub fn bytepad(x: &[u8], w: usize) -> Vec<u8> {
+    assert!(w > 0, "bytepad width must be positive");
+
+    let mut padded = left_encode(w as u64);
+    padded.extend_from_slice(x);
+    while !padded.len().is_multiple_of(w) {
+        padded.push(0x00);
+    }
+
+    padded
+}
+
+// Big-endian bytes of `x` without leading zeros, at least one byte.
+fn minimal_be_bytes(x: u64) -> Vec<u8> {
+    let bytes = x.to_be_bytes();
+    let mut i = 0;
+    while i < 7 && bytes[i] == 0 {
+        i += 1;
+    }
+
+    bytes[i..].to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encodes_zero_with_one_byte() {
+        assert_eq!((left_encode(0)), (vec![0x01, 0x00]));
+        assert_eq!((right_encode(0)), (vec![0x00, 0x01]));
+    }
+
+    #[test]
+    fn encodes_multi_byte_values_big_endian() {
+        assert_eq!((left_encode(168)), (vec![0x01, 0xa8]));
+        assert_eq!((left_encode(256)), (vec![0x02, 0x01, 0x00]));
+        assert_eq!((right_encode(0x010203)), (vec![0x01, 0x02, 0x03, 0x03]));
+        assert_eq!((left_encode(u64::MAX).len()), (9));
+    }
+
+    #[test]
+    fn encode_string_prefixes_bit_length() {
+        assert_eq!((encode_string(b"")), (vec![0x01, 0x00]));
+        assert_eq!(
+            (encode_string(b"KMAC")),
+            (vec![0x01, 0x20, b'K', b'M', b'A', b'C'])
+        );
+    }
+
+    #[test]
+    fn bytepad_fills_to_width() {
+        let padded = bytepad(&[0xff; 3], 8);
+        assert_eq!(
+            (padded),
+            (vec![0x01, 0x08, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00])
+        );
+        assert_eq!((bytepad(&[0xff; 6], 8).len()), (8));
+        assert_eq!((bytepad(&[0xff; 7], 8).len()), (16));
+    }
+}
@@ -0,0 +1,23 @@
+use std::fmt;
+
+/// Errors of the Keccak sponge parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sha3Error {
+    /// Rate and capacity must both be non-zero and add up to the 200-byte
+    /// Keccak-f[1600] state.
+    InvalidRate { rate: usize, capacity: usize },
+}
+
+impl fmt::Display for Sha3Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sha3Error::InvalidRate { rate, capacity } => write!(
+                f, 
+                "Keccak rate {rate} and capacity {capacity} do not split \
+                 the 200-byte state"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Sha3Error {}
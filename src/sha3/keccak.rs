@@ -0,0 +1,194 @@
+use crate::digest::Digest;
+use crate::sha3::Keccak;
+use crate::utils::truncate;
+
+// Original Keccak hash functions, as submitted to the SHA-3 competition and
+// used by Ethereum: same sponge parameters as SHA3-256/512, but the padding
+// starts with 0x01 instead of the FIPS 202 domain suffix 0x06.
+
+/// Keccak-256 streaming context: rate 136 bytes, capacity 64 bytes.
+#[derive(Clone)]
+pub struct Keccak256 {
+    inner: Keccak,
+}
+
+impl Keccak256 {
+    pub fn new() -> Self {
+        Keccak256 {
+            inner: Keccak::init(136, 64, 0x01, 32),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad the buffered tail, absorb it, and squeeze the 32-byte digest.
+    pub fn finalize(self) -> [u8; 32] {
+        truncate(&self.inner.finalize())
+    }
+}
+
+impl Default for Keccak256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Keccak256 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 136;
+    type Output = [u8; 32];
+
+    fn new() -> Self { Keccak256::new() }
+    fn update(&mut self, data: &[u8]) { Keccak256::update(self, data) }
+    fn finalize(self) -> [u8; 32] { Keccak256::finalize(self) }
+}
+
+/// Keccak-256: 32-byte digest of `msg` with the original 0x01 padding.
+pub fn keccak256(msg: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// Keccak-512 streaming context: rate 72 bytes, capacity 128 bytes.
+#[derive(Clone)]
+pub struct Keccak512 {
+    inner: Keccak,
+}
+
+impl Keccak512 {
+    pub fn new() -> Self {
+        Keccak512 {
+            inner: Keccak::init(72, 128, 0x01, 64),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad the buffered tail, absorb it, and squeeze the 64-byte digest.
+    pub fn finalize(self) -> [u8; 64] {
+        truncate(&self.inner.finalize())
+    }
+}
+
+impl Default for Keccak512 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Keccak512 {
+    const OUTPUT_SIZE: usize = 64;
+    const BLOCK_SIZE: usize = 72;
+    type Output = [u8; 64];
+
+    fn new() -> Self { Keccak512::new() }
+    fn update(&mut self, data: &[u8]) { Keccak512::update(self, data) }
+    fn finalize(self) -> [u8; 64] { Keccak512::finalize(self) }
+}
+
+/// Keccak-512: 64-byte digest of `msg` with the original 0x01 padding.
+pub fn keccak512(msg: &[u8]) -> [u8; 64] {
+    let mut hasher = Keccak512::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::{ bytes_to_hex, sha3_256 };
+
+    #[test]
+    fn keccak256_empty() {
+        let hex = bytes_to_hex(&keccak256(b""));
+        assert_eq!(
+            hex,
+            b"c5d2460186f7233c927e7db2dcc703c0\
+              e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[test]
+    fn keccak256_abc() {
+        let hex = bytes_to_hex(&keccak256(b"abc"));
+        assert_eq!(
+            hex,
+            b"4e03657aea45a94fc7d47ba826c8d667\
+              c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+    }
+
+    #[test]
+    fn keccak256_erc20_transfer_selector() {
+        let hex = bytes_to_hex(&keccak256(b"transfer(address,uint256)"));
+        assert_eq!(
+            hex,
+            b"a9059cbb2ab09eb219583f4a59a5d062\
+              3ade346d962bcd4e46b11da047c9049b"
+        );
+    }
+
+    #[test]
+    fn keccak512_empty() {
+        let hex = bytes_to_hex(&keccak512(b""));
+        assert_eq!(
+            hex,
+            b"0eab42de4c3ceb9235fc91acffe746b2\
+              9c29a8c366b7c60e4e67c466f36a4304\
+              c00fa9caf9d87976ba469bcbe06713b4\
+              35f091ef2769fb160cdab33d3670680e"
+        );
+    }
+
+    #[test]
+    fn keccak512_abc() {
+        let hex = bytes_to_hex(&keccak512(b"abc"));
+        assert_eq!(
+            hex,
+            b"18587dc2ea106b9a1563e32b3312421c\
+              a164c7f1f07bc922a9c83d77cea3a1e5\
+              d0c69910739025372dc14ac964262937\
+              9540c17e2a65b19d77aa511a9d00bb96"
+        );
+    }
+
+    #[test]
+    fn keccak256_one_byte_short_of_rate() {
+        // Domain and final padding bit share the last byte: 0x81.
+        let hex = bytes_to_hex(&keccak256(&[b'a'; 135]));
+        assert_eq!(
+            hex,
+            b"34367dc248bbd832f4e3e69dfaac2f92\
+              638bd0bbd18f2912ba4ef454919cf446"
+        );
+    }
+
+    #[test]
+    fn keccak512_streams_across_rate_boundaries() {
+        let msg = [b'a'; 72];
+        let mut hasher = Keccak512::new();
+        for chunk in msg.chunks(5) {
+            hasher.update(chunk);
+        }
+        let hex = bytes_to_hex(&hasher.finalize());
+        assert_eq!(
+            hex,
+            b"4cb1cecbc96415025c7a9d6fb89f82a8\
+              482773fd9664c378691a05323ff4700f\
+              a3e60414e6064814f98b36a61a87f62d\
+              ffa7c56a2371355868dd37b8a654cf50"
+        );
+    }
+
+    #[test]
+    fn keccak256_differs_from_sha3_256() {
+        assert_ne!((keccak256(b"abc")), (sha3_256(b"abc")));
+    }
+}
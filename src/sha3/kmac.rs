@@ -0,0 +1,310 @@
+use crate::sha3::{ bytepad, encode_string, right_encode, Keccak, XofReader };
+use crate::sha3::cshake::cshake_engine;
+
+// KMAC keyed hash (NIST SP 800-185, section 4): cSHAKE with function name
+// "KMAC", absorbing `bytepad(encode_string(key), rate)` before the message.
+//
+// - The requested output length is bound into the MAC with `right_encode`,
+//   so tags of different lengths are unrelated.
+// - In XOF mode (KMACXOF) the length is encoded as 0 and the output can be
+//   read incrementally.
+
+/// KMAC128 context: rate 168 bytes, capacity 32 bytes.
+#[derive(Clone)]
+pub struct Kmac128 {
+    inner: Keccak,
+}
+
+impl Kmac128 {
+    pub fn new(key: &[u8], customization: &[u8]) -> Self {
+        let mut inner = cshake_engine(168, b"KMAC", customization);
+        inner.update(&bytepad(&encode_string(key), 168));
+
+        Kmac128 { inner }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// KMAC128: tag of exactly `out_len` bytes.
+    pub fn finalize(mut self, out_len: usize) -> Vec<u8> {
+        self.inner.update(&right_encode(out_len as u64 * 8));
+
+        let mut tag = vec![0u8; out_len];
+        self.inner.finalize_xof().read(&mut tag);
+        tag
+    }
+
+    /// KMACXOF128: output reader of arbitrary length.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.inner.update(&right_encode(0));
+        self.inner.finalize_xof()
+    }
+}
+
+/// KMAC128: `out_len`-byte tag of `msg` under `key`.
+pub fn kmac128(
+    key: &[u8], 
+    msg: &[u8], 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut mac = Kmac128::new(key, customization);
+    mac.update(msg);
+    mac.finalize(out_len)
+}
+
+/// KMACXOF128: first `out_len` bytes of the XOF output.
+pub fn kmac_xof128(
+    key: &[u8], 
+    msg: &[u8], 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut mac = Kmac128::new(key, customization);
+    mac.update(msg);
+
+    let mut out = vec![0u8; out_len];
+    mac.finalize_xof().read(&mut out);
+    out
+}
+
+/// KMAC256 context: rate 136 bytes, capacity 64 bytes.
+#[derive(Clone)]
+pub struct Kmac256 {
+    inner: Keccak,
+}
+
+impl Kmac256 {
+    pub fn new(key: &[u8], customization: &[u8]) -> Self {
+        let mut inner = cshake_engine(136, b"KMAC", customization);
+        inner.update(&bytepad(&encode_string(key), 136));
+
+        Kmac256 { inner }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// KMAC256: tag of exactly `out_len` bytes.
+    pub fn finalize(mut self, out_len: usize) -> Vec<u8> {
+        self.inner.update(&right_encode(out_len as u64 * 8));
+
+        let mut tag = vec![0u8; out_len];
+        self.inner.finalize_xof().read(&mut tag);
+        tag
+    }
+
+    /// KMACXOF256: output reader of arbitrary length.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.inner.update(&right_encode(0));
+        self.inner.finalize_xof()
+    }
+}
+
+/// KMAC256: `out_len`-byte tag of `msg` under `key`.
+pub fn kmac256(
+    key: &[u8], 
+    msg: &[u8], 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut mac = Kmac256::new(key, customization);
+    mac.update(msg);
+    mac.finalize(out_len)
+}
+
+/// KMACXOF256: first `out_len` bytes of the XOF output.
+pub fn kmac_xof256(
+    key: &[u8], 
+    msg: &[u8], 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut mac = Kmac256::new(key, customization);
+    mac.update(msg);
+
+    let mut out = vec![0u8; out_len];
+    mac.finalize_xof().read(&mut out);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    // Key 40 41 ... 5f shared by all NIST samples.
+    fn sample_key() -> Vec<u8> {
+        (0x40..0x60).collect()
+    }
+
+    // Message byte strings 00 01 02 ... from the NIST samples.
+    fn counting(len: usize) -> Vec<u8> {
+        (0..len).map(|i| i as u8).collect()
+    }
+
+    #[test]
+    fn kmac128_nist_sample_1() {
+        let tag = kmac128(&sample_key(), &counting(4), 32, b"");
+        let hex = bytes_to_hex(&tag);
+        assert_eq!(
+            hex,
+            b"e5780b0d3ea6f7d3a429c5706aa43a00\
+              fadbd7d49628839e3187243f456ee14e"
+        );
+    }
+
+    #[test]
+    fn kmac128_nist_sample_2() {
+        let tag = kmac128(
+            &sample_key(), &counting(4), 32, b"My Tagged Application"
+        );
+        let hex = bytes_to_hex(&tag);
+        assert_eq!(
+            hex,
+            b"3b1fba963cd8b0b59e8c1a6d71888b71\
+              43651af8ba0a7070c0979e2811324aa5"
+        );
+    }
+
+    #[test]
+    fn kmac128_nist_sample_3() {
+        let tag = kmac128(
+            &sample_key(), &counting(200), 32, b"My Tagged Application"
+        );
+        let hex = bytes_to_hex(&tag);
+        assert_eq!(
+            hex,
+            b"1f5b4e6cca02209e0dcb5ca635b89a15\
+              e271ecc760071dfd805faa38f9729230"
+        );
+    }
+
+    #[test]
+    fn kmac256_nist_sample_4() {
+        let tag = kmac256(
+            &sample_key(), &counting(4), 64, b"My Tagged Application"
+        );
+        let hex = bytes_to_hex(&tag);
+        assert_eq!(
+            hex,
+            b"20c570c31346f703c9ac36c61c03cb64\
+              c3970d0cfc787e9b79599d273a68d2f7\
+              f69d4cc3de9d104a351689f27cf6f595\
+              1f0103f33f4f24871024d9c27773a8dd"
+        );
+    }
+
+    #[test]
+    fn kmac256_nist_sample_5() {
+        let tag = kmac256(&sample_key(), &counting(200), 64, b"");
+        let hex = bytes_to_hex(&tag);
+        assert_eq!(
+            hex,
+            b"75358cf39e41494e949707927cee0af2\
+              0a3ff553904c86b08f21cc414bcfd691\
+              589d27cf5e15369cbbff8b9a4c2eb178\
+              00855d0235ff635da82533ec6b759b69"
+        );
+    }
+
+    #[test]
+    fn kmac256_nist_sample_6() {
+        let tag = kmac256(
+            &sample_key(), &counting(200), 64, b"My Tagged Application"
+        );
+        let hex = bytes_to_hex(&tag);
+        assert_eq!(
+            hex,
+            b"b58618f71f92e1d56c1b8c55ddd7cd18\
+              8b97b4ca4d99831eb2699a837da2e4d9\
+              70fbacfde50033aea585f1a2708510c3\
+              2d07880801bd182898fe476876fc8965"
+        );
+    }
+
+    #[test]
+    fn kmac_xof128_nist_sample_1() {
+        let tag = kmac_xof128(&sample_key(), &counting(4), 32, b"");
+        let hex = bytes_to_hex(&tag);
+        assert_eq!(
+            hex,
+            b"cd83740bbd92ccc8cf032b1481a0f446\
+              0e7ca9dd12b08a0c4031178bacd6ec35"
+        );
+    }
+
+    #[test]
+    fn kmac_xof128_nist_sample_3() {
+        let tag = kmac_xof128(
+            &sample_key(), &counting(200), 32, b"My Tagged Application"
+        );
+        let hex = bytes_to_hex(&tag);
+        assert_eq!(
+            hex,
+            b"47026c7cd793084aa0283c253ef65849\
+              0c0db61438b8326fe9bddf281b83ae0f"
+        );
+    }
+
+    #[test]
+    fn kmac_xof256_nist_sample_4() {
+        let tag = kmac_xof256(
+            &sample_key(), &counting(4), 64, b"My Tagged Application"
+        );
+        let hex = bytes_to_hex(&tag);
+        assert_eq!(
+            hex,
+            b"1755133f1534752aad0748f2c706fb5c\
+              784512cab835cd15676b16c0c6647fa9\
+              6faa7af634a0bf8ff6df39374fa00fad\
+              9a39e322a7c92065a64eb1fb0801eb2b"
+        );
+    }
+
+    #[test]
+    fn kmac_xof256_nist_sample_6() {
+        let tag = kmac_xof256(
+            &sample_key(), &counting(200), 64, b"My Tagged Application"
+        );
+        let hex = bytes_to_hex(&tag);
+        assert_eq!(
+            hex,
+            b"d5be731c954ed7732846bb59dbe3a8e3\
+              0f83e77a4bff4459f2f1c2b4ecebb8ce\
+              67ba01c62e8ab8578d2d499bd1bb2767\
+              68781190020a306a97de281dcc30305d"
+        );
+    }
+
+    #[test]
+    fn tag_length_is_bound_into_the_tag() {
+        let short = kmac256(b"key", b"msg", 16, b"");
+        let long = kmac256(b"key", b"msg", 32, b"");
+        assert_ne!((&long[..16]), (&short[..]));
+
+        // XOF output, on the other hand, is prefix-consistent.
+        let short = kmac_xof256(b"key", b"msg", 16, b"");
+        let long = kmac_xof256(b"key", b"msg", 32, b"");
+        assert_eq!((&long[..16]), (&short[..]));
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        let msg = counting(1_000);
+        let mut mac = Kmac128::new(&sample_key(), b"app");
+        for chunk in msg.chunks(77) {
+            mac.update(chunk);
+        }
+
+        assert_eq!(
+            (mac.finalize(48)), 
+            (kmac128(&sample_key(), &msg, 48, b"app"))
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod error;
 pub mod theta;
 pub mod rho;
 pub mod pi;
@@ -12,7 +13,17 @@ pub mod lanes_to_state;
 pub mod state_to_lanes;
 pub mod state_to_string;
 pub mod string_to_state;
+#[allow(clippy::module_inception)]
+pub mod sha3;
+pub mod shake;
+pub mod keccak;
+pub mod encoding;
+pub mod cshake;
+pub mod kmac;
+pub mod tuple_hash;
+pub mod parallel_hash;
 
+pub use error::Sha3Error;
 pub use theta::theta_func;
 pub use rho::rho_func;
 pub use pi::pi_func;
@@ -20,10 +31,28 @@ pub use chi::chi_func;
 pub use rc::rc_func;
 pub use iota::iota_func;
 pub use round::rnd_func;
-pub use sponge::sponge;
+pub use sponge::{ sponge, Keccak, XofReader };
 pub use permutation::keccak_permutation;
-pub use bytes_to_hex::bytes_to_hex;
+pub use bytes_to_hex::{ bytes_to_hex, hex_to_bytes };
 pub use lanes_to_state::lanes_to_state;
 pub use state_to_lanes::state_to_lanes;
 pub use state_to_string::state_to_string;
-pub use string_to_state::string_to_state;
\ No newline at end of file
+pub use string_to_state::string_to_state;
+pub use sha3::{
+    sha3_224, sha3_256, sha3_384, sha3_512,
+    Sha3_224, Sha3_256, Sha3_384, Sha3_512
+};
+pub use shake::{ shake128, shake256, Shake128, Shake256 };
+pub use keccak::{ keccak256, keccak512, Keccak256, Keccak512 };
+pub use encoding::{ bytepad, encode_string, left_encode, right_encode };
+pub use cshake::{ cshake128, cshake256, CShake128, CShake256 };
+pub use kmac::{ kmac128, kmac256, kmac_xof128, kmac_xof256, Kmac128, Kmac256 };
+pub use tuple_hash::{
+    tuple_hash128, tuple_hash256, tuple_hash_xof128, tuple_hash_xof256,
+    TupleHash128, TupleHash256
+};
+pub use parallel_hash::{
+    parallel_hash128, parallel_hash256,
+    parallel_hash_xof128, parallel_hash_xof256,
+    ParallelHash128, ParallelHash256
+};
@@ -0,0 +1,312 @@
+use crate::sha3::{
+    left_encode, right_encode, shake128, shake256, Keccak, XofReader
+};
+use crate::sha3::cshake::cshake_engine;
+
+// ParallelHash (NIST SP 800-185, section 6): the message is cut into
+// `block_size`-byte blocks, every block is hashed on its own with SHAKE
+// (32 bytes for ParallelHash128, 64 for ParallelHash256), and the chained
+// values are absorbed by cSHAKE with function name "ParallelHash".
+//
+// The blocks are independent, so they could be hashed on separate cores;
+// here they are processed in order as the message streams in.
+
+/// ParallelHash128 context with a fixed block size in bytes.
+#[derive(Clone)]
+pub struct ParallelHash128 {
+    inner: Keccak,
+    block_size: usize,
+    buffer: Vec<u8>,
+    blocks: u64,
+}
+
+impl ParallelHash128 {
+    pub fn new(block_size: usize, customization: &[u8]) -> Self {
+        assert!(block_size > 0, "block size must be positive");
+
+        let mut inner = cshake_engine(168, b"ParallelHash", customization);
+        inner.update(&left_encode(block_size as u64));
+
+        ParallelHash128 {
+            inner,
+            block_size,
+            buffer: Vec::with_capacity(block_size),
+            blocks: 0,
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.buffer.push(byte);
+            if self.buffer.len() == self.block_size {
+                self.absorb_buffer();
+            }
+        }
+    }
+
+    /// ParallelHash128: digest of exactly `out_len` bytes.
+    pub fn finalize(mut self, out_len: usize) -> Vec<u8> {
+        self.absorb_trailer(out_len as u64 * 8);
+
+        let mut out = vec![0u8; out_len];
+        self.inner.finalize_xof().read(&mut out);
+        out
+    }
+
+    /// ParallelHashXOF128: output reader of arbitrary length.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.absorb_trailer(0);
+        self.inner.finalize_xof()
+    }
+
+    // Hash the buffered block and absorb its chaining value.
+    fn absorb_buffer(&mut self) {
+        let chaining_value = shake128(&self.buffer, 32);
+        self.inner.update(&chaining_value);
+        self.buffer.clear();
+        self.blocks += 1;
+    }
+
+    // Flush a partial last block, then encode block count and output length.
+    fn absorb_trailer(&mut self, out_bits: u64) {
+        if !self.buffer.is_empty() {
+            self.absorb_buffer();
+        }
+
+        self.inner.update(&right_encode(self.blocks));
+        self.inner.update(&right_encode(out_bits));
+    }
+}
+
+/// ParallelHash128: `out_len`-byte digest of `msg`.
+pub fn parallel_hash128(
+    msg: &[u8], 
+    block_size: usize, 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut hasher = ParallelHash128::new(block_size, customization);
+    hasher.update(msg);
+    hasher.finalize(out_len)
+}
+
+/// ParallelHashXOF128: first `out_len` bytes of the XOF output.
+pub fn parallel_hash_xof128(
+    msg: &[u8], 
+    block_size: usize, 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut hasher = ParallelHash128::new(block_size, customization);
+    hasher.update(msg);
+
+    let mut out = vec![0u8; out_len];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+/// ParallelHash256 context with a fixed block size in bytes.
+#[derive(Clone)]
+pub struct ParallelHash256 {
+    inner: Keccak,
+    block_size: usize,
+    buffer: Vec<u8>,
+    blocks: u64,
+}
+
+impl ParallelHash256 {
+    pub fn new(block_size: usize, customization: &[u8]) -> Self {
+        assert!(block_size > 0, "block size must be positive");
+
+        let mut inner = cshake_engine(136, b"ParallelHash", customization);
+        inner.update(&left_encode(block_size as u64));
+
+        ParallelHash256 {
+            inner,
+            block_size,
+            buffer: Vec::with_capacity(block_size),
+            blocks: 0,
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.buffer.push(byte);
+            if self.buffer.len() == self.block_size {
+                self.absorb_buffer();
+            }
+        }
+    }
+
+    /// ParallelHash256: digest of exactly `out_len` bytes.
+    pub fn finalize(mut self, out_len: usize) -> Vec<u8> {
+        self.absorb_trailer(out_len as u64 * 8);
+
+        let mut out = vec![0u8; out_len];
+        self.inner.finalize_xof().read(&mut out);
+        out
+    }
+
+    /// ParallelHashXOF256: output reader of arbitrary length.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.absorb_trailer(0);
+        self.inner.finalize_xof()
+    }
+
+    // Hash the buffered block and absorb its chaining value.
+    fn absorb_buffer(&mut self) {
+        let chaining_value = shake256(&self.buffer, 64);
+        self.inner.update(&chaining_value);
+        self.buffer.clear();
+        self.blocks += 1;
+    }
+
+    // Flush a partial last block, then encode block count and output length.
+    fn absorb_trailer(&mut self, out_bits: u64) {
+        if !self.buffer.is_empty() {
+            self.absorb_buffer();
+        }
+
+        self.inner.update(&right_encode(self.blocks));
+        self.inner.update(&right_encode(out_bits));
+    }
+}
+
+/// ParallelHash256: `out_len`-byte digest of `msg`.
+pub fn parallel_hash256(
+    msg: &[u8], 
+    block_size: usize, 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut hasher = ParallelHash256::new(block_size, customization);
+    hasher.update(msg);
+    hasher.finalize(out_len)
+}
+
+/// ParallelHashXOF256: first `out_len` bytes of the XOF output.
+pub fn parallel_hash_xof256(
+    msg: &[u8], 
+    block_size: usize, 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut hasher = ParallelHash256::new(block_size, customization);
+    hasher.update(msg);
+
+    let mut out = vec![0u8; out_len];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    // 00..07 10..17 20..27, three 8-byte blocks in the NIST samples.
+    const X: &[u8] = &[
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+        0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+    ];
+
+    #[test]
+    fn parallel_hash128_nist_sample_1() {
+        let out = parallel_hash128(X, 8, 32, b"");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"ba8dc1d1d979331d3f813603c67f7260\
+              9ab5e44b94a0b8f9af46514454a2b4f5"
+        );
+    }
+
+    #[test]
+    fn parallel_hash128_nist_sample_2() {
+        let out = parallel_hash128(X, 8, 32, b"Parallel Data");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"fc484dcb3f84dceedc353438151bee58\
+              157d6efed0445a81f165e495795b7206"
+        );
+    }
+
+    #[test]
+    fn parallel_hash256_nist_sample_4() {
+        let out = parallel_hash256(X, 8, 64, b"");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"bc1ef124da34495e948ead207dd98422\
+              35da432d2bbc54b4c110e64c45110553\
+              1b7f2a3e0ce055c02805e7c2de1fb746\
+              af97a1dd01f43b824e31b87612410429"
+        );
+    }
+
+    #[test]
+    fn parallel_hash256_nist_sample_5() {
+        let out = parallel_hash256(X, 8, 64, b"Parallel Data");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"cdf15289b54f6212b4bc270528b49526\
+              006dd9b54e2b6add1ef6900dda3963bb\
+              33a72491f236969ca8afaea29c682d47\
+              a393c065b38e29fae651a2091c833110"
+        );
+    }
+
+    #[test]
+    fn parallel_hash_xof128_nist_sample_1() {
+        let out = parallel_hash_xof128(X, 8, 32, b"");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"fe47d661e49ffe5b7d999922c0623567\
+              50caf552985b8e8ce6667f2727c3c8d3"
+        );
+    }
+
+    #[test]
+    fn parallel_hash_xof256_nist_sample_5() {
+        let out = parallel_hash_xof256(X, 8, 64, b"Parallel Data");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"538e105f1a22f44ed2f5cc1674fbd40b\
+              e803d9c99bf5f8d90a2c8193f3fe6ea7\
+              68e5c1a20987e2c9c65febed03887a51\
+              d35624ed12377594b5585541dc377efc"
+        );
+    }
+
+    #[test]
+    fn partial_last_block_is_hashed() {
+        let out = parallel_hash128(&X[..20], 8, 32, b"");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"a725ed02aa4acb2041624b6222cd4879\
+              42e03e63836b0face5dec556b9121297"
+        );
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        let msg: Vec<u8> = (0..1_000).map(|i| i as u8).collect();
+        let mut hasher = ParallelHash256::new(64, b"");
+        for chunk in msg.chunks(100) {
+            hasher.update(chunk);
+        }
+
+        assert_eq!(
+            (hasher.finalize(64)), 
+            (parallel_hash256(&msg, 64, 64, b""))
+        );
+    }
+}
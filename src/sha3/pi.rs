@@ -1,4 +1,5 @@
 // Pi: rearrange positions of lanes.
+#[allow(clippy::needless_range_loop)]
 pub fn pi_func(a: &[[u64; 5]; 5]) -> [[u64; 5]; 5] {
     let mut out = [[0u64; 5]; 5];
 
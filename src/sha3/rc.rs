@@ -1,4 +1,5 @@
 // Rc: inject unique bit in each round to break symmetry between rounds.
+#[allow(clippy::manual_is_multiple_of, clippy::assign_op_pattern)]
 pub fn rc_func(t: u64) -> u8 {
     if t % 255 == 0 {
         return 1;
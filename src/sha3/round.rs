@@ -3,6 +3,7 @@ use crate::sha3::{
 };
 
 // RnD: for applying stepmapping to receive a transformed state.
+#[allow(clippy::let_and_return)]
 pub fn rnd_func(a: &[[u64; 5]; 5], ir: usize) -> [[u64; 5]; 5] {
     let tata = theta_func(a);
     let rho = rho_func(&tata);
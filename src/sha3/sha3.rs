@@ -0,0 +1,416 @@
+use crate::digest::Digest;
+use crate::sha3::Keccak;
+use crate::utils::truncate;
+
+// SHA-3 fixed-length hash functions (FIPS 202): the Keccak sponge with
+// capacity twice the digest length and domain suffix 0x06.
+
+/// SHA3-224 streaming context: rate 144 bytes, capacity 56 bytes.
+#[derive(Clone)]
+pub struct Sha3_224 {
+    inner: Keccak,
+}
+
+impl Sha3_224 {
+    pub fn new() -> Self {
+        Sha3_224 {
+            inner: Keccak::init(144, 56, 0x06, 28),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad the buffered tail, absorb it, and squeeze the 28-byte digest.
+    pub fn finalize(self) -> [u8; 28] {
+        truncate(&self.inner.finalize())
+    }
+}
+
+impl Default for Sha3_224 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha3_224 {
+    const OUTPUT_SIZE: usize = 28;
+    const BLOCK_SIZE: usize = 144;
+    type Output = [u8; 28];
+
+    fn new() -> Self { Sha3_224::new() }
+    fn update(&mut self, data: &[u8]) { Sha3_224::update(self, data) }
+    fn finalize(self) -> [u8; 28] { Sha3_224::finalize(self) }
+}
+
+/// SHA3-224: 28-byte digest of `msg`.
+pub fn sha3_224(msg: &[u8]) -> [u8; 28] {
+    let mut hasher = Sha3_224::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// SHA3-256 streaming context: rate 136 bytes, capacity 64 bytes.
+#[derive(Clone)]
+pub struct Sha3_256 {
+    inner: Keccak,
+}
+
+impl Sha3_256 {
+    pub fn new() -> Self {
+        Sha3_256 {
+            inner: Keccak::init(136, 64, 0x06, 32),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad the buffered tail, absorb it, and squeeze the 32-byte digest.
+    pub fn finalize(self) -> [u8; 32] {
+        truncate(&self.inner.finalize())
+    }
+}
+
+impl Default for Sha3_256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha3_256 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 136;
+    type Output = [u8; 32];
+
+    fn new() -> Self { Sha3_256::new() }
+    fn update(&mut self, data: &[u8]) { Sha3_256::update(self, data) }
+    fn finalize(self) -> [u8; 32] { Sha3_256::finalize(self) }
+}
+
+/// SHA3-256: 32-byte digest of `msg`.
+pub fn sha3_256(msg: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// SHA3-384 streaming context: rate 104 bytes, capacity 96 bytes.
+#[derive(Clone)]
+pub struct Sha3_384 {
+    inner: Keccak,
+}
+
+impl Sha3_384 {
+    pub fn new() -> Self {
+        Sha3_384 {
+            inner: Keccak::init(104, 96, 0x06, 48),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad the buffered tail, absorb it, and squeeze the 48-byte digest.
+    pub fn finalize(self) -> [u8; 48] {
+        truncate(&self.inner.finalize())
+    }
+}
+
+impl Default for Sha3_384 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha3_384 {
+    const OUTPUT_SIZE: usize = 48;
+    const BLOCK_SIZE: usize = 104;
+    type Output = [u8; 48];
+
+    fn new() -> Self { Sha3_384::new() }
+    fn update(&mut self, data: &[u8]) { Sha3_384::update(self, data) }
+    fn finalize(self) -> [u8; 48] { Sha3_384::finalize(self) }
+}
+
+/// SHA3-384: 48-byte digest of `msg`.
+pub fn sha3_384(msg: &[u8]) -> [u8; 48] {
+    let mut hasher = Sha3_384::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// SHA3-512 streaming context: rate 72 bytes, capacity 128 bytes.
+#[derive(Clone)]
+pub struct Sha3_512 {
+    inner: Keccak,
+}
+
+impl Sha3_512 {
+    pub fn new() -> Self {
+        Sha3_512 {
+            inner: Keccak::init(72, 128, 0x06, 64),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad the buffered tail, absorb it, and squeeze the 64-byte digest.
+    pub fn finalize(self) -> [u8; 64] {
+        truncate(&self.inner.finalize())
+    }
+}
+
+impl Default for Sha3_512 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha3_512 {
+    const OUTPUT_SIZE: usize = 64;
+    const BLOCK_SIZE: usize = 72;
+    type Output = [u8; 64];
+
+    fn new() -> Self { Sha3_512::new() }
+    fn update(&mut self, data: &[u8]) { Sha3_512::update(self, data) }
+    fn finalize(self) -> [u8; 64] { Sha3_512::finalize(self) }
+}
+
+/// SHA3-512: 64-byte digest of `msg`.
+pub fn sha3_512(msg: &[u8]) -> [u8; 64] {
+    let mut hasher = Sha3_512::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::{ bytes_to_hex, sponge };
+
+    #[test]
+    fn sha3_224_empty() {
+        let msg = b"";
+        let hex = bytes_to_hex(&sha3_224(msg));
+        assert_eq!(
+            hex,
+            b"6b4e03423667dbb73b6e15454f0eb1ab\
+              d4597f9a1b078e3f5b5a6bc7"
+        );
+    }
+
+    #[test]
+    fn sha3_224_abc() {
+        let msg = b"abc";
+        let hex = bytes_to_hex(&sha3_224(msg));
+        assert_eq!(
+            hex,
+            b"e642824c3f8cf24ad09234ee7d3c766f\
+              c9a3a5168d0c94ad73b46fdf"
+        );
+    }
+
+    #[test]
+    fn sha3_224_896_bits() {
+        let msg = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                    hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+        let hex = bytes_to_hex(&sha3_224(msg));
+        assert_eq!(
+            hex,
+            b"543e6868e1666c1a643630df77367ae5\
+              a62a85070a51c14cbf665cbc"
+        );
+    }
+
+    #[test]
+    fn sha3_256_empty() {
+        let msg = b"";
+        let hex = bytes_to_hex(&sha3_256(msg));
+        assert_eq!(
+            hex,
+            b"a7ffc6f8bf1ed76651c14756a061d662\
+              f580ff4de43b49fa82d80a4b80f8434a"
+        );
+    }
+
+    #[test]
+    fn sha3_256_abc() {
+        let msg = b"abc";
+        let hex = bytes_to_hex(&sha3_256(msg));
+        assert_eq!(
+            hex,
+            b"3a985da74fe225b2045c172d6bd390bd\
+              855f086e3e9d525b46bfe24511431532"
+        );
+    }
+
+    #[test]
+    fn sha3_256_896_bits() {
+        let msg = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                    hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+        let hex = bytes_to_hex(&sha3_256(msg));
+        assert_eq!(
+            hex,
+            b"916f6061fe879741ca6469b43971dfdb\
+              28b1a32dc36cb3254e812be27aad1d18"
+        );
+    }
+
+    #[test]
+    fn sha3_384_empty() {
+        let msg = b"";
+        let hex = bytes_to_hex(&sha3_384(msg));
+        assert_eq!(
+            hex,
+            b"0c63a75b845e4f7d01107d852e4c2485\
+              c51a50aaaa94fc61995e71bbee983a2a\
+              c3713831264adb47fb6bd1e058d5f004"
+        );
+    }
+
+    #[test]
+    fn sha3_384_abc() {
+        let msg = b"abc";
+        let hex = bytes_to_hex(&sha3_384(msg));
+        assert_eq!(
+            hex,
+            b"ec01498288516fc926459f58e2c6ad8d\
+              f9b473cb0fc08c2596da7cf0e49be4b2\
+              98d88cea927ac7f539f1edf228376d25"
+        );
+    }
+
+    #[test]
+    fn sha3_384_896_bits() {
+        let msg = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                    hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+        let hex = bytes_to_hex(&sha3_384(msg));
+        assert_eq!(
+            hex,
+            b"79407d3b5916b59c3e30b09822974791\
+              c313fb9ecc849e406f23592d04f625dc\
+              8c709b98b43b3852b337216179aa7fc7"
+        );
+    }
+
+    #[test]
+    fn sha3_512_empty() {
+        let msg = b"";
+        let hex = bytes_to_hex(&sha3_512(msg));
+        assert_eq!(
+            hex,
+            b"a69f73cca23a9ac5c8b567dc185a756e\
+              97c982164fe25859e0d1dcc1475c80a6\
+              15b2123af1f5f94c11e3e9402c3ac558\
+              f500199d95b6d3e301758586281dcd26"
+        );
+    }
+
+    #[test]
+    fn sha3_512_abc() {
+        let msg = b"abc";
+        let hex = bytes_to_hex(&sha3_512(msg));
+        assert_eq!(
+            hex,
+            b"b751850b1a57168a5693cd924b6b096e\
+              08f621827444f70d884f5d0240d2712e\
+              10e116e9192af3c91a7ec57647e39340\
+              57340b4cf408d5a56592f8274eec53f0"
+        );
+    }
+
+    #[test]
+    fn sha3_512_896_bits() {
+        let msg = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                    hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+        let hex = bytes_to_hex(&sha3_512(msg));
+        assert_eq!(
+            hex,
+            b"afebb2ef542e6579c50cad06d2e578f9\
+              f8dd6881d7dc824d26360feebf18a4fa\
+              73e3261122948efcfd492e74e82e2189\
+              ed0fb440d187f382270cb455f21dd185"
+        );
+    }
+
+    #[test]
+    fn sha3_256_streams_abc_in_single_bytes() {
+        let mut hasher = Sha3_256::new();
+        hasher.update(b"a");
+        hasher.update(b"b");
+        hasher.update(b"c");
+        let expected = [
+            0x3a, 0x98, 0x5d, 0xa7, 0x4f, 0xe2, 0x25, 0xb2,
+            0x04, 0x5c, 0x17, 0x2d, 0x6b, 0xd3, 0x90, 0xbd,
+            0x85, 0x5f, 0x08, 0x6e, 0x3e, 0x9d, 0x52, 0x5b,
+            0x46, 0xbf, 0xe2, 0x45, 0x11, 0x43, 0x15, 0x32,
+        ];
+
+        assert_eq!((hasher.finalize()), (expected));
+    }
+
+    #[test]
+    fn sha3_256_one_byte_short_of_rate() {
+        let msg = [b'a'; 135];
+        let result = Sha3_256::digest(&msg);
+        let expected = [
+            0x80, 0x94, 0xbb, 0x53, 0xc4, 0x4c, 0xfb, 0x1e,
+            0x67, 0xb7, 0xc3, 0x04, 0x47, 0xf9, 0xa1, 0xc3,
+            0x36, 0x96, 0xd2, 0x46, 0x3e, 0xcc, 0x1d, 0x9c,
+            0x92, 0x53, 0x89, 0x13, 0x39, 0x28, 0x43, 0xc9,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha3_256_exactly_one_rate() {
+        let msg = [b'a'; 136];
+        let result = Sha3_256::digest(&msg);
+        let expected = [
+            0x3f, 0xc5, 0x55, 0x9f, 0x14, 0xdb, 0x8e, 0x45,
+            0x3a, 0x0a, 0x30, 0x91, 0xed, 0xbd, 0x2b, 0xc2,
+            0x5e, 0x11, 0x52, 0x8d, 0x81, 0xc6, 0x6f, 0xa5,
+            0x70, 0xa4, 0xef, 0xdc, 0xc2, 0x69, 0x5e, 0xe1,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha3_256_streaming_matches_sponge() {
+        let msg = b"a".repeat(1_000);
+        for chunk_size in [1, 7, 135, 136, 137, 500] {
+            let mut hasher = Sha3_256::new();
+            for chunk in msg.chunks(chunk_size) {
+                hasher.update(chunk);
+            }
+
+            assert_eq!((hasher.finalize().to_vec()), (sponge(&msg)));
+        }
+    }
+
+    #[test]
+    fn sha3_512_streams_across_rate_boundaries() {
+        let msg = b"a".repeat(1_000);
+        let expected = sha3_512(&msg);
+        for chunk_size in [1, 71, 72, 73] {
+            let mut hasher = Sha3_512::new();
+            for chunk in msg.chunks(chunk_size) {
+                hasher.update(chunk);
+            }
+
+            assert_eq!((hasher.finalize()), (expected));
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,178 @@
+use crate::sha3::{ Keccak, XofReader };
+
+// SHAKE extendable-output functions (FIPS 202): the Keccak sponge with
+// domain suffix 0x1F and no fixed output length. `finalize_xof` returns an
+// `XofReader` to squeeze the output from.
+
+/// SHAKE128 absorbing context: rate 168 bytes, capacity 32 bytes.
+#[derive(Clone)]
+pub struct Shake128 {
+    inner: Keccak,
+}
+
+impl Shake128 {
+    pub fn new() -> Self {
+        Shake128 {
+            inner: Keccak::init(168, 32, 0x1f, 0),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad and absorb the buffered tail, then return the output reader.
+    pub fn finalize_xof(self) -> XofReader {
+        self.inner.finalize_xof()
+    }
+}
+
+impl Default for Shake128 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// SHAKE128: first `out_len` bytes of output for `msg`.
+pub fn shake128(msg: &[u8], out_len: usize) -> Vec<u8> {
+    let mut hasher = Shake128::new();
+    hasher.update(msg);
+
+    let mut out = vec![0u8; out_len];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+/// SHAKE256 absorbing context: rate 136 bytes, capacity 64 bytes.
+#[derive(Clone)]
+pub struct Shake256 {
+    inner: Keccak,
+}
+
+impl Shake256 {
+    pub fn new() -> Self {
+        Shake256 {
+            inner: Keccak::init(136, 64, 0x1f, 0),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad and absorb the buffered tail, then return the output reader.
+    pub fn finalize_xof(self) -> XofReader {
+        self.inner.finalize_xof()
+    }
+}
+
+impl Default for Shake256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// SHAKE256: first `out_len` bytes of output for `msg`.
+pub fn shake256(msg: &[u8], out_len: usize) -> Vec<u8> {
+    let mut hasher = Shake256::new();
+    hasher.update(msg);
+
+    let mut out = vec![0u8; out_len];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    #[test]
+    fn shake128_empty() {
+        let hex = bytes_to_hex(&shake128(b"", 32));
+        assert_eq!(
+            hex,
+            b"7f9c2ba4e88f827d616045507605853e\
+              d73b8093f6efbc88eb1a6eacfa66ef26"
+        );
+    }
+
+    #[test]
+    fn shake128_abc() {
+        let hex = bytes_to_hex(&shake128(b"abc", 32));
+        assert_eq!(
+            hex,
+            b"5881092dd818bf5cf8a3ddb793fbcba7\
+              4097d5c526a6d35f97b83351940f2cc8"
+        );
+    }
+
+    #[test]
+    fn shake256_empty() {
+        let hex = bytes_to_hex(&shake256(b"", 64));
+        assert_eq!(
+            hex,
+            b"46b9dd2b0ba88d13233b3feb743eeb24\
+              3fcd52ea62b81b82b50c27646ed5762f\
+              d75dc4ddd8c0f200cb05019d67b592f6\
+              fc821c49479ab48640292eacb3b7c4be"
+        );
+    }
+
+    #[test]
+    fn shake256_abc() {
+        let hex = bytes_to_hex(&shake256(b"abc", 64));
+        assert_eq!(
+            hex,
+            b"483366601360a8771c6863080cc4114d\
+              8db44530f8f1e1ee4f94ea37e78b5739\
+              d5a15bef186a5386c75744c0527e1faa\
+              9f8726e462a12a4feb06bd8801e751e4"
+        );
+    }
+
+    #[test]
+    fn shake128_reads_in_uneven_chunks() {
+        // 1000 bytes need several permutations between reads.
+        let mut hasher = Shake128::new();
+        for chunk in [b'a'; 200].chunks(33) {
+            hasher.update(chunk);
+        }
+        let mut reader = hasher.finalize_xof();
+
+        let mut out = Vec::new();
+        for len in [1, 167, 168, 169, 495] {
+            let mut part = vec![0u8; len];
+            reader.read(&mut part);
+            out.extend_from_slice(&part);
+        }
+
+        let hex = bytes_to_hex(&out[968..]);
+        assert_eq!(
+            hex,
+            b"dc01741e6449625ebc4b71020d4832f7\
+              7233ab09edd8a48e6c5898b942b8bea2"
+        );
+        assert_eq!((out), (shake128(&[b'a'; 200], 1000)));
+    }
+
+    #[test]
+    fn shake256_long_output_of_empty_string() {
+        let out = shake256(b"", 512);
+        let hex = bytes_to_hex(&out[480..]);
+        assert_eq!(
+            hex,
+            b"ab0bae316339894304e35877b0c28a9b\
+              1fd166c796b9cc258a064a8f57e27f2a"
+        );
+    }
+
+    #[test]
+    fn shorter_output_is_a_prefix() {
+        let long = shake256(b"abc", 300);
+        let short = shake256(b"abc", 17);
+        assert_eq!((&long[..17]), (&short[..]));
+    }
+}
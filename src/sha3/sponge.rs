@@ -1,58 +1,222 @@
 use crate::padd_pars::keccak_padding::keccak_padd;
 use crate::sha3::{
-    state_to_lanes, keccak_permutation, lanes_to_state
+    state_to_lanes, keccak_permutation, lanes_to_state, Sha3Error
 };
 
-// Sponge construction: to absorb an arbitrary number of input bits into the
-// state function, to afterwards squeeze an arbitrary number of output bits.
-pub fn sponge(msg: &[u8]) -> Vec<u8> {
-    let rate_in_bits = 1088;
-    let rate_in_bytes = rate_in_bits / 8;
-    let output_length = 32;
-    
-    let mut state = [0u8; 200];
+/// Keccak sponge engine: absorbs the message `rate` bytes at a time and
+/// squeezes `output_len` bytes out of the state. All sizes are in bytes.
+///
+/// # Description
+/// - `rate` + `capacity` always make up the 200-byte Keccak-f[1600] state.
+/// - `domain_suffix` is the byte that `keccak_padd` appends first (0x06 for
+///   SHA-3, 0x1F for SHAKE).
+/// - Blocks are absorbed as soon as they fill up, only a partial block is
+///   buffered between `update` calls.
+///
+/// # Reference
+/// [FIPS 202](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf),
+/// section 4.
+#[derive(Clone)]
+pub struct Keccak {
+    rate: usize,
+    capacity: usize,
+    domain_suffix: u8,
+    output_len: usize,
+    state: [u8; 200],
+    buffer: [u8; 200],
+    buffer_len: usize,
+}
+
+impl Keccak {
+    /// Keccak sponge with the given parameters, all in bytes.
+    ///
+    /// # Arguments
+    /// - `rate`, `capacity`: Both non-zero, adding up to 200.
+    /// - `domain_suffix`: First padding byte, e.g. 0x06 for SHA-3.
+    /// - `output_len`: Number of bytes `finalize` squeezes.
+    ///
+    /// # Returns
+    /// `InvalidRate` when `rate` and `capacity` do not split the state.
+    pub fn new(
+        rate: usize, 
+        capacity: usize, 
+        domain_suffix: u8, 
+        output_len: usize
+    ) -> Result<Self, Sha3Error> {
+        if rate == 0 || capacity == 0 || rate + capacity != 200 {
+            return Err(Sha3Error::InvalidRate { rate, capacity });
+        }
+
+        Ok(Self::init(rate, capacity, domain_suffix, output_len))
+    }
+
+    // Fixed parameter sets of the crate (SHA-3, SHAKE, Keccak, cSHAKE),
+    // which are valid by construction.
+    pub(crate) fn init(
+        rate: usize, 
+        capacity: usize, 
+        domain_suffix: u8, 
+        output_len: usize
+    ) -> Self {
+        Keccak {
+            rate,
+            capacity,
+            domain_suffix,
+            output_len,
+            state: [0u8; 200],
+            buffer: [0u8; 200],
+            buffer_len: 0,
+        }
+    }
+
+    /// Rate in bytes.
+    pub fn rate(&self) -> usize {
+        self.rate
+    }
+
+    /// Capacity in bytes.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Output length in bytes that `finalize` squeezes.
+    pub fn output_len(&self) -> usize {
+        self.output_len
+    }
 
-    let mut padded = keccak_padd(msg.to_vec(), rate_in_bits);
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        let mut i = 0;
 
-    let mut i = 0;
+        // Top up a partially filled block first.
+        if self.buffer_len > 0 {
+            while self.buffer_len < self.rate && i < data.len() {
+                self.buffer[self.buffer_len] = data[i];
+                self.buffer_len += 1;
+                i += 1;
+            }
 
-    // Absorb part.
-    while i < padded.len() {
-        let mut j = 0;
+            if self.buffer_len < self.rate {
+                return;
+            }
 
-        while j < rate_in_bytes && (i + j) < padded.len() {
-            state[j] ^= padded[i + j];
-            j += 1;
+            let block = self.buffer;
+            self.absorb_block(&block[..self.rate]);
+            self.buffer_len = 0;
         }
 
-        let lanes = state_to_lanes(&state);
-        let new_lanes = keccak_permutation(&lanes, 24);
-        state = lanes_to_state(&new_lanes);
+        // Absorb whole blocks directly from the input.
+        while data.len() - i >= self.rate {
+            self.absorb_block(&data[i..i + self.rate]);
+            i += self.rate;
+        }
+
+        // Keep the remainder for the next call.
+        while i < data.len() {
+            self.buffer[self.buffer_len] = data[i];
+            self.buffer_len += 1;
+            i += 1;
+        }
+    }
+
+    /// Pad, absorb the last block(s), and squeeze `output_len` bytes.
+    pub fn finalize(self) -> Vec<u8> {
+        let mut new_msg_string = vec![0u8; self.output_len];
+        self.finalize_xof().read(&mut new_msg_string);
+        new_msg_string
+    }
+
+    /// Pad and absorb the last block(s), then hand the state over to a
+    /// reader that squeezes as many bytes as asked for.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.absorb_padding();
 
-        i += rate_in_bytes;
+        XofReader {
+            rate: self.rate,
+            state: self.state,
+            pos: 0,
+        }
     }
 
-    let mut new_msg_string: Vec<u8> = Vec::new();
+    // Pad the buffered tail with the domain suffix and absorb it.
+    fn absorb_padding(&mut self) {
+        let padded = keccak_padd(
+            self.buffer[..self.buffer_len].to_vec(), 
+            self.rate * 8, 
+            self.domain_suffix
+        );
+        self.buffer_len = 0;
+
+        let mut i = 0;
+        while i < padded.len() {
+            self.absorb_block(&padded[i..i + self.rate]);
+            i += self.rate;
+        }
+    }
 
-    // Squeeze part.
-    while new_msg_string.len() < output_length {
-        let mut j = 0;
-        while j < rate_in_bytes && new_msg_string.len() < output_length {
-            new_msg_string.push(state[j]);
-            j += 1;
+    // Absorb part: XOR one block into the state, then permute.
+    fn absorb_block(&mut self, block: &[u8]) {
+        for (byte, b) in self.state.iter_mut().zip(&block[..self.rate]) {
+            *byte ^= b;
         }
 
-        // Truncate.
-        if new_msg_string.len() >= output_length {
-            break;
-        };
-    
-        let lanes = state_to_lanes(&state);
+        self.permute();
+    }
+
+    // Keccak-f[1600] on the byte state.
+    fn permute(&mut self) {
+        let lanes = state_to_lanes(&self.state);
         let new_lanes = keccak_permutation(&lanes, 24);
-        state = lanes_to_state(&new_lanes);
+        self.state = lanes_to_state(&new_lanes);
+    }
+}
+
+/// Squeeze part of the sponge, detached from the absorbing context.
+///
+/// # Description
+/// Every `read` continues exactly where the previous one stopped: bytes are
+/// taken from the first `rate` bytes of the state and the permutation only
+/// runs once all of them are used up.
+///
+/// # Reference
+/// [FIPS 202](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf),
+/// section 4, steps 7-10 of Algorithm 8.
+#[derive(Clone)]
+pub struct XofReader {
+    rate: usize,
+    state: [u8; 200],
+    pos: usize,
+}
 
+impl XofReader {
+    /// Fill `out` with the next `out.len()` bytes of output.
+    pub fn read(&mut self, out: &mut [u8]) {
+        for byte in out.iter_mut() {
+            if self.pos == self.rate {
+                self.permute();
+                self.pos = 0;
+            }
+
+            *byte = self.state[self.pos];
+            self.pos += 1;
+        }
+    }
+
+    fn permute(&mut self) {
+        let lanes = state_to_lanes(&self.state);
+        let new_lanes = keccak_permutation(&lanes, 24);
+        self.state = lanes_to_state(&new_lanes);
     }
-    new_msg_string
+}
+
+// Sponge construction: to absorb an arbitrary number of input bits into the
+// state function, to afterwards squeeze an arbitrary number of output bits.
+//
+// Runs the `Keccak` engine with the SHA3-256 parameters.
+pub fn sponge(msg: &[u8]) -> Vec<u8> {
+    let mut keccak = Keccak::init(136, 64, 0x06, 32);
+    keccak.update(msg);
+    keccak.finalize()
 }
 
 #[cfg(test)]
@@ -94,4 +258,50 @@ mod test {
         );
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_squeezes_past_one_rate() {
+        // SHAKE128 parameters, 200 output bytes need a second permutation.
+        let mut keccak = Keccak::new(168, 32, 0x1f, 200).unwrap();
+        keccak.update(b"");
+        let out = keccak.finalize();
+        let hex = bytes_to_hex(&out[168..]);
+        assert_eq!(
+            hex,
+            b"767be1fda69419dfb927e9df07348b19\
+              6691abaeb580b32def58538b8d23f877"
+        );
+    }
+
+    #[test]
+    fn test_reads_continue_where_they_stopped() {
+        let mut keccak = Keccak::new(168, 32, 0x1f, 200).unwrap();
+        keccak.update(b"abc");
+        let expected = keccak.clone().finalize();
+
+        let mut reader = keccak.finalize_xof();
+        let mut out = vec![0u8; 200];
+        let (first, rest) = out.split_at_mut(5);
+        let (second, third) = rest.split_at_mut(163);
+        reader.read(first);
+        reader.read(second);
+        reader.read(third);
+
+        assert_eq!((out), (expected));
+    }
+
+    #[test]
+    fn test_rejects_rates_that_do_not_split_the_state() {
+        assert_eq!(
+            (Keccak::new(0, 200, 0x06, 32).err()),
+            (Some(Sha3Error::InvalidRate { rate: 0, capacity: 200 }))
+        );
+        assert_eq!(
+            (Keccak::new(200, 0, 0x06, 32).err()),
+            (Some(Sha3Error::InvalidRate { rate: 200, capacity: 0 }))
+        );
+        assert_eq!(
+            (Keccak::new(136, 32, 0x06, 32).err()),
+            (Some(Sha3Error::InvalidRate { rate: 136, capacity: 32 }))
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use crate::sha3::{ encode_string, right_encode, Keccak, XofReader };
+use crate::sha3::cshake::cshake_engine;
+
+// TupleHash (NIST SP 800-185, section 5): cSHAKE with function name
+// "TupleHash" over the `encode_string` of every tuple element, so that
+// ("ab", "c") and ("a", "bc") hash differently.
+
+/// TupleHash128 context, fed one tuple element at a time.
+#[derive(Clone)]
+pub struct TupleHash128 {
+    inner: Keccak,
+}
+
+impl TupleHash128 {
+    pub fn new(customization: &[u8]) -> Self {
+        TupleHash128 {
+            inner: cshake_engine(168, b"TupleHash", customization),
+        }
+    }
+
+    /// Absorb the next element of the tuple.
+    pub fn push(&mut self, item: &[u8]) {
+        self.inner.update(&encode_string(item));
+    }
+
+    /// TupleHash128: digest of exactly `out_len` bytes.
+    pub fn finalize(mut self, out_len: usize) -> Vec<u8> {
+        self.inner.update(&right_encode(out_len as u64 * 8));
+
+        let mut out = vec![0u8; out_len];
+        self.inner.finalize_xof().read(&mut out);
+        out
+    }
+
+    /// TupleHashXOF128: output reader of arbitrary length.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.inner.update(&right_encode(0));
+        self.inner.finalize_xof()
+    }
+}
+
+/// TupleHash128: `out_len`-byte digest of `tuple`.
+pub fn tuple_hash128(
+    tuple: &[&[u8]], 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut hasher = TupleHash128::new(customization);
+    for item in tuple {
+        hasher.push(item);
+    }
+    hasher.finalize(out_len)
+}
+
+/// TupleHashXOF128: first `out_len` bytes of the XOF output.
+pub fn tuple_hash_xof128(
+    tuple: &[&[u8]], 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut hasher = TupleHash128::new(customization);
+    for item in tuple {
+        hasher.push(item);
+    }
+
+    let mut out = vec![0u8; out_len];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+/// TupleHash256 context, fed one tuple element at a time.
+#[derive(Clone)]
+pub struct TupleHash256 {
+    inner: Keccak,
+}
+
+impl TupleHash256 {
+    pub fn new(customization: &[u8]) -> Self {
+        TupleHash256 {
+            inner: cshake_engine(136, b"TupleHash", customization),
+        }
+    }
+
+    /// Absorb the next element of the tuple.
+    pub fn push(&mut self, item: &[u8]) {
+        self.inner.update(&encode_string(item));
+    }
+
+    /// TupleHash256: digest of exactly `out_len` bytes.
+    pub fn finalize(mut self, out_len: usize) -> Vec<u8> {
+        self.inner.update(&right_encode(out_len as u64 * 8));
+
+        let mut out = vec![0u8; out_len];
+        self.inner.finalize_xof().read(&mut out);
+        out
+    }
+
+    /// TupleHashXOF256: output reader of arbitrary length.
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.inner.update(&right_encode(0));
+        self.inner.finalize_xof()
+    }
+}
+
+/// TupleHash256: `out_len`-byte digest of `tuple`.
+pub fn tuple_hash256(
+    tuple: &[&[u8]], 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut hasher = TupleHash256::new(customization);
+    for item in tuple {
+        hasher.push(item);
+    }
+    hasher.finalize(out_len)
+}
+
+/// TupleHashXOF256: first `out_len` bytes of the XOF output.
+pub fn tuple_hash_xof256(
+    tuple: &[&[u8]], 
+    out_len: usize, 
+    customization: &[u8]
+) -> Vec<u8> {
+    let mut hasher = TupleHash256::new(customization);
+    for item in tuple {
+        hasher.push(item);
+    }
+
+    let mut out = vec![0u8; out_len];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::bytes_to_hex;
+
+    const A: &[u8] = &[0x00, 0x01, 0x02];
+    const B: &[u8] = &[0x10, 0x11, 0x12, 0x13, 0x14, 0x15];
+    const C: &[u8] = &[0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28];
+
+    #[test]
+    fn tuple_hash128_nist_sample_1() {
+        let out = tuple_hash128(&[A, B], 32, b"");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"c5d8786c1afb9b82111ab34b65b2c004\
+              8fa64e6d48e263264ce1707d3ffc8ed1"
+        );
+    }
+
+    #[test]
+    fn tuple_hash128_nist_sample_2() {
+        let out = tuple_hash128(&[A, B], 32, b"My Tuple App");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"75cdb20ff4db1154e841d758e24160c5\
+              4bae86eb8c13e7f5f40eb35588e96dfb"
+        );
+    }
+
+    #[test]
+    fn tuple_hash128_nist_sample_3() {
+        let out = tuple_hash128(&[A, B, C], 32, b"My Tuple App");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"e60f202c89a2631eda8d4c588ca5fd07\
+              f39e5151998deccf973adb3804bb6e84"
+        );
+    }
+
+    #[test]
+    fn tuple_hash256_nist_sample_4() {
+        let out = tuple_hash256(&[A, B], 64, b"");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"cfb7058caca5e668f81a12a20a2195ce\
+              97a925f1dba3e7449a56f82201ec6073\
+              11ac2696b1ab5ea2352df1423bde7bd4\
+              bb78c9aed1a853c78672f9eb23bbe194"
+        );
+    }
+
+    #[test]
+    fn tuple_hash256_nist_sample_6() {
+        let out = tuple_hash256(&[A, B, C], 64, b"My Tuple App");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"45000be63f9b6bfd89f54717670f69a9\
+              bc763591a4f05c50d68891a744bcc6e7\
+              d6d5b5e82c018da999ed35b0bb49c967\
+              8e526abd8e85c13ed254021db9e790ce"
+        );
+    }
+
+    #[test]
+    fn tuple_hash_xof128_nist_sample_1() {
+        let out = tuple_hash_xof128(&[A, B], 32, b"");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"2f103cd7c32320353495c68de1a81292\
+              45c6325f6f2a3d608d92179c96e68488"
+        );
+    }
+
+    #[test]
+    fn tuple_hash_xof256_nist_sample_6() {
+        let out = tuple_hash_xof256(&[A, B, C], 64, b"My Tuple App");
+        let hex = bytes_to_hex(&out);
+        assert_eq!(
+            hex,
+            b"0c59b11464f2336c34663ed51b2b950b\
+              ec743610856f36c28d1d088d8a244628\
+              4dd09830a6a178dc752376199fae935d\
+              86cfdee5913d4922dfd369b66a53c897"
+        );
+    }
+
+    #[test]
+    fn element_boundaries_matter() {
+        let split_late = tuple_hash128(&[b"ab", b"c"], 32, b"");
+        let split_early = tuple_hash128(&[b"a", b"bc"], 32, b"");
+        assert_ne!((split_late), (split_early));
+    }
+}
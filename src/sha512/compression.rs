@@ -0,0 +1,222 @@
+use crate::utils64::{
+    big_sigma0_64, big_sigma1_64, ch64, maj64, z64
+};
+use crate::sha512::K;
+
+/// SHA512 initial hash value H(0).
+pub const H512: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 
+    0xa54ff53a5f1d36f1, 0x510e527fade682d1, 0x9b05688c2b3e6c1f, 
+    0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+/// SHA384 initial hash value H(0).
+pub const H384: [u64; 8] = [
+    0xcbbb9d5dc1059ed8, 0x629a292a367cd507, 0x9159015a3070dd17, 
+    0x152fecd8f70e5939, 0x67332667ffc00b31, 0x8eb44a8768581511, 
+    0xdb0c2e0d64f98fa7, 0x47b5481dbefa4fa4,
+];
+
+/// SHA512/224 initial hash value H(0), as generated by `sha512_t_iv(224)`.
+pub const H512_224: [u64; 8] = [
+    0x8c3d37c819544da2, 0x73e1996689dcd4d6, 0x1dfab7ae32ff9c82, 
+    0x679dd514582f9fcf, 0x0f6d2b697bd44da8, 0x77e36f7304c48942, 
+    0x3f9d85a86a1d36c8, 0x1112e6ad91d692a1,
+];
+
+/// SHA512/256 initial hash value H(0), as generated by `sha512_t_iv(256)`.
+pub const H512_256: [u64; 8] = [
+    0x22312194fc2bf72c, 0x9f555fa3c84c64c2, 0x2393b86b6f53b151, 
+    0x963877195940eabd, 0x96283ee2a88effe3, 0xbe5e1e2553863992, 
+    0x2b0199fc2c85b8aa, 0x0eb72ddc81c52ca2,
+];
+
+/// SHA512 Message Digestion Algorithm.
+///
+/// # Argument
+/// Takes scheduled message as Vec<[u64; 80]>, then compression starts.
+///
+/// # Description
+/// - Initialize (h0, h1, h2, h3, h4, h5, h6, h7) with the hash value H(0).
+/// - Run `compress_block` on every scheduled block.
+///
+/// # Returns
+/// Final 8-word digest as [u64; 8] (512 bits).
+///
+/// # Reference
+/// Based on the FIPS PUB 180-4 specification:
+/// [FIPS PUB 180-4](https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.180-4.pdf)
+pub fn compress(msg_blocks: &[[u64; 80]]) -> [u64; 8] {
+    compress_with_iv(H512, msg_blocks)
+}
+
+/// SHA512 compression starting from a given initial hash value.
+///
+/// SHA-384 and SHA-512/t run the exact same rounds, only starting from their
+/// own H(0).
+pub fn compress_with_iv(iv: [u64; 8], msg_blocks: &[[u64; 80]]) -> [u64; 8] {
+    // Hash values.
+    let mut h = iv;
+
+    for w in msg_blocks {
+        compress_block(&mut h, w);
+    }
+
+    // Digested state.
+    h
+}
+
+/// SHA512 compression of a single scheduled block.
+///
+/// # Description
+/// - Initialize (a, b, c, d, e, f, g, h), with the 8 working variables: 
+///   (h0, h1, h2, h3, h4, h5, h6, h7).
+/// - Implement all round operations (Big sigma 0 & 1, Small sigma 0 & 1,
+///   Ch, Maj).
+/// - Compute the i-th intermediate hash value H(i) in place.
+pub fn compress_block(state: &mut [u64; 8], w: &[u64; 80]) {
+    // Initialized working variables.
+    let mut a = state[0];
+    let mut b = state[1];
+    let mut c = state[2];
+    let mut d = state[3];
+    let mut e = state[4];
+    let mut f = state[5];
+    let mut g = state[6];
+    let mut h = state[7];
+
+    // Implement 80 round operations.
+    for t in 0..80 {
+
+        let temp1 = z64(
+            z64(
+                z64(
+                    z64(
+                        h, 
+                        big_sigma1_64(e)), 
+                    ch64(e, f, g)), 
+                K[t]), 
+            w[t]
+        );
+        let temp2 = z64(
+            big_sigma0_64(a), 
+            maj64(a, b, c)
+        );
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+
+    }
+
+    // Compute the i-th intermediate hash value H(i).
+    state[0] = z64(a, state[0]);
+    state[1] = z64(b, state[1]);
+    state[2] = z64(c, state[2]);
+    state[3] = z64(d, state[3]);
+    state[4] = z64(e, state[4]);
+    state[5] = z64(f, state[5]);
+    state[6] = z64(g, state[6]);
+    state[7] = z64(h, state[7]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha512::{ schedule };
+    use crate::padd_pars::{
+        big_endian_padd64, big_endian_pars64        
+    };
+
+    #[test]
+    fn sha512_computes_empty_string() {
+        let msg = b"";
+        let padded = big_endian_padd64(msg);
+        let parsed = big_endian_pars64(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+        let expected = [
+            0xcf83e1357eefb8bd, 
+            0xf1542850d66d8007, 
+            0xd620e4050b5715dc,
+            0x83f4a921d36ce9ce,
+            0x47d0d13c5d85f2b0,
+            0xff8318d2877eec2f, 
+            0x63b931bd47417a81,
+            0xa538327af927da3e
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_computes_abc() {
+        let msg = b"abc";
+        let padded = big_endian_padd64(msg);
+        let parsed = big_endian_pars64(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+        let expected = [
+            0xddaf35a193617aba,
+            0xcc417349ae204131,
+            0x12e6fa4e89a97ea2, 
+            0x0a9eeee64b55d39a,
+            0x2192992a274fc1a8, 
+            0x36ba3c23a3feebbd,
+            0x454d4423643ce80e,
+            0x2a9ac94fa54ca49f
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_computes_brown_fox() {
+        let msg = b"The quick brown fox jumps over the lazy dog";
+        let padded = big_endian_padd64(msg);
+        let parsed = big_endian_pars64(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+        let expected = [
+            0x07e547d9586f6a73, 
+            0xf73fbac0435ed769,
+            0x51218fb7d0c8d788, 
+            0xa309d785436bbb64,
+            0x2e93a252a954f239,
+            0x12547d1e8a3b5ed6,
+            0xe1bfd7097821233f,
+            0xa0538f3db854fee6
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_computes_1_000_000_as() {
+        let msg = b"a".repeat(1_000_000);
+        let padded = big_endian_padd64(&msg);
+        let parsed = big_endian_pars64(padded);
+        let scheduled = schedule(&parsed);
+        
+        let result = compress(&scheduled);
+        let expected = [
+            0xe718483d0ce76964,
+            0x4e2e42c7bc15b463,
+            0x8e1f98b13b204428,
+            0x5632a803afa973eb,
+            0xde0ff244877ea60a,
+            0x4cb0432ce577c31b,
+            0xeb009c5c2c49aa2e,
+            0x4eadb217ad8cc09b
+        ];
+
+        assert_eq!((result), (expected));
+    }
+}
\ No newline at end of file
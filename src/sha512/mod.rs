@@ -1,6 +1,20 @@
+pub mod compression;
 pub mod constants;
 pub mod schedule;
+pub mod sha384;
+#[allow(clippy::module_inception)]
 pub mod sha512;
+pub mod sha512_t;
+pub mod to_bytes;
 
 pub use constants::K;
-pub use schedule::schedule;
\ No newline at end of file
+pub use compression::{
+    compress, compress_with_iv, compress_block, H384, H512, H512_224, H512_256
+};
+pub use schedule::{ schedule, schedule_block };
+pub use to_bytes::to_bytes;
+pub use sha384::{ sha384, Sha384 };
+pub use sha512::{ sha512, Sha512 };
+pub use sha512_t::{
+    sha512_t_iv, sha512_224, sha512_256, Sha512_224, Sha512_256
+};
\ No newline at end of file
@@ -17,26 +17,30 @@ use crate::utils64::{
 /// Scheduled message as vector [u32; 80] for downstream compression.
 pub fn schedule(msg_as_blocks: &[[u64; 16]]) -> Vec<[u64; 80]> {
     let mut w: Vec<[u64; 80]> = Vec::new();
-    let mut m = [0u64; 80];
 
     for block in msg_as_blocks {
-        for i in 0..16 {
-            m[i] = block[i];
-        }
+        w.push(schedule_block(block));
+    }
 
-        for t in 16..80 {
-            m[t] = z64(
-                z64(
-                    z64(
-                        small_sigma1_64(m[t-2]), 
-                        m[t-7]), 
-                    small_sigma0_64(m[t-15])), 
-                m[t-16]
-            );
-        }
+    w
+}
 
-        w.push(m);
+/// Expand a single 1024-bit block into its 80-word message schedule.
+pub fn schedule_block(block: &[u64; 16]) -> [u64; 80] {
+    let mut m = [0u64; 80];
+
+    m[..16].copy_from_slice(block);
+
+    for t in 16..80 {
+        m[t] = z64(
+            z64(
+                z64(
+                    small_sigma1_64(m[t-2]), 
+                    m[t-7]), 
+                small_sigma0_64(m[t-15])), 
+            m[t-16]
+        );
     }
 
-    w
+    m
 }
\ No newline at end of file
@@ -0,0 +1,160 @@
+use crate::digest::Digest;
+use crate::sha512::{ Sha512, H384 };
+use crate::utils::truncate;
+
+/// SHA-384 streaming context.
+///
+/// A `Sha512` context started from `H384`, whose digest is cut down to the
+/// leftmost 48 bytes.
+#[derive(Clone)]
+pub struct Sha384 {
+    inner: Sha512,
+}
+
+impl Sha384 {
+    pub fn new() -> Self {
+        Sha384 {
+            inner: Sha512::with_iv(H384),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad the buffered tail and return the 48-byte digest.
+    pub fn finalize(self) -> [u8; 48] {
+        truncate(&self.inner.finalize())
+    }
+}
+
+impl Default for Sha384 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha384 {
+    const OUTPUT_SIZE: usize = 48;
+    const BLOCK_SIZE: usize = 128;
+    type Output = [u8; 48];
+
+    fn new() -> Self { Sha384::new() }
+    fn update(&mut self, data: &[u8]) { Sha384::update(self, data) }
+    fn finalize(self) -> [u8; 48] { Sha384::finalize(self) }
+}
+
+/// SHA-384: SHA-512 with a different initial hash value, truncated to 384 
+/// bits.
+///
+/// # Arguments
+/// - `msg`: Message as a byte slice (`&[u8]`). 
+///
+/// # Description
+/// - **Padding, Parsing, Scheduling:** Identical to SHA-512.
+/// - **Compression:** Same rounds as SHA-512, started from `H384`.
+/// - **Truncation:** Only the leftmost six state words (48 bytes) are kept.
+///
+/// All steps run block by block through the `Sha384` streaming context.
+///
+/// # Returns
+/// A 48-byte array representing the 384-bit hash.
+///
+/// # Reference
+/// [FIPS PUB 180-4](https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.180-4.pdf)
+pub fn sha384(msg: &[u8]) -> [u8; 48] {
+    let mut hasher = Sha384::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sha384_digests_empty_string() {
+        let msg = b"";
+        let result = sha384(msg);
+        let expected = [
+            0x38, 0xb0, 0x60, 0xa7, 0x51, 0xac, 0x96, 0x38,
+            0x4c, 0xd9, 0x32, 0x7e, 0xb1, 0xb1, 0xe3, 0x6a,
+            0x21, 0xfd, 0xb7, 0x11, 0x14, 0xbe, 0x07, 0x43,
+            0x4c, 0x0c, 0xc7, 0xbf, 0x63, 0xf6, 0xe1, 0xda,
+            0x27, 0x4e, 0xde, 0xbf, 0xe7, 0x6f, 0x65, 0xfb,
+            0xd5, 0x1a, 0xd2, 0xf1, 0x48, 0x98, 0xb9, 0x5b,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha384_digests_abc() {
+        let msg = b"abc";
+        let result = sha384(msg);
+        let expected = [
+            0xcb, 0x00, 0x75, 0x3f, 0x45, 0xa3, 0x5e, 0x8b,
+            0xb5, 0xa0, 0x3d, 0x69, 0x9a, 0xc6, 0x50, 0x07,
+            0x27, 0x2c, 0x32, 0xab, 0x0e, 0xde, 0xd1, 0x63,
+            0x1a, 0x8b, 0x60, 0x5a, 0x43, 0xff, 0x5b, 0xed,
+            0x80, 0x86, 0x07, 0x2b, 0xa1, 0xe7, 0xcc, 0x23,
+            0x58, 0xba, 0xec, 0xa1, 0x34, 0xc8, 0x25, 0xa7,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha384_digests_896_bits() {
+        let msg = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                    hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+        let result = sha384(msg);
+        let expected = [
+            0x09, 0x33, 0x0c, 0x33, 0xf7, 0x11, 0x47, 0xe8,
+            0x3d, 0x19, 0x2f, 0xc7, 0x82, 0xcd, 0x1b, 0x47,
+            0x53, 0x11, 0x1b, 0x17, 0x3b, 0x3b, 0x05, 0xd2,
+            0x2f, 0xa0, 0x80, 0x86, 0xe3, 0xb0, 0xf7, 0x12,
+            0xfc, 0xc7, 0xc7, 0x1a, 0x55, 0x7e, 0x2d, 0xb9,
+            0x66, 0xc3, 0xe9, 0xfa, 0x91, 0x74, 0x60, 0x39,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha384_matches_cavp_short_msg_len_8() {
+        // CAVP SHA384ShortMsg.rsp, Len = 8.
+        let msg = [0xc5];
+        let result = sha384(&msg);
+        let expected = [
+            0xb5, 0x2b, 0x72, 0xda, 0x75, 0xd0, 0x66, 0x63,
+            0x79, 0xe2, 0x0f, 0x9b, 0x4a, 0x79, 0xc3, 0x3a,
+            0x32, 0x9a, 0x01, 0xf0, 0x6a, 0x2f, 0xb7, 0x86,
+            0x5c, 0x90, 0x62, 0xa2, 0x8c, 0x1d, 0xe8, 0x60,
+            0xba, 0x43, 0x2e, 0xdf, 0xd8, 0x6b, 0x4c, 0xb1,
+            0xcb, 0x8a, 0x75, 0xb4, 0x60, 0x76, 0xe3, 0xb1,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha384_streams_one_million_a() {
+        let msg = b"a".repeat(1_000_000);
+        let mut hasher = Sha384::new();
+        for chunk in msg.chunks(4_099) {
+            hasher.update(chunk);
+        }
+        let expected = [
+            0x9d, 0x0e, 0x18, 0x09, 0x71, 0x64, 0x74, 0xcb,
+            0x08, 0x6e, 0x83, 0x4e, 0x31, 0x0a, 0x4a, 0x1c,
+            0xed, 0x14, 0x9e, 0x9c, 0x00, 0xf2, 0x48, 0x52,
+            0x79, 0x72, 0xce, 0xc5, 0x70, 0x4c, 0x2a, 0x5b,
+            0x07, 0xb8, 0xb3, 0xdc, 0x38, 0xec, 0xc4, 0xeb,
+            0xae, 0x97, 0xdd, 0xd8, 0x7f, 0x3d, 0x89, 0x85,
+        ];
+
+        assert_eq!((hasher.finalize()), (expected));
+    }
+}
\ No newline at end of file
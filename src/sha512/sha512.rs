@@ -1,192 +1,179 @@
-use crate::utils64::{
-    big_sigma0_64, big_sigma1_64, ch64, maj64, z64
+use crate::digest::Digest;
+use crate::padd_pars::{
+    big_endian_padd64_tail, big_endian_pars64_block, BlockBuffer
 };
-use crate::sha512::K;
 
-/// SHA512 Message Digestion Algorithm.
+use crate::sha512::{
+    schedule_block, compress_block, to_bytes, H512
+};
+
+/// SHA-512 streaming context.
+///
+/// Buffers at most one partial 128-byte block; every block that fills up is
+/// parsed, scheduled and compressed straight away.
+#[derive(Clone)]
+pub struct Sha512 {
+    state: [u64; 8],
+    buffer: BlockBuffer<128>,
+    msg_len: u128,
+}
+
+impl Sha512 {
+    pub fn new() -> Self {
+        Self::with_iv(H512)
+    }
+
+    /// Start a new hash from a custom initial hash value.
+    pub(crate) fn with_iv(iv: [u64; 8]) -> Self {
+        Sha512 {
+            state: iv,
+            buffer: BlockBuffer::new(),
+            msg_len: 0,
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.msg_len = self.msg_len.wrapping_add(data.len() as u128);
+
+        let state = &mut self.state;
+        self.buffer.feed(data, |bytes| process_block(state, bytes));
+    }
+
+    /// Pad the buffered tail and return the 64-byte digest.
+    pub fn finalize(mut self) -> [u8; 64] {
+        let padded = big_endian_padd64_tail(self.buffer.tail(), self.msg_len);
+
+        let mut j = 0;
+        while j < padded.len() {
+            process_block(&mut self.state, &padded[j..j + 128]);
+            j += 128;
+        }
+
+        to_bytes(self.state)
+    }
+}
+
+impl Default for Sha512 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha512 {
+    const OUTPUT_SIZE: usize = 64;
+    const BLOCK_SIZE: usize = 128;
+    type Output = [u8; 64];
+
+    fn new() -> Self { Sha512::new() }
+    fn update(&mut self, data: &[u8]) { Sha512::update(self, data) }
+    fn finalize(self) -> [u8; 64] { Sha512::finalize(self) }
+}
+
+/// Parse, schedule, and compress one 128-byte block into the state.
+fn process_block(state: &mut [u64; 8], bytes: &[u8]) {
+    let block = big_endian_pars64_block(bytes);
+    let w = schedule_block(&block);
+    compress_block(state, &w);
+}
+
+/// SHA-512: Pads, parses, schedules, and compresses a message into a 512-bit 
+/// hash.
 ///
-/// # Argument
-/// Takes scheduled message as Vec<[u64; 80]>, then compression starts.
+/// # Arguments
+/// - `msg`: Message as a byte slice (`&[u8]`). 
 ///
 /// # Description
-/// - Initialize (a, b, c, d, e, f, g, h), with the 8 working variables: 
-///   (h0, h1, h2, h3, h4, h5, h6, h7).
-/// - Implement all round operations (Big sigma 0 & 1, Small sigma 0 & 1,
-///   Ch, Maj).
-/// - Compute the i-th intermediate hash value H(i)
+/// - **Padding:** Big-endian padding to a multiple of 1024 bits, with a
+///   128-bit length field.
+/// - **Parsing:** Splits the padded message into 16 × 64-bit word blocks.
+/// - **Scheduling:** Expands each block into 80 words using `σ0` and `σ1`.
+/// - **Compression:** Updates the eight word state across all blocks.
+///
+/// All steps run block by block through the `Sha512` streaming context.
 ///
 /// # Returns
-/// Final 8-word digest as [u64; 8] (512 bits).
+/// A 64-byte array representing the 512-bit hash.
 ///
 /// # Reference
-/// Based on the FIPS PUB 180-4 specification:
 /// [FIPS PUB 180-4](https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.180-4.pdf)
-fn sha512(msg_blocks: &[[u64; 80]]) -> [u64; 8] {
-    // Hash values.
-    let mut h0: u64 = 0x6a09e667f3bcc908;
-    let mut h1: u64 = 0xbb67ae8584caa73b;
-    let mut h2: u64 = 0x3c6ef372fe94f82b;
-    let mut h3: u64 = 0xa54ff53a5f1d36f1;
-    let mut h4: u64 = 0x510e527fade682d1;
-    let mut h5: u64 = 0x9b05688c2b3e6c1f;
-    let mut h6: u64 = 0x1f83d9abfb41bd6b;
-    let mut h7: u64 = 0x5be0cd19137e2179;
-
-    for w in msg_blocks {
-        // Initialized working variables.
-        let mut a = h0;
-        let mut b = h1;
-        let mut c = h2;
-        let mut d = h3;
-        let mut e = h4;
-        let mut f = h5;
-        let mut g = h6;
-        let mut h = h7;
-
-        // Implement 80 round operations.
-        for t in 0..80 {
-
-            let temp1 = z64(
-                z64(
-                    z64(
-                        z64(
-                            h, 
-                            big_sigma1_64(e)), 
-                        ch64(e, f, g)), 
-                    K[t]), 
-                w[t]
-            );
-            let temp2 = z64(
-                big_sigma0_64(a), 
-                maj64(a, b, c)
-            );
-            h = g;
-            g = f;
-            f = e;
-            e = d.wrapping_add(temp1);
-            d = c;
-            c = b;
-            b = a;
-            a = temp1.wrapping_add(temp2);
-
-        }
-
-        // Compute the i-th intermediate hash value H(i).
-        h0 = z64(a, h0);
-        h1 = z64(b, h1);
-        h2 = z64(c, h2);
-        h3 = z64(d, h3);
-        h4 = z64(e, h4);
-        h5 = z64(f, h5);
-        h6 = z64(g, h6);
-        h7 = z64(h, h7);
-
-    }
-
-    // Digested state.
-    [
-        h0,
-        h1,
-        h2,
-        h3,
-        h4,
-        h5,
-        h6,
-        h7
-    ]
+pub fn sha512(msg: &[u8]) -> [u8; 64] {
+    let mut hasher = Sha512::new();
+    hasher.update(msg);
+    hasher.finalize()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::sha512::{ schedule };
-    use crate::padd_pars::{
-        big_endian_padd64, big_endian_pars64        
-    };
 
     #[test]
-    fn sha512_computes_empty_string() {
+    fn sha512_digests_empty_string() {
         let msg = b"";
-        let padded = big_endian_padd64(msg);
-        let parsed = big_endian_pars64(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha512(&scheduled);
+        let result = sha512(msg);
         let expected = [
-            0xcf83e1357eefb8bd, 
-            0xf1542850d66d8007, 
-            0xd620e4050b5715dc,
-            0x83f4a921d36ce9ce,
-            0x47d0d13c5d85f2b0,
-            0xff8318d2877eec2f, 
-            0x63b931bd47417a81,
-            0xa538327af927da3e
+            0xcf, 0x83, 0xe1, 0x35, 0x7e, 0xef, 0xb8, 0xbd,
+            0xf1, 0x54, 0x28, 0x50, 0xd6, 0x6d, 0x80, 0x07,
+            0xd6, 0x20, 0xe4, 0x05, 0x0b, 0x57, 0x15, 0xdc,
+            0x83, 0xf4, 0xa9, 0x21, 0xd3, 0x6c, 0xe9, 0xce,
+            0x47, 0xd0, 0xd1, 0x3c, 0x5d, 0x85, 0xf2, 0xb0,
+            0xff, 0x83, 0x18, 0xd2, 0x87, 0x7e, 0xec, 0x2f,
+            0x63, 0xb9, 0x31, 0xbd, 0x47, 0x41, 0x7a, 0x81,
+            0xa5, 0x38, 0x32, 0x7a, 0xf9, 0x27, 0xda, 0x3e,
         ];
 
         assert_eq!((result), (expected));
     }
 
     #[test]
-    fn sha512_computes_abc() {
+    fn sha512_digests_abc() {
         let msg = b"abc";
-        let padded = big_endian_padd64(msg);
-        let parsed = big_endian_pars64(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha512(&scheduled);
+        let result = sha512(msg);
         let expected = [
-            0xddaf35a193617aba,
-            0xcc417349ae204131,
-            0x12e6fa4e89a97ea2, 
-            0x0a9eeee64b55d39a,
-            0x2192992a274fc1a8, 
-            0x36ba3c23a3feebbd,
-            0x454d4423643ce80e,
-            0x2a9ac94fa54ca49f
+            0xdd, 0xaf, 0x35, 0xa1, 0x93, 0x61, 0x7a, 0xba,
+            0xcc, 0x41, 0x73, 0x49, 0xae, 0x20, 0x41, 0x31,
+            0x12, 0xe6, 0xfa, 0x4e, 0x89, 0xa9, 0x7e, 0xa2,
+            0x0a, 0x9e, 0xee, 0xe6, 0x4b, 0x55, 0xd3, 0x9a,
+            0x21, 0x92, 0x99, 0x2a, 0x27, 0x4f, 0xc1, 0xa8,
+            0x36, 0xba, 0x3c, 0x23, 0xa3, 0xfe, 0xeb, 0xbd,
+            0x45, 0x4d, 0x44, 0x23, 0x64, 0x3c, 0xe8, 0x0e,
+            0x2a, 0x9a, 0xc9, 0x4f, 0xa5, 0x4c, 0xa4, 0x9f,
         ];
 
         assert_eq!((result), (expected));
     }
 
     #[test]
-    fn sha512_computes_brown_fox() {
+    fn sha512_digests_brown_fox() {
         let msg = b"The quick brown fox jumps over the lazy dog";
-        let padded = big_endian_padd64(msg);
-        let parsed = big_endian_pars64(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha512(&scheduled);
+        let result = sha512(msg);
         let expected = [
-            0x07e547d9586f6a73, 
-            0xf73fbac0435ed769,
-            0x51218fb7d0c8d788, 
-            0xa309d785436bbb64,
-            0x2e93a252a954f239,
-            0x12547d1e8a3b5ed6,
-            0xe1bfd7097821233f,
-            0xa0538f3db854fee6
+            0x07, 0xe5, 0x47, 0xd9, 0x58, 0x6f, 0x6a, 0x73,
+            0xf7, 0x3f, 0xba, 0xc0, 0x43, 0x5e, 0xd7, 0x69,
+            0x51, 0x21, 0x8f, 0xb7, 0xd0, 0xc8, 0xd7, 0x88,
+            0xa3, 0x09, 0xd7, 0x85, 0x43, 0x6b, 0xbb, 0x64,
+            0x2e, 0x93, 0xa2, 0x52, 0xa9, 0x54, 0xf2, 0x39,
+            0x12, 0x54, 0x7d, 0x1e, 0x8a, 0x3b, 0x5e, 0xd6,
+            0xe1, 0xbf, 0xd7, 0x09, 0x78, 0x21, 0x23, 0x3f,
+            0xa0, 0x53, 0x8f, 0x3d, 0xb8, 0x54, 0xfe, 0xe6,
         ];
 
         assert_eq!((result), (expected));
     }
 
     #[test]
-    fn sha512_computes_1_000_000_as() {
+    fn sha512_digests_one_million_a() {
         let msg = b"a".repeat(1_000_000);
-        let padded = big_endian_padd64(&msg);
-        let parsed = big_endian_pars64(padded);
-        let scheduled = schedule(&parsed);
-        
-        let result = sha512(&scheduled);
+        let result = sha512(&msg);
         let expected = [
-            0xe718483d0ce76964,
-            0x4e2e42c7bc15b463,
-            0x8e1f98b13b204428,
-            0x5632a803afa973eb,
-            0xde0ff244877ea60a,
-            0x4cb0432ce577c31b,
-            0xeb009c5c2c49aa2e,
-            0x4eadb217ad8cc09b
+            0xe7, 0x18, 0x48, 0x3d, 0x0c, 0xe7, 0x69, 0x64,
+            0x4e, 0x2e, 0x42, 0xc7, 0xbc, 0x15, 0xb4, 0x63,
+            0x8e, 0x1f, 0x98, 0xb1, 0x3b, 0x20, 0x44, 0x28,
+            0x56, 0x32, 0xa8, 0x03, 0xaf, 0xa9, 0x73, 0xeb,
+            0xde, 0x0f, 0xf2, 0x44, 0x87, 0x7e, 0xa6, 0x0a,
+            0x4c, 0xb0, 0x43, 0x2c, 0xe5, 0x77, 0xc3, 0x1b,
+            0xeb, 0x00, 0x9c, 0x5c, 0x2c, 0x49, 0xaa, 0x2e,
+            0x4e, 0xad, 0xb2, 0x17, 0xad, 0x8c, 0xc0, 0x9b,
         ];
 
         assert_eq!((result), (expected));
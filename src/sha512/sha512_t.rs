@@ -0,0 +1,349 @@
+use crate::digest::Digest;
+use crate::padd_pars::{
+    big_endian_padd64, big_endian_pars64
+};
+
+use crate::sha512::{
+    schedule, compress_with_iv, Sha512, H512, H512_224, H512_256
+};
+use crate::utils::truncate;
+
+/// SHA-512/t IV generation function.
+///
+/// # Arguments
+/// - `t`: Output length in bits.
+///
+/// # Description
+/// - XOR every word of the SHA-512 H(0) with `0xa5a5a5a5a5a5a5a5`, giving
+///   H(0)''.
+/// - Hash the ASCII string "SHA-512/t" (with `t` in decimal) with SHA-512,
+///   started from H(0)'' instead of H(0).
+/// - The resulting state is the initial hash value of SHA-512/t.
+///
+/// # Returns
+/// `None` when `t` is 0, 384 or not below 512. Any other `t`, whole bytes
+/// or not, names a valid SHA-512/t.
+///
+/// # Reference
+/// [FIPS PUB 180-4 §5.3.6](https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.180-4.pdf)
+pub fn sha512_t_iv(t: usize) -> Option<[u64; 8]> {
+    if t == 0 || t == 384 || t >= 512 {
+        return None;
+    }
+
+    let mut h0 = H512;
+    for word in h0.iter_mut() {
+        *word ^= 0xa5a5a5a5a5a5a5a5;
+    }
+
+    let name = format!("SHA-512/{}", t);
+    let padded = big_endian_padd64(name.as_bytes());
+    let parsed = big_endian_pars64(padded);
+    let scheduled = schedule(&parsed);
+
+    Some(compress_with_iv(h0, &scheduled))
+}
+
+/// SHA-512/224 streaming context.
+///
+/// A `Sha512` context started from `H512_224`, whose digest is cut down to the
+/// leftmost 28 bytes.
+#[derive(Clone)]
+pub struct Sha512_224 {
+    inner: Sha512,
+}
+
+impl Sha512_224 {
+    pub fn new() -> Self {
+        Sha512_224 {
+            inner: Sha512::with_iv(H512_224),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad the buffered tail and return the 28-byte digest.
+    pub fn finalize(self) -> [u8; 28] {
+        truncate(&self.inner.finalize())
+    }
+}
+
+impl Default for Sha512_224 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha512_224 {
+    const OUTPUT_SIZE: usize = 28;
+    const BLOCK_SIZE: usize = 128;
+    type Output = [u8; 28];
+
+    fn new() -> Self { Sha512_224::new() }
+    fn update(&mut self, data: &[u8]) { Sha512_224::update(self, data) }
+    fn finalize(self) -> [u8; 28] { Sha512_224::finalize(self) }
+}
+
+/// SHA-512/224: SHA-512 from the `sha512_t_iv(224)` initial hash value,
+/// truncated to 224 bits.
+///
+/// # Arguments
+/// - `msg`: Message as a byte slice (`&[u8]`). 
+///
+/// # Returns
+/// A 28-byte array representing the 224-bit hash.
+///
+/// # Reference
+/// [FIPS PUB 180-4](https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.180-4.pdf)
+pub fn sha512_224(msg: &[u8]) -> [u8; 28] {
+    let mut hasher = Sha512_224::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+/// SHA-512/256 streaming context.
+///
+/// A `Sha512` context started from `H512_256`, whose digest is cut down to the
+/// leftmost 32 bytes.
+#[derive(Clone)]
+pub struct Sha512_256 {
+    inner: Sha512,
+}
+
+impl Sha512_256 {
+    pub fn new() -> Self {
+        Sha512_256 {
+            inner: Sha512::with_iv(H512_256),
+        }
+    }
+
+    /// Absorb the next chunk of the message.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Pad the buffered tail and return the 32-byte digest.
+    pub fn finalize(self) -> [u8; 32] {
+        truncate(&self.inner.finalize())
+    }
+}
+
+impl Default for Sha512_256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha512_256 {
+    const OUTPUT_SIZE: usize = 32;
+    const BLOCK_SIZE: usize = 128;
+    type Output = [u8; 32];
+
+    fn new() -> Self { Sha512_256::new() }
+    fn update(&mut self, data: &[u8]) { Sha512_256::update(self, data) }
+    fn finalize(self) -> [u8; 32] { Sha512_256::finalize(self) }
+}
+
+/// SHA-512/256: SHA-512 from the `sha512_t_iv(256)` initial hash value,
+/// truncated to 256 bits.
+///
+/// # Arguments
+/// - `msg`: Message as a byte slice (`&[u8]`). 
+///
+/// # Returns
+/// A 32-byte array representing the 256-bit hash.
+///
+/// # Reference
+/// [FIPS PUB 180-4](https://nvlpubs.nist.gov/nistpubs/fips/nist.fips.180-4.pdf)
+pub fn sha512_256(msg: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha512_256::new();
+    hasher.update(msg);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generates_the_published_sha512_224_iv() {
+        assert_eq!((sha512_t_iv(224)), (Some(H512_224)));
+    }
+
+    #[test]
+    fn generates_the_published_sha512_256_iv() {
+        assert_eq!((sha512_t_iv(256)), (Some(H512_256)));
+    }
+
+    #[test]
+    fn rejects_t_values_outside_of_the_family() {
+        assert_eq!((sha512_t_iv(0)), (None));
+        assert_eq!((sha512_t_iv(384)), (None));
+        assert_eq!((sha512_t_iv(512)), (None));
+    }
+
+    #[test]
+    fn generates_ivs_for_t_values_that_are_not_whole_bytes() {
+        // SHA-512/100: H(0)'' run over the ASCII string "SHA-512/100".
+        let expected = [
+            0x5a87893e985fd6f1, 0x023f2d1c5470be46, 0x4ff817f3cda97a6f,
+            0x929acf13a1d8690d, 0x84955dadf1796452, 0x25f7e120fba6ee3b,
+            0xb0420b4a33612642, 0x831b873e01735afa,
+        ];
+
+        assert_eq!((sha512_t_iv(100)), (Some(expected)));
+    }
+
+    #[test]
+    fn sha512_224_digests_empty_string() {
+        let msg = b"";
+        let result = sha512_224(msg);
+        let expected = [
+            0x6e, 0xd0, 0xdd, 0x02, 0x80, 0x6f, 0xa8, 0x9e,
+            0x25, 0xde, 0x06, 0x0c, 0x19, 0xd3, 0xac, 0x86,
+            0xca, 0xbb, 0x87, 0xd6, 0xa0, 0xdd, 0xd0, 0x5c,
+            0x33, 0x3b, 0x84, 0xf4,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_224_digests_abc() {
+        let msg = b"abc";
+        let result = sha512_224(msg);
+        let expected = [
+            0x46, 0x34, 0x27, 0x0f, 0x70, 0x7b, 0x6a, 0x54,
+            0xda, 0xae, 0x75, 0x30, 0x46, 0x08, 0x42, 0xe2,
+            0x0e, 0x37, 0xed, 0x26, 0x5c, 0xee, 0xe9, 0xa4,
+            0x3e, 0x89, 0x24, 0xaa,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_224_digests_896_bits() {
+        let msg = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                    hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+        let result = sha512_224(msg);
+        let expected = [
+            0x23, 0xfe, 0xc5, 0xbb, 0x94, 0xd6, 0x0b, 0x23,
+            0x30, 0x81, 0x92, 0x64, 0x0b, 0x0c, 0x45, 0x33,
+            0x35, 0xd6, 0x64, 0x73, 0x4f, 0xe4, 0x0e, 0x72,
+            0x68, 0x67, 0x4a, 0xf9,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_224_matches_cavp_short_msg_len_8() {
+        // CAVP SHA512_224ShortMsg.rsp, Len = 8.
+        let msg = [0xcf];
+        let result = sha512_224(&msg);
+        let expected = [
+            0x41, 0x99, 0x23, 0x9e, 0x87, 0xd4, 0x7b, 0x6f,
+            0xed, 0xa0, 0x16, 0x80, 0x2b, 0xf3, 0x67, 0xfb,
+            0x6e, 0x8b, 0x56, 0x55, 0xef, 0xf6, 0x22, 0x5c,
+            0xb2, 0x66, 0x8f, 0x4a,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_224_streams_one_million_a() {
+        let msg = b"a".repeat(1_000_000);
+        let mut hasher = Sha512_224::new();
+        for chunk in msg.chunks(4_099) {
+            hasher.update(chunk);
+        }
+        let expected = [
+            0x37, 0xab, 0x33, 0x1d, 0x76, 0xf0, 0xd3, 0x6d,
+            0xe4, 0x22, 0xbd, 0x0e, 0xde, 0xb2, 0x2a, 0x28,
+            0xac, 0xcd, 0x48, 0x7b, 0x7a, 0x84, 0x53, 0xae,
+            0x96, 0x5d, 0xd2, 0x87,
+        ];
+
+        assert_eq!((hasher.finalize()), (expected));
+    }
+
+    #[test]
+    fn sha512_256_digests_empty_string() {
+        let msg = b"";
+        let result = sha512_256(msg);
+        let expected = [
+            0xc6, 0x72, 0xb8, 0xd1, 0xef, 0x56, 0xed, 0x28,
+            0xab, 0x87, 0xc3, 0x62, 0x2c, 0x51, 0x14, 0x06,
+            0x9b, 0xdd, 0x3a, 0xd7, 0xb8, 0xf9, 0x73, 0x74,
+            0x98, 0xd0, 0xc0, 0x1e, 0xce, 0xf0, 0x96, 0x7a,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_256_digests_abc() {
+        let msg = b"abc";
+        let result = sha512_256(msg);
+        let expected = [
+            0x53, 0x04, 0x8e, 0x26, 0x81, 0x94, 0x1e, 0xf9,
+            0x9b, 0x2e, 0x29, 0xb7, 0x6b, 0x4c, 0x7d, 0xab,
+            0xe4, 0xc2, 0xd0, 0xc6, 0x34, 0xfc, 0x6d, 0x46,
+            0xe0, 0xe2, 0xf1, 0x31, 0x07, 0xe7, 0xaf, 0x23,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_256_digests_896_bits() {
+        let msg = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                    hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+        let result = sha512_256(msg);
+        let expected = [
+            0x39, 0x28, 0xe1, 0x84, 0xfb, 0x86, 0x90, 0xf8,
+            0x40, 0xda, 0x39, 0x88, 0x12, 0x1d, 0x31, 0xbe,
+            0x65, 0xcb, 0x9d, 0x3e, 0xf8, 0x3e, 0xe6, 0x14,
+            0x6f, 0xea, 0xc8, 0x61, 0xe1, 0x9b, 0x56, 0x3a,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_256_matches_cavp_short_msg_len_8() {
+        // CAVP SHA512_256ShortMsg.rsp, Len = 8.
+        let msg = [0xfa];
+        let result = sha512_256(&msg);
+        let expected = [
+            0xc4, 0xef, 0x36, 0x92, 0x3c, 0x64, 0xe5, 0x1e,
+            0x87, 0x57, 0x20, 0xe5, 0x50, 0x29, 0x8a, 0x5a,
+            0xb8, 0xa3, 0xf2, 0xf8, 0x75, 0xb1, 0xe1, 0xa4,
+            0xc9, 0xb9, 0x5b, 0xab, 0xf7, 0x34, 0x4f, 0xef,
+        ];
+
+        assert_eq!((result), (expected));
+    }
+
+    #[test]
+    fn sha512_256_streams_one_million_a() {
+        let msg = b"a".repeat(1_000_000);
+        let mut hasher = Sha512_256::new();
+        for chunk in msg.chunks(4_099) {
+            hasher.update(chunk);
+        }
+        let expected = [
+            0x9a, 0x59, 0xa0, 0x52, 0x93, 0x01, 0x87, 0xa9,
+            0x70, 0x38, 0xca, 0xe6, 0x92, 0xf3, 0x07, 0x08,
+            0xaa, 0x64, 0x91, 0x92, 0x3e, 0xf5, 0x19, 0x43,
+            0x94, 0xdc, 0x68, 0xd5, 0x6c, 0x74, 0xfb, 0x21,
+        ];
+
+        assert_eq!((hasher.finalize()), (expected));
+    }
+}
\ No newline at end of file
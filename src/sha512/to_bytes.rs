@@ -0,0 +1,44 @@
+/// Convert 8 × 64-bit words (digest state) into a 64-byte array.
+///
+/// Each 64-bit word is split into 8 bytes in big-endian order
+/// (most significant byte first).
+pub fn to_bytes(digest: [u64; 8]) -> [u8; 64] {
+    let mut outp = [0u8; 64];
+    let mut i = 0;
+
+    while i < 8 {
+        let word = digest[i];
+        let j = i * 8;
+
+        for k in 0..8 {
+            // Shift by (7 - k) * 8 to take the MSB first.
+            outp[j + k] = (word >> ((7 - k) * 8)) as u8;
+        }
+
+        i += 1;
+    }
+
+    outp
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn converts_words_most_significant_byte_first() {
+        let digest = [
+            0x0123456789abcdef, 0, 0, 0, 0, 0, 0, 0xfedcba9876543210,
+        ];
+        let result = to_bytes(digest);
+
+        assert_eq!(
+            (result[..8]),
+            ([0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef])
+        );
+        assert_eq!(
+            (result[56..]),
+            ([0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10])
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use std::fmt;
+
+/// Errors of AES-SIV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SivError {
+    /// The key must be 32, 48 or 64 bytes, two AES keys of equal size.
+    InvalidKeyLength { len: usize },
+    /// S2V takes at most 126 associated data components.
+    TooManyHeaders { count: usize },
+    /// The sealed message is shorter than the 16-byte synthetic IV.
+    CiphertextTooShort { len: usize },
+    /// The synthetic IV does not match, no plaintext is returned.
+    AuthenticationFailed,
+}
+
+impl fmt::Display for SivError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SivError::InvalidKeyLength { len } => {
+                write!(f, "SIV key length {len} is not 32, 48 or 64")
+            }
+            SivError::TooManyHeaders { count } => {
+                write!(f, "SIV takes at most 126 headers, got {count}")
+            }
+            SivError::CiphertextTooShort { len } => {
+                write!(f, "SIV ciphertext of {len} bytes has no full IV")
+            }
+            SivError::AuthenticationFailed => {
+                write!(f, "SIV synthetic IV mismatch")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SivError {}
@@ -0,0 +1,6 @@
+pub mod error;
+#[allow(clippy::module_inception)]
+pub mod siv;
+
+pub use error::SivError;
+pub use siv::{ open, seal, AesSiv, TAG_SIZE };
@@ -0,0 +1,336 @@
+use crate::aead::Aead;
+use crate::aes::{ Aes, BLOCK_SIZE };
+use crate::cmac::{ dbl, Cmac };
+use crate::modes::Ctr;
+use crate::siv::SivError;
+use crate::utils::ct_eq;
+
+/// Size in bytes of the synthetic IV, which doubles as the tag.
+pub const TAG_SIZE: usize = 16;
+
+// S2V has room for 126 associated data components besides the plaintext.
+const MAX_HEADERS: usize = 126;
+
+/// AES-SIV deterministic, nonce-misuse-resistant authenticated encryption.
+///
+/// # Description
+/// - The key is split in half: K1 keys S2V (a CMAC-based PRF over a
+///   vector of strings), K2 keys CTR mode.
+/// - S2V over the associated data components and the plaintext gives the
+///   synthetic IV V, which is both the tag and, with bits 31 and 63
+///   cleared, the initial counter block.
+/// - `encrypt` takes any list of associated data components, without a
+///   nonce the output only depends on the key and the inputs.
+/// - `seal` is the nonce-based use of section 3, the nonce is the last
+///   component after `aad`. Repeating a nonce only reveals whether the
+///   same message was sealed twice.
+/// - The output is V || ciphertext; `decrypt` and `open` decrypt first and
+///   return the plaintext only once S2V of it matches V.
+///
+/// # Reference
+/// [RFC 5297](https://www.rfc-editor.org/rfc/rfc5297)
+#[derive(Clone)]
+pub struct AesSiv {
+    mac: Cmac,
+    aes: Aes,
+}
+
+impl AesSiv {
+    /// AES-SIV-CMAC-256, -384 or -512, picked by the 32, 48 or 64-byte key.
+    pub fn new(key: &[u8]) -> Result<Self, SivError> {
+        if ![32, 48, 64].contains(&key.len()) {
+            return Err(SivError::InvalidKeyLength { len: key.len() });
+        }
+
+        let (k1, k2) = key.split_at(key.len() / 2);
+        let mac = Cmac::new(k1).expect("half of an SIV key is an AES key");
+        let aes = Aes::new(k2).expect("half of an SIV key is an AES key");
+
+        Ok(AesSiv { mac, aes })
+    }
+
+    /// Encrypt `plaintext` and authenticate it together with every one of
+    /// `headers`, returns V || ciphertext.
+    pub fn encrypt(
+        &self, 
+        headers: &[&[u8]], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, SivError> {
+        check_headers(headers)?;
+
+        let v = self.s2v(headers, plaintext);
+        let mut sealed = v.to_vec();
+        sealed.extend(self.ctr(&v, plaintext));
+
+        Ok(sealed)
+    }
+
+    /// Verify and decrypt the output of `encrypt` with the same headers.
+    ///
+    /// Fails closed: on a mismatch no plaintext is returned at all.
+    pub fn decrypt(
+        &self, 
+        headers: &[&[u8]], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, SivError> {
+        check_headers(headers)?;
+        if sealed.len() < TAG_SIZE {
+            let len = sealed.len();
+            return Err(SivError::CiphertextTooShort { len });
+        }
+
+        let (v, ciphertext) = sealed.split_at(TAG_SIZE);
+        let mut iv = [0u8; TAG_SIZE];
+        iv.copy_from_slice(v);
+
+        let plaintext = self.ctr(&iv, ciphertext);
+        if !ct_eq(&self.s2v(headers, &plaintext), v) {
+            return Err(SivError::AuthenticationFailed);
+        }
+
+        Ok(plaintext)
+    }
+
+    /// Nonce-based encryption: S2V over `aad`, `nonce` and `plaintext`.
+    pub fn seal(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, SivError> {
+        self.encrypt(&[aad, nonce], plaintext)
+    }
+
+    /// Verify and decrypt the output of `seal`.
+    pub fn open(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, SivError> {
+        self.decrypt(&[aad, nonce], sealed)
+    }
+
+    // S2V: D = CMAC(0^128), D = dbl(D) ^ CMAC(S_i) for every header, then
+    // CMAC of the plaintext with D xored onto its end (xorend), or of
+    // dbl(D) ^ pad(plaintext) if it is shorter than a block.
+    fn s2v(&self, headers: &[&[u8]], plaintext: &[u8]) -> [u8; TAG_SIZE] {
+        let mut d = self.cmac(&[0u8; BLOCK_SIZE]);
+        for header in headers {
+            let mac = self.cmac(header);
+            d = dbl(&d);
+            for i in 0..BLOCK_SIZE {
+                d[i] ^= mac[i];
+            }
+        }
+
+        let mut mac = self.mac.clone();
+        if plaintext.len() >= BLOCK_SIZE {
+            let (head, last) = plaintext.split_at(plaintext.len() - BLOCK_SIZE);
+            mac.update(head);
+            for i in 0..BLOCK_SIZE {
+                d[i] ^= last[i];
+            }
+        } else {
+            d = dbl(&d);
+            for i in 0..plaintext.len() {
+                d[i] ^= plaintext[i];
+            }
+            d[plaintext.len()] ^= 0x80;
+        }
+        mac.update(&d);
+
+        mac.finalize()
+    }
+
+    fn cmac(&self, data: &[u8]) -> [u8; TAG_SIZE] {
+        let mut mac = self.mac.clone();
+        mac.update(data);
+        mac.finalize()
+    }
+
+    // CTR with the full 128-bit counter Q = V & 1^64 0 1^31 0 1^31.
+    fn ctr(&self, v: &[u8; TAG_SIZE], data: &[u8]) -> Vec<u8> {
+        let mut q = *v;
+        q[8] &= 0x7f;
+        q[12] &= 0x7f;
+
+        let mut ctr = Ctr::new(self.aes.clone(), &q, BLOCK_SIZE)
+            .expect("the synthetic IV is one block");
+        ctr.update(data)
+            .expect("a 128-bit counter does not run out")
+    }
+}
+
+impl Aead for AesSiv {
+    type Error = SivError;
+
+    fn tag_size(&self) -> usize {
+        TAG_SIZE
+    }
+
+    fn seal(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        plaintext: &[u8]
+    ) -> Result<Vec<u8>, SivError> {
+        AesSiv::seal(self, nonce, aad, plaintext)
+    }
+
+    fn open(
+        &self, 
+        nonce: &[u8], 
+        aad: &[u8], 
+        sealed: &[u8]
+    ) -> Result<Vec<u8>, SivError> {
+        AesSiv::open(self, nonce, aad, sealed)
+    }
+}
+
+/// AES-SIV: nonce-based encryption, returns V || ciphertext.
+pub fn seal(
+    key: &[u8], 
+    nonce: &[u8], 
+    aad: &[u8], 
+    plaintext: &[u8]
+) -> Result<Vec<u8>, SivError> {
+    AesSiv::new(key)?.seal(nonce, aad, plaintext)
+}
+
+/// AES-SIV: verify and decrypt V || ciphertext.
+pub fn open(
+    key: &[u8], 
+    nonce: &[u8], 
+    aad: &[u8], 
+    sealed: &[u8]
+) -> Result<Vec<u8>, SivError> {
+    AesSiv::new(key)?.open(nonce, aad, sealed)
+}
+
+fn check_headers(headers: &[&[u8]]) -> Result<(), SivError> {
+    if headers.len() > MAX_HEADERS {
+        return Err(SivError::TooManyHeaders { count: headers.len() });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sha3::{ bytes_to_hex, hex_to_bytes };
+
+    // RFC 5297 appendix A.1, deterministic use with one header.
+    #[test]
+    fn siv_rfc5297_a_1() {
+        let key = hex_to_bytes(b"fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0\
+                                 f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");
+        let ad = hex_to_bytes(b"101112131415161718191a1b1c1d1e1f\
+                                2021222324252627");
+        let plaintext = hex_to_bytes(b"112233445566778899aabbccddee");
+        let siv = AesSiv::new(&key).unwrap();
+
+        let sealed = siv.encrypt(&[&ad], &plaintext).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&sealed)),
+            (b"85632d07c6e8f37f950acd320a2ecc93\
+               40c02b9690c4dc04daef7f6afe5c")
+        );
+
+        let opened = siv.decrypt(&[&ad], &sealed).unwrap();
+        assert_eq!((opened), (plaintext));
+    }
+
+    // RFC 5297 appendix A.2, two headers and a nonce.
+    #[test]
+    fn siv_rfc5297_a_2() {
+        let key = hex_to_bytes(b"7f7e7d7c7b7a79787776757473727170\
+                                 404142434445464748494a4b4c4d4e4f");
+        let ad1 = hex_to_bytes(b"00112233445566778899aabbccddeeff\
+                                 deaddadadeaddadaffeeddccbbaa9988\
+                                 7766554433221100");
+        let ad2 = hex_to_bytes(b"102030405060708090a0");
+        let nonce = hex_to_bytes(b"09f911029d74e35bd84156c5635688c0");
+        let plaintext = b"this is some plaintext to encrypt using SIV-AES";
+        let siv = AesSiv::new(&key).unwrap();
+
+        let sealed = siv.encrypt(&[&ad1, &ad2, &nonce], plaintext).unwrap();
+        assert_eq!(
+            (bytes_to_hex(&sealed)),
+            (b"7bdb6e3b432667eb06f4d14bff2fbd0f\
+               cb900f2fddbe404326601965c889bf17\
+               dba77ceb094fa663b7a3f748ba8af829\
+               ea64ad544a272e9c485b62a3fd5c0d".to_vec())
+        );
+
+        let opened = siv.decrypt(&[&ad1, &ad2, &nonce], &sealed).unwrap();
+        assert_eq!((opened), (plaintext.to_vec()));
+    }
+
+    #[test]
+    fn seal_puts_the_nonce_after_the_aad() {
+        for key_len in [32, 48, 64] {
+            let key: Vec<u8> = (0..key_len as u8).collect();
+            let siv = AesSiv::new(&key).unwrap();
+
+            let sealed = seal(&key, b"nonce", b"ad", b"hello").unwrap();
+            let expected = siv.encrypt(&[b"ad", b"nonce"], b"hello");
+            assert_eq!((sealed), (expected.unwrap()));
+
+            let opened = open(&key, b"nonce", b"ad", &sealed).unwrap();
+            assert_eq!((opened), (b"hello".to_vec()));
+        }
+    }
+
+    // Checked against another AES-SIV implementation.
+    #[test]
+    fn siv_384_and_512() {
+        let key_384: Vec<u8> = (0..48).collect();
+        let sealed = seal(&key_384, b"nonce", b"ad", b"hello").unwrap();
+        assert_eq!(
+            (bytes_to_hex(&sealed)),
+            (b"d56ea3e65032e4b5a5de34eb26128be7\
+               35cee67ce6".to_vec())
+        );
+
+        let key_512: Vec<u8> = (0..64).collect();
+        let sealed = seal(&key_512, b"nonce", b"ad", b"hello").unwrap();
+        assert_eq!(
+            (bytes_to_hex(&sealed)),
+            (b"975daae83091aeb97bf77c4828eff93c\
+               e2b4c93397".to_vec())
+        );
+    }
+
+    #[test]
+    fn siv_open_fails_closed_on_any_change() {
+        let siv = AesSiv::new(&[0x42u8; 32]).unwrap();
+        let sealed = siv.seal(b"nonce", b"ad", &[0x5au8; 40]).unwrap();
+        let failed = Err(SivError::AuthenticationFailed);
+
+        for i in [0, 15, 16, sealed.len() - 1] {
+            let mut bad = sealed.clone();
+            bad[i] ^= 0x01;
+            assert_eq!((siv.open(b"nonce", b"ad", &bad)), (failed));
+        }
+
+        assert_eq!((siv.open(b"nonce", b"AD", &sealed)), (failed));
+        assert_eq!((siv.open(b"Nonce", b"ad", &sealed)), (failed));
+        assert_eq!((siv.decrypt(&[b"ad"], &sealed)), (failed));
+    }
+
+    #[test]
+    fn siv_rejects_bad_inputs() {
+        let expected = Some(SivError::InvalidKeyLength { len: 16 });
+        assert_eq!((AesSiv::new(&[0u8; 16]).err()), (expected));
+
+        let siv = AesSiv::new(&[0u8; 32]).unwrap();
+        let headers: Vec<&[u8]> = vec![b""; 127];
+        let expected = Err(SivError::TooManyHeaders { count: 127 });
+        assert_eq!((siv.encrypt(&headers, b"")), (expected));
+
+        let expected = Err(SivError::CiphertextTooShort { len: 15 });
+        assert_eq!((siv.decrypt(&[], &[0u8; 15])), (expected));
+    }
+}
@@ -15,6 +15,7 @@ pub fn parity(x: u32, y: u32, z: u32) -> u32 { x ^ y ^ z }
 /// Rotate right (ROTR).
 /// 
 /// Rotate x right by n bits within a 32-bit word (wraps bits around).
+#[allow(clippy::manual_rotate)]
 pub fn rotr(x: u32, n: u32) -> u32 {
     // Normalize 0..31.
     let n = n & 31;
@@ -25,6 +26,7 @@ pub fn rotr(x: u32, n: u32) -> u32 {
 /// Rotate left (ROTL).
 ///
 /// Rotate x left by n bits within a 32-bit word (wraps bits around).
+#[allow(clippy::manual_rotate)]
 pub fn rotl(x: u32, n: u32) -> u32 {
     // Normalize 0..31.
     let n = n & 31;
@@ -96,6 +98,34 @@ pub fn small_sigma1(x: u32) -> u32 {
     rotr(x, 17) ^ rotr(x, 19) ^ shr(x, 10)
 }
 
+/// Keep the leftmost `N` bytes of a digest, as a fixed-size array.
+///
+/// Truncated hashes use it to cut the output of the full-width context
+/// down to size.
+pub fn truncate<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(&bytes[..N]);
+    out
+}
+
+/// Constant-time equality of two byte strings.
+///
+/// Every byte is compared no matter where the first difference is, so the
+/// running time only depends on the (public) lengths. Used to check MAC
+/// tags.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut diff = 0u8;
+    for i in 0..a.len() {
+        diff |= a[i] ^ b[i];
+    }
+
+    std::hint::black_box(diff) == 0
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -230,4 +260,20 @@ mod test {
 
         assert_eq!((result), (expected));
     }
-}   
\ No newline at end of file
+
+    #[test]
+    fn truncate_keeps_the_leftmost_bytes() {
+        let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+        let result: [u8; 4] = truncate(&bytes);
+
+        assert_eq!((result), ([0x01, 0x02, 0x03, 0x04]));
+    }
+
+    #[test]
+    fn ct_eq_compares_whole_slices() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(b"tag", b"tag"));
+        assert!(!ct_eq(b"tag", b"tah"));
+        assert!(!ct_eq(b"tag", b"tags"));
+    }
+}
@@ -1,4 +1,4 @@
-/// All functions work on unsigned 64-bit words.
+//! All functions work on unsigned 64-bit words.
 
 /// Wrapping add (MOD 2^64).
 pub fn z64(x: u64, y: u64) -> u64 {
@@ -12,6 +12,7 @@ pub fn shr64(x: u64, n: u64) -> u64 {
 
 /// Rotate right, shifts bits of 'x' outside of right scope LSB,
 /// and appends at MSB (Wraps bits around).
+#[allow(clippy::manual_rotate)]
 pub fn rotr64(x: u64, n: u64) -> u64 {
     let n = n & 63;
     (x >> n) | (x << (64 - n))
@@ -19,6 +20,7 @@ pub fn rotr64(x: u64, n: u64) -> u64 {
 
 /// Rotate left, shifts bits of 'x' outside of left scope MSB,
 /// and appends at LSB (Wraps bits around).
+#[allow(clippy::manual_rotate)]
 pub fn rotl64(x: u64, n: u64) -> u64 {
     let n = n & 63;
     (x << n) | (x >> (64 - n))